@@ -0,0 +1,5 @@
+moddef::moddef!(
+    pub mod {
+        nth
+    }
+);
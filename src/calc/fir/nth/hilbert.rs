@@ -0,0 +1,51 @@
+use core::f64::consts::PI;
+
+use crate::{f, filters::fir::{kaiser_beta, kaiser_hilbert}, param::{FilterFloat, Transition}};
+
+pub struct FirHilbertCalc<F>
+where
+    F: FilterFloat
+{
+    transition: F
+}
+
+impl<F> FirHilbertCalc<F>
+where
+    F: FilterFloat
+{
+    /// The transition is clamped to the range documented on [`Transition`].
+    pub fn new(transition: Transition<F>) -> Self
+    {
+        let Transition {transition} = transition;
+        Self {
+            transition: transition.max(F::epsilon())
+                .min(f!(0.25))
+        }
+    }
+
+    /// The stopband attenuation `A` of a Kaiser-windowed low-pass filter with the same order and transition width, in dB. The magnitude ripple in the band is
+    /// of the order of `10^(-A/20)`.
+    pub fn attenuation(&self, order: usize) -> f64
+    {
+        // The ideal response jumps from j to -j at DC, so the transition band is twice as wide as the distance to the band edge
+        2.285*4.0*PI*f!(self.transition; f64)*order as f64 + 8.0
+    }
+
+    /// Writes the taps of the in-phase output into `b`, which is a pure delay to the center of the taps. The order of the filter is one less than the length of `b`.
+    pub fn b_in_phase(&self, b: &mut [F])
+    {
+        let c = b.len()/2;
+        for (n, b) in b.iter_mut()
+            .enumerate()
+        {
+            *b = if n == c {F::one()} else {F::zero()}
+        }
+    }
+
+    /// Writes the taps of the quadrature output into `b`. The order of the filter is one less than the length of `b`.
+    pub fn b_quadrature(&self, b: &mut [F])
+    {
+        let order = b.len().saturating_sub(1);
+        kaiser_hilbert(b, kaiser_beta(self.attenuation(order)))
+    }
+}
\ No newline at end of file
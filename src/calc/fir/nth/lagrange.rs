@@ -0,0 +1,37 @@
+use crate::{f, param::{Delay, FilterFloat}};
+
+pub struct LagrangeCalc<F>
+where
+    F: FilterFloat
+{
+    delay: F
+}
+
+impl<F> LagrangeCalc<F>
+where
+    F: FilterFloat
+{
+    pub fn new(delay: Delay<F>) -> Self
+    {
+        let Delay {delay} = delay;
+        Self {
+            delay
+        }
+    }
+
+    /// Writes the taps into `b`. The order of the filter is one less than the length of `b`.
+    pub fn b(&self, b: &mut [F])
+    {
+        let order = b.len().saturating_sub(1);
+        let d = self.delay;
+
+        // hₙ = Π(D - k)/(n - k), k ≠ n
+        for (n, b) in b.iter_mut()
+            .enumerate()
+        {
+            *b = (0..=order).filter(|&k| k != n)
+                .map(|k| (d - f!(k))/(f!(n) - f!(k)))
+                .fold(F::one(), |b, t| b*t)
+        }
+    }
+}
@@ -0,0 +1,6 @@
+moddef::moddef!(
+    flat(pub) mod {
+        hilbert for cfg(feature = "hilbert"),
+        lagrange for cfg(feature = "lagrange")
+    }
+);
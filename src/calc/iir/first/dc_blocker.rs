@@ -0,0 +1,45 @@
+use crate::param::{FilterFloat, PoleRadius};
+
+pub struct DcBlockerCalc<F>
+where
+    F: FilterFloat
+{
+    radius: F
+}
+
+impl<F> DcBlockerCalc<F>
+where
+    F: FilterFloat
+{
+    pub fn new(pole_radius: PoleRadius<F>) -> Self
+    {
+        let PoleRadius {radius} = pole_radius;
+        Self {
+            radius
+        }
+    }
+
+    pub fn b(&self) -> [F; 2]
+    {
+        [
+            F::one(),
+            -F::one()
+        ]
+    }
+    pub fn b_compensated(&self) -> [F; 2]
+    {
+        let g = (F::one() + self.radius)/(F::one() + F::one());
+        [
+            g,
+            -g
+        ]
+    }
+
+    pub fn a(&self) -> [F; 2]
+    {
+        [
+            F::one(),
+            -self.radius
+        ]
+    }
+}
\ No newline at end of file
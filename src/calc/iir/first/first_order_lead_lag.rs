@@ -0,0 +1,45 @@
+use crate::param::{FilterFloat, ZeroPole};
+
+pub struct FirstOrderLeadLagCalc<F>
+where
+    F: FilterFloat
+{
+    zero: F,
+    pole: F,
+    gain: F,
+    two_rate: F
+}
+
+impl<F> FirstOrderLeadLagCalc<F>
+where
+    F: FilterFloat
+{
+    pub fn new(zero_pole: ZeroPole<F>, rate: F) -> Self
+    {
+        let ZeroPole {zero, pole, gain} = zero_pole;
+        let two_rate = rate + rate;
+        Self {
+            zero,
+            pole,
+            gain,
+            two_rate
+        }
+    }
+
+    pub fn b(&self) -> [F; 2]
+    {
+        let gain_pole = self.gain*self.pole;
+        [
+            gain_pole*(self.zero + self.two_rate),
+            gain_pole*(self.zero - self.two_rate)
+        ]
+    }
+
+    pub fn a(&self) -> [F; 2]
+    {
+        [
+            self.zero*(self.pole + self.two_rate),
+            self.zero*(self.pole - self.two_rate)
+        ]
+    }
+}
\ No newline at end of file
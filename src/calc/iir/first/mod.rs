@@ -2,10 +2,12 @@ use num::Float;
 
 moddef::moddef!(
     flat(pub) mod {
+        dc_blocker for cfg(feature = "dc_blocker"),
         first_order_all_pass for cfg(feature = "first_order_all_pass"),
         first_order_lr for cfg(feature = "first_order_lr"),
         first_order_rc for cfg(feature = "first_order_rc"),
         first_order for cfg(feature = "first_order"),
+        first_order_lead_lag for cfg(feature = "lead_lag"),
         pi for cfg(feature = "pi")
 ,    }
 );
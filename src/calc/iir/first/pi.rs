@@ -1,43 +1,43 @@
-use crate::param::{FilterFloat, PI};
+use crate::param::{Discretization, FilterFloat, PI};
 
 pub struct PICalc<F>
 where
     F: FilterFloat
 {
-    two_rate: F,
-    two_rate_p: F,
-    i: F
+    b: [F; 2],
+    a: [F; 2]
 }
 
 impl<F> PICalc<F>
 where
     F: FilterFloat
 {
-    pub fn new(pi: PI<F>, rate: F) -> Self
+    pub fn new(pi: PI<F>, rate: F, discretization: Discretization) -> Self
     {
         let PI {p, i} = pi;
-        let two_rate = rate + rate;
-        let two_rate_p = two_rate*p;
+
+        // I/s = I(α + βz⁻¹)/(k(1 - z⁻¹))
+        let (k, alpha, beta) = discretization.integral(rate);
+        let kp = k*p;
         Self {
-            two_rate,
-            two_rate_p,
-            i
+            b: [
+                kp + i*alpha,
+                i*beta - kp
+            ],
+            a: [
+                k,
+                -k
+            ]
         }
     }
 
     pub fn b(&self) -> [F; 2]
     {
-        [
-            self.i + self.two_rate_p,
-            self.i - self.two_rate_p
-        ]
+        self.b
     }
 
     pub fn a(&self) -> [F; 2]
     {
-        [
-            self.two_rate,
-            -self.two_rate
-        ]
+        self.a
     }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use num::Float;
 
 moddef::moddef!(
     flat(pub) mod {
+        moog_ladder for cfg(feature = "moog_ladder"),
         wah for cfg(feature = "wah")
     }
 );
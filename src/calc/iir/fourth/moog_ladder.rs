@@ -0,0 +1,70 @@
+use crate::{f, param::{FilterFloat, OmegaResonance}};
+
+pub struct MoogLadderCalc<F>
+where
+    F: FilterFloat
+{
+    g: F,
+    feedback: F
+}
+
+impl<F> MoogLadderCalc<F>
+where
+    F: FilterFloat
+{
+    pub fn new(omega_resonance: OmegaResonance<F>, rate: F) -> Self
+    {
+        let OmegaResonance {omega, resonance} = omega_resonance;
+        Self {
+            g: (omega/(rate + rate)).tan(),
+            feedback: f!(4.0)*resonance
+        }
+    }
+
+    /// The prewarped integrator gain of each one-pole stage.
+    pub fn g(&self) -> F
+    {
+        self.g
+    }
+
+    /// The gain of the feedback path, from the output of the last stage to the input of the first.
+    pub fn feedback(&self) -> F
+    {
+        self.feedback
+    }
+
+    pub fn b(&self) -> [F; 5]
+    {
+        let g2 = self.g*self.g;
+        let g4 = g2*g2;
+        let four = f!(4.0);
+        let six = f!(6.0);
+        [
+            g4,
+            four*g4,
+            six*g4,
+            four*g4,
+            g4
+        ]
+    }
+
+    pub fn a(&self) -> [F; 5]
+    {
+        // ((1 + g) - (1 - g)z⁻¹)⁴ + 4k g⁴(1 + z⁻¹)⁴
+        let p = F::one() + self.g;
+        let m = self.g - F::one();
+        let p2 = p*p;
+        let m2 = m*m;
+        let four = f!(4.0);
+        let six = f!(6.0);
+        let [b0, b1, b2, b3, b4] = self.b();
+        let k = self.feedback;
+        [
+            p2*p2 + k*b0,
+            four*p2*p*m + k*b1,
+            six*p2*m2 + k*b2,
+            four*p*m2*m + k*b3,
+            m2*m2 + k*b4
+        ]
+    }
+}
\ No newline at end of file
@@ -1,4 +1,96 @@
-use crate::{f, param::{FilterFloat, WahFilterParam, X}};
+use core::marker::PhantomData;
+
+use crate::{f, param::{FilterFloat, WahCircuit, WahFilterParam, X}};
+
+/// Returns the component table made from the associated constants of the param, at no particular pedal position.
+const fn consts64<P>() -> WahCircuit<f64>
+where
+    P: WahFilterParam
+{
+    WahCircuit {
+        x: 0.0,
+        boost: 1.0,
+        r_i: P::R_I,
+        r_s: P::R_S,
+        r_p: P::R_P,
+        r_c1: P::R_C1,
+        r_e1: P::R_E1,
+        r_c2: P::R_C2,
+        r_e2: P::R_E2,
+        r_j: P::R_J,
+        r_g: P::R_G,
+        r_pot: P::R_POT,
+        v_cc: P::V_CC,
+        v_f: P::V_F,
+        v_t: P::V_T,
+        beta: P::BETA,
+        c_g: P::C_G,
+        c_i: P::C_I,
+        c_f: P::C_F,
+        l: P::L
+    }
+}
+
+fn circuit64<F>(circuit: &WahCircuit<F>) -> WahCircuit<f64>
+where
+    F: FilterFloat
+{
+    let WahCircuit {x, boost, r_i, r_s, r_p, r_c1, r_e1, r_c2, r_e2, r_j, r_g, r_pot, v_cc, v_f, v_t, beta, c_g, c_i, c_f, l} = *circuit;
+    WahCircuit {
+        x: f!(x; f64),
+        boost: f!(boost; f64),
+        r_i: f!(r_i; f64),
+        r_s: f!(r_s; f64),
+        r_p: f!(r_p; f64),
+        r_c1: f!(r_c1; f64),
+        r_e1: f!(r_e1; f64),
+        r_c2: f!(r_c2; f64),
+        r_e2: f!(r_e2; f64),
+        r_j: f!(r_j; f64),
+        r_g: f!(r_g; f64),
+        r_pot: f!(r_pot; f64),
+        v_cc: f!(v_cc; f64),
+        v_f: f!(v_f; f64),
+        v_t: f!(v_t; f64),
+        beta: f!(beta; f64),
+        c_g: f!(c_g; f64),
+        c_i: f!(c_i; f64),
+        c_f: f!(c_f; f64),
+        l: f!(l; f64)
+    }
+}
+
+/// Returns the quiescent collector current of Q1.
+const fn q1_bias64(circuit: &WahCircuit<f64>) -> f64
+{
+    let &WahCircuit {r_s, beta, r_e1, r_g, r_j, r_c1, r_e2, v_cc, v_f, ..} = circuit;
+
+    let z1 = r_s + beta*r_e1;
+
+    let s_g = 1.0/z1 + 1.0/r_g + 1.0/r_j;
+
+    let z2 = 1.0/z1/r_j/s_g - beta*(1.0 - z1*s_g)/z1/z1/s_g;
+    
+    let s_c1 = 1.0/r_c1 + 2.0/r_j + beta/z1/r_j/s_g - 1.0/r_j/r_j/s_g;
+    let s_b2 = 1.0/r_j + 1.0/beta/r_e2 - 1.0/r_j/r_j/s_c1;
+
+    let v_b2 = (v_cc/r_c1 + z2*v_f)/r_j/s_b2/s_c1 + v_f/r_e2/s_b2;
+    let v_c1 = v_cc/r_c1/s_c1 + v_b2/r_j/s_c1 + z2/s_c1*v_f;
+    let v_g = v_f/z1/s_g + v_c1/r_j/s_g;
+
+    beta*(v_g - v_f)/z1
+}
+
+/// Returns the small-signal gain and input conductance of Q1 at a collector current.
+const fn q1_small_signal64(circuit: &WahCircuit<f64>, i_c: f64) -> (f64, f64)
+{
+    let &WahCircuit {beta, r_e1, r_c1, v_t, ..} = circuit;
+
+    // Equal to `1/(Vₜ/I꜀ + Rₑ₁)`, but without dividing by zero when Q1 is cut off
+    let s = i_c/(v_t + r_e1*i_c);
+
+    (r_c1*s, s/beta)
+}
 
 struct WahConstCalc<F>
 where
@@ -17,48 +109,15 @@ where
 }
 impl WahConstCalc<f64>
 {
-    const fn new64<P>() -> WahConstCalc<f64>
-    where
-        P: WahFilterParam
+    const fn new64(circuit: &WahCircuit<f64>) -> WahConstCalc<f64>
     {
-        let r_s = P::R_S;
-        let beta = P::BETA;
-        let r_e1 = P::R_E1;
-        let r_g = P::R_G;
-        let r_j = P::R_J;
-        let r_c1 = P::R_C1;
-        let r_e2 = P::R_E2;
-        let v_cc = P::V_CC;
-        let v_f = P::V_F;
-        let v_t = P::V_T;
-        let c_i = P::C_I;
-        let l = P::L;
-        let r_i = P::R_I;
-        let r_p = P::R_P;
-        let c_f = P::C_F;
-
-        let z1 = r_s + beta*r_e1;
-
-        let s_g = 1.0/z1 + 1.0/r_g + 1.0/r_j;
-
-        let z2 = 1.0/z1/r_j/s_g - beta*(1.0 - z1*s_g)/z1/z1/s_g;
-        
-        let s_c1 = 1.0/r_c1 + 2.0/r_j + beta/z1/r_j/s_g - 1.0/r_j/r_j/s_g;
-        let s_b2 = 1.0/r_j + 1.0/beta/r_e2 - 1.0/r_j/r_j/s_c1;
-
-        let v_b2 = (v_cc/r_c1 + z2*v_f)/r_j/s_b2/s_c1 + v_f/r_e2/s_b2;
-        let v_c1 = v_cc/r_c1/s_c1 + v_b2/r_j/s_c1 + z2/s_c1*v_f;
-        let v_g = v_f/z1/s_g + v_c1/r_j/s_g;
-
-        let i_c1 = beta*(v_g - v_f)/z1;
-        let i_c2 = (v_b2 - v_f)/r_e2;
-
-        let _r_e1 = v_t/i_c1;
-        let _r_e2 = v_t/i_c2;
-
-        let g_q1 = r_c1/(_r_e1 + r_e1);
+        let (g_q1, s_q1) = q1_small_signal64(circuit, q1_bias64(circuit));
+        Self::from_q1_64(circuit, g_q1, s_q1)
+    }
 
-        let s_q1 = 1.0/beta/(_r_e1 + r_e1);
+    const fn from_q1_64(circuit: &WahCircuit<f64>, g_q1: f64, s_q1: f64) -> WahConstCalc<f64>
+    {
+        let &WahCircuit {boost, r_s, c_i, l, r_i, r_p, c_f, ..} = circuit;
 
         let a1 = r_s*c_i/l*(1.0 + r_i*s_q1) + s_q1*(1.0 + r_s/r_p) + r_i*c_i/l + 1.0/r_p;
         let a0 = (r_s*s_q1 + 1.0)/l;
@@ -73,7 +132,7 @@ impl WahConstCalc<f64>
         let a2a = (c_i*(1.0 + r_s/r_p) + c_f)*(1.0 + r_s*s_q1) + c_i*r_i/r_p;
         let a2b = c_f*g_q1;
 
-        let mg_q1 = -g_q1;
+        let mg_q1 = -g_q1*boost;
 
         Self {a0, a1, b1, b2, b3, a2a, a2b, a3a, a3b, mg_q1}
     }
@@ -82,11 +141,9 @@ impl<F> WahConstCalc<F>
 where
     F: FilterFloat
 {
-    pub fn new<P>() -> Self
-    where
-        P: WahFilterParam<F = F>
+    pub fn new(calc: WahConstCalc<f64>) -> Self
     {
-        let WahConstCalc {a0, a1, b1, b2, b3, a2a, a2b, a3a, a3b, mg_q1} = WahConstCalc::new64::<P>();
+        let WahConstCalc {a0, a1, b1, b2, b3, a2a, a2b, a3a, a3b, mg_q1} = calc;
         Self {
             a0: f!(a0),
             a1: f!(a1),
@@ -102,6 +159,16 @@ where
     }
 }
 
+struct WahConsts<P>(PhantomData<P>)
+where
+    P: WahFilterParam;
+impl<P> WahConsts<P>
+where
+    P: WahFilterParam
+{
+    const CALC: WahConstCalc<f64> = WahConstCalc::new64(&consts64::<P>());
+}
+
 pub struct WahCalc<F>
 where
     F: FilterFloat
@@ -124,15 +191,29 @@ where
     where
         P: WahFilterParam<F = F>
     {
-        let WahConstCalc {a0, a1, b1, b2, b3, a2a, a2b, a3a, a3b, mg_q1} = WahConstCalc::new::<P>();
-        let X {x} = param.x();
+        let (x, r_pot_per_r_j, consts) = if P::RUNTIME_CIRCUIT
+        {
+            let circuit = param.circuit();
+            (circuit.x, circuit.r_pot/circuit.r_j, WahConstCalc::new(WahConstCalc::new64(&circuit64(&circuit))))
+        }
+        else
+        {
+            let X {x} = param.x();
+            (x, f!(P::R_POT/P::R_J), WahConstCalc::new(WahConsts::<P>::CALC))
+        };
+        Self::from_consts(x, r_pot_per_r_j, consts, rate)
+    }
+
+    fn from_consts(x: F, r_pot_per_r_j: F, consts: WahConstCalc<F>, rate: F) -> Self
+    {
+        let WahConstCalc {a0, a1, b1, b2, b3, a2a, a2b, a3a, a3b, mg_q1} = consts;
 
         let two_rate = rate + rate;
         let four_rate = two_rate + two_rate;
         let four_rate2 = two_rate*two_rate;
         let eight_rate3 = four_rate2*two_rate;
         
-        let one_m_k_recip_plus_r_pot_per_r_j = (F::one() - x).recip() + f!(P::R_POT/P::R_J);
+        let one_m_k_recip_plus_r_pot_per_r_j = (F::one() - x).recip() + r_pot_per_r_j;
 
         let g_q2 = one_m_k_recip_plus_r_pot_per_r_j/(x.recip() + one_m_k_recip_plus_r_pot_per_r_j);
 
@@ -191,4 +272,50 @@ where
             self.four_a2_rate2_p_a0 - self.two_a1_rate_p_eight_a3_rate3
         ]
     }
+}
+
+/// Collector-emitter voltage of a saturated BJT.
+const V_CE_SAT: f64 = 0.2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WahNonlinearCalc
+{
+    circuit: WahCircuit<f64>,
+    i_c1: f64,
+    i_sat: f64,
+    r_c1_boost: f64
+}
+impl WahNonlinearCalc
+{
+    pub fn new<F>(circuit: &WahCircuit<F>) -> Self
+    where
+        F: FilterFloat
+    {
+        let circuit = circuit64(circuit);
+        let WahCircuit {boost, r_c1, r_e1, v_cc, ..} = circuit;
+
+        Self {
+            circuit,
+            i_c1: q1_bias64(&circuit),
+            i_sat: (v_cc - V_CE_SAT)/(r_c1 + r_e1),
+            r_c1_boost: r_c1*boost
+        }
+    }
+
+    /// Returns the filter linearized around the collector current of Q1 that gives the output `y`.
+    ///
+    /// The output is taken as the collector swing of Q1, amplified by the boost, like in the linear model. The collector current is limited to zero where Q1
+    /// cuts off, and to what the supply can drive where it saturates. Q1 has no gain in either.
+    pub fn calc<F>(&self, y: F, rate: F) -> WahCalc<F>
+    where
+        F: FilterFloat
+    {
+        let WahCircuit {x, r_pot, r_j, ..} = self.circuit;
+
+        let i_c = (self.i_c1 - f!(y; f64)/self.r_c1_boost).clamp(0.0, self.i_sat);
+        let (g_q1, s_q1) = q1_small_signal64(&self.circuit, i_c);
+        let g_q1 = if i_c < self.i_sat {g_q1} else {0.0};
+
+        WahCalc::from_consts(f!(x), f!(r_pot/r_j), WahConstCalc::new(WahConstCalc::from_q1_64(&self.circuit, g_q1, s_q1)), rate)
+    }
 }
\ No newline at end of file
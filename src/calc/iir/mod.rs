@@ -4,6 +4,7 @@ moddef::moddef!(
         second,
         third,
         fourth,
-        fifth
+        fifth,
+        nth
     }
 );
\ No newline at end of file
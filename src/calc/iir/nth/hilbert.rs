@@ -0,0 +1,105 @@
+use core::f64::consts::PI;
+
+use num::Float;
+
+use crate::{f, param::{FilterFloat, Transition}};
+
+pub struct HilbertCalc<F>
+where
+    F: FilterFloat
+{
+    k: f64,
+    q: f64,
+    phantom: core::marker::PhantomData<F>
+}
+
+impl<F> HilbertCalc<F>
+where
+    F: FilterFloat
+{
+    const TRANSITION_MIN: f64 = f64::EPSILON;
+    const TRANSITION_MAX: f64 = 0.25;
+    const MAX_TERMS: i32 = 64;
+
+    /// The transition is clamped to the range documented on [`Transition`]. A NaN gives the narrowest transition.
+    pub fn new(transition: Transition<F>) -> Self
+    {
+        let Transition {transition} = transition;
+        let transition = f!(transition; f64);
+        let transition = if transition.is_nan() {Self::TRANSITION_MIN} else {transition.clamp(Self::TRANSITION_MIN, Self::TRANSITION_MAX)};
+
+        // Selectivity and nome of the equivalent elliptic half-band filter
+        let k = Float::tan((1.0 - transition*2.0)*PI/4.0);
+        let k = k*k;
+        let kk = Float::powf(1.0 - k*k, 0.25);
+        let e = 0.5*(1.0 - kk)/(1.0 + kk);
+        let e4 = e*e*e*e;
+        let q = e*(1.0 + e4*(2.0 + e4*(15.0 + 150.0*e4)));
+        Self {
+            k,
+            q,
+            phantom: core::marker::PhantomData
+        }
+    }
+
+    /// Writes the all-pass coefficients of both chains into `c`, in increasing order.
+    ///
+    /// The even coefficients belong to the leading chain, and the odd coefficients to the lagging chain. This is done in [`f64`] regardless of `F`, since the
+    /// series converge very slowly in low precision.
+    pub fn c(&self, c: &mut [F])
+    {
+        let Self {k, q, ..} = *self;
+        let order = 2.0*c.len() as f64 + 1.0;
+        for (i, c) in c.iter_mut()
+            .enumerate()
+        {
+            let m = i as f64 + 1.0;
+            let num = Self::theta(|i| Float::powi(q, i*(i + 1))*Float::sin(f!(2*i + 1; f64)*m*PI/order), 0)*Float::powf(q, 0.25);
+            let den = Self::theta(|i| -Float::powi(q, i*i)*Float::cos(f!(2*i; f64)*m*PI/order), 1) + 0.5;
+            let ww = num/den;
+            let ww2 = ww*ww;
+            let x = Float::sqrt((1.0 - ww2*k)*(1.0 - ww2/k))/(1.0 + ww2);
+            *c = f!((1.0 - x)/(1.0 + x))
+        }
+    }
+
+    /// Sums the alternating series `t(i) - t(i + 1) + t(i + 2) - ...` until the terms vanish.
+    ///
+    /// The nome is below one for any valid transition, so this takes a couple dozen terms at most, but the number of terms is capped regardless.
+    fn theta(t: impl Fn(i32) -> f64, i0: i32) -> f64
+    {
+        let mut acc = 0.0;
+        let mut sign = 1.0;
+        for i in i0..i0 + Self::MAX_TERMS
+        {
+            let t = sign*t(i);
+            acc += t;
+            sign = -sign;
+            if t.abs() <= 1e-100
+            {
+                break
+            }
+        }
+        acc
+    }
+
+    /// Numerator of one all-pass section, `c - z⁻²`.
+    pub fn b(c: F) -> [F; 3]
+    {
+        [
+            c,
+            F::zero(),
+            -F::one()
+        ]
+    }
+
+    /// Denominator of one all-pass section, `1 - cz⁻²`.
+    pub fn a(c: F) -> [F; 3]
+    {
+        [
+            F::one(),
+            F::zero(),
+            -c
+        ]
+    }
+}
\ No newline at end of file
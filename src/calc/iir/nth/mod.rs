@@ -0,0 +1,7 @@
+moddef::moddef!(
+    flat(pub) mod {
+        hilbert for cfg(feature = "hilbert"),
+        phaser for cfg(feature = "phaser"),
+        thiran for cfg(feature = "thiran")
+    }
+);
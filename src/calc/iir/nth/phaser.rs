@@ -0,0 +1,41 @@
+use num::Float;
+
+use crate::{f, param::{FilterFloat, Tau, TauSpreadMix}};
+
+pub struct PhaserCalc<F>
+where
+    F: FilterFloat
+{
+    tau: F,
+    spread: F
+}
+
+impl<F> PhaserCalc<F>
+where
+    F: FilterFloat
+{
+    pub fn new(tau_spread_mix: TauSpreadMix<F>) -> Self
+    {
+        let TauSpreadMix {tau, spread, mix: _} = tau_spread_mix;
+        Self {
+            tau,
+            spread
+        }
+    }
+
+    /// The time constant of stage `k` out of `n`, spread geometrically around `tau`.
+    pub fn tau(&self, k: usize, n: usize) -> Tau<F>
+    {
+        let exponent = if n > 1
+        {
+            f!(k)/f!(n - 1) - f!(0.5)
+        }
+        else
+        {
+            F::zero()
+        };
+        Tau {
+            tau: self.tau*Float::powf(self.spread, exponent)
+        }
+    }
+}
\ No newline at end of file
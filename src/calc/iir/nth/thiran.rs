@@ -0,0 +1,52 @@
+use crate::{f, param::{Delay, FilterFloat}};
+
+pub struct ThiranCalc<F>
+where
+    F: FilterFloat
+{
+    delay: F
+}
+
+impl<F> ThiranCalc<F>
+where
+    F: FilterFloat
+{
+    pub fn new(delay: Delay<F>) -> Self
+    {
+        let Delay {delay} = delay;
+        Self {
+            delay
+        }
+    }
+
+    /// Writes the numerator coefficients into `b`. The order of the filter is one less than the length of `b`.
+    pub fn b(&self, b: &mut [F])
+    {
+        self.a(b);
+        b.reverse()
+    }
+
+    /// Writes the denominator coefficients into `a`. The order of the filter is one less than the length of `a`.
+    pub fn a(&self, a: &mut [F])
+    {
+        let order = a.len().saturating_sub(1);
+        let n = f!(order);
+        let d = self.delay;
+
+        // aₖ = (-1)ᵏ(N k)Π(D - N + i)/(D - N + k + i)
+        let mut binomial = F::one();
+        for (k, a) in a.iter_mut()
+            .enumerate()
+        {
+            let k_f = f!(k);
+            if k > 0
+            {
+                binomial = -binomial*(n - k_f + F::one())/k_f;
+            }
+            *a = (0..=order).map(|i| {
+                    let i = f!(i);
+                    (d - n + i)/(d - n + k_f + i)
+                }).fold(binomial, |a, t| a*t)
+        }
+    }
+}
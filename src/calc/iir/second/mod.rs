@@ -3,10 +3,12 @@ use num::Float;
 moddef::moddef!(
     flat(pub) mod {
         pid for cfg(feature = "pid"),
+        second_order_all_pass for cfg(feature = "second_order_all_pass"),
         second_order_bessel for cfg(feature = "second_order_bessel"),
         second_order_chebyshev1 for cfg(feature = "second_order_chebyshev1"),
         second_order_chebyshev2 for cfg(feature = "second_order_chebyshev2"),
         second_order_elliptic for cfg(feature = "second_order_elliptic"),
+        second_order_lead_lag for cfg(feature = "lead_lag"),
         second_order_rc for cfg(feature = "second_order_rc"),
         second_order_rlc for cfg(feature = "second_order_rlc"),
         second_order_sallen_key for cfg(feature = "second_order_sallen_key"),
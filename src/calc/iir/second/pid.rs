@@ -1,51 +1,49 @@
-use crate::param::{FilterFloat, PID};
+use crate::param::{Discretization, FilterFloat, PIDF};
 
 pub struct PIDCalc<F>
 where
     F: FilterFloat
 {
-    four_rate2_d_p_i: F,
-    two_i_m_eight_rate2_d: F,
-    two_rate_p: F,
-    two_rate: F
+    b: [F; 3],
+    a: [F; 3]
 }
 
 impl<F> PIDCalc<F>
 where
     F: FilterFloat
 {
-    pub fn new(pid: PID<F>, rate: F) -> Self
+    pub fn new(pidf: PIDF<F>, rate: F, discretization: Discretization) -> Self
     {
-        let PID {p, i, d} = pid;
-        let two_rate = rate + rate;
-        let two_rate_p = two_rate*p;
-        let four_rate2_d = two_rate*two_rate*d;
-        let four_rate2_d_p_i = four_rate2_d + i;
-        let i_m_four_rate2_d = i - four_rate2_d;
-        let two_i_m_eight_rate2_d = i_m_four_rate2_d + i_m_four_rate2_d;
+        let PIDF {p, i, d, tf} = pidf;
+
+        // I/s = I(α + βz⁻¹)/(k(1 - z⁻¹))
+        let (k_i, alpha_i, beta_i) = discretization.integral(rate);
+        // Ds/(1 + sTf) = Dk(1 - z⁻¹)/(c₀ + c₁z⁻¹)
+        let (k_d, alpha_d, beta_d) = discretization.derivative(rate, tf);
+        let c0 = alpha_d + k_d*tf;
+        let c1 = beta_d - k_d*tf;
+
+        // Everything over the common denominator k(1 - z⁻¹)(c₀ + c₁z⁻¹)
+        let a = [c0, c1 - c0, -c1].map(|a| a*k_i);
+        let kd = k_i*k_d*d;
+        let b = [
+            a[0]*p + i*alpha_i*c0 + kd,
+            a[1]*p + i*(alpha_i*c1 + beta_i*c0) - (kd + kd),
+            a[2]*p + i*beta_i*c1 + kd
+        ];
         Self {
-            four_rate2_d_p_i,
-            two_i_m_eight_rate2_d,
-            two_rate_p,
-            two_rate
+            b,
+            a
         }
     }
 
     pub fn b(&self) -> [F; 3]
     {
-        [
-            self.four_rate2_d_p_i + self.two_rate_p,
-            self.two_i_m_eight_rate2_d,
-            self.four_rate2_d_p_i - self.two_rate_p,
-        ]
+        self.b
     }
 
     pub fn a(&self) -> [F; 3]
     {
-        [
-            self.two_rate,
-            F::zero(),
-            -self.two_rate
-        ]
+        self.a
     }
 }
\ No newline at end of file
@@ -0,0 +1,50 @@
+use crate::param::{FilterFloat, OmegaZeta};
+
+pub struct SecondOrderAllPassCalc<F>
+where
+    F: FilterFloat
+{
+    a0: F,
+    a1: F,
+    a2: F
+}
+
+impl<F> SecondOrderAllPassCalc<F>
+where
+    F: FilterFloat
+{
+    pub fn new(omega_zeta: OmegaZeta<F>, rate: F) -> Self
+    {
+        let OmegaZeta {omega, zeta} = omega_zeta;
+        let omega2 = omega*omega;
+        let two_omega2 = omega2 + omega2;
+        let two_rate = rate + rate;
+        let four_rate2 = two_rate*two_rate;
+        let eight_rate2 = four_rate2 + four_rate2;
+        let four_rate2_p_omega2 = four_rate2 + omega2;
+        let four_rate_zeta_omega = two_rate*zeta*(omega + omega);
+        Self {
+            a0: four_rate2_p_omega2 + four_rate_zeta_omega,
+            a1: two_omega2 - eight_rate2,
+            a2: four_rate2_p_omega2 - four_rate_zeta_omega
+        }
+    }
+
+    pub fn b(&self) -> [F; 3]
+    {
+        [
+            self.a2,
+            self.a1,
+            self.a0
+        ]
+    }
+
+    pub fn a(&self) -> [F; 3]
+    {
+        [
+            self.a0,
+            self.a1,
+            self.a2
+        ]
+    }
+}
\ No newline at end of file
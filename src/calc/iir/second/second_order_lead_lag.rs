@@ -0,0 +1,39 @@
+use crate::{calc::iir::first::FirstOrderLeadLagCalc, param::{FilterFloat, ZeroPole}};
+
+pub struct SecondOrderLeadLagCalc<F>
+where
+    F: FilterFloat
+{
+    sections: [FirstOrderLeadLagCalc<F>; 2]
+}
+
+impl<F> SecondOrderLeadLagCalc<F>
+where
+    F: FilterFloat
+{
+    pub fn new(zero_pole2: [ZeroPole<F>; 2], rate: F) -> Self
+    {
+        Self {
+            sections: zero_pole2.map(|zero_pole| FirstOrderLeadLagCalc::new(zero_pole, rate))
+        }
+    }
+
+    fn mul(p: [F; 2], q: [F; 2]) -> [F; 3]
+    {
+        [
+            p[0]*q[0],
+            p[0]*q[1] + p[1]*q[0],
+            p[1]*q[1]
+        ]
+    }
+
+    pub fn b(&self) -> [F; 3]
+    {
+        Self::mul(self.sections[0].b(), self.sections[1].b())
+    }
+
+    pub fn a(&self) -> [F; 3]
+    {
+        Self::mul(self.sections[0].a(), self.sections[1].a())
+    }
+}
\ No newline at end of file
@@ -1,4 +1,18 @@
-use crate::param::{FilterFloat, RC3GSallenKey};
+use crate::{param::{FilterFloat, RC3GSallenKey}, rtf::factor_sos};
+
+/// The filters sharing one of the denominators, split into a second-order section and a first-order output stage.
+///
+/// The section is shared, and each of the two filters gets its own numerator in the output stage.
+pub struct ThirdOrderSallenKeySection<F>
+where
+    F: FilterFloat
+{
+    pub b: [F; 3],
+    pub a: [F; 3],
+    pub b_low: [F; 2],
+    pub b_high: [F; 2],
+    pub a_output: [F; 2]
+}
 
 pub struct ThirdOrderSallenKeyCalc<F>
 where
@@ -122,4 +136,50 @@ where
 
         super::bilinear3_0_1_2_3(self.rate, p0, p1, p2, p3)
     }
+
+    pub fn sos_low(&self) -> ThirdOrderSallenKeySection<F>
+    {
+        let two = F::one() + F::one();
+        Self::section([F::one(), two, F::one()], self.b_low_low(), self.b_high_low(), self.a_low())
+    }
+    pub fn sos_band1(&self) -> ThirdOrderSallenKeySection<F>
+    {
+        Self::section([F::one(), F::zero(), -F::one()], self.b_low_band1(), self.b_high_band1(), self.a_band1())
+    }
+    pub fn sos_band2(&self) -> ThirdOrderSallenKeySection<F>
+    {
+        Self::section([F::one(), F::zero(), -F::one()], self.b_low_band2(), self.b_high_band2(), self.a_band2())
+    }
+    pub fn sos_high(&self) -> ThirdOrderSallenKeySection<F>
+    {
+        let two = F::one() + F::one();
+        Self::section([F::one(), -two, F::one()], self.b_low_high(), self.b_high_high(), self.a_high())
+    }
+
+    /// All the zeros are at DC or Nyquist, so they are placed analytically: the section gets the `zeros` both numerators have in common, and the
+    /// output stage gets the one that's left. Only the poles are found numerically, with [`factor_sos`].
+    fn section(zeros: [F; 3], b_low: [F; 4], b_high: [F; 4], a: [F; 4]) -> ThirdOrderSallenKeySection<F>
+    {
+        let zero = F::zero();
+        let one = F::one();
+
+        let ((_, _), ([a_sos], a_output)) = factor_sos::<F, 4, 1, 2>(&[one, zero, zero, zero], &a)
+            .unwrap_or_else(|| {
+                let nan = F::nan();
+                (([[nan; 3]], [nan; 2]), ([[nan; 3]], [nan; 2]))
+            });
+        // The gain of the denominator goes in the output stage, together with what's left of the numerators
+        let divide = |b: [F; 4]| {
+            let b0 = b[0]/a[0];
+            [b0, b[1]/a[0] - b0*zeros[1]]
+        };
+
+        ThirdOrderSallenKeySection {
+            b: zeros,
+            a: a_sos,
+            b_low: divide(b_low),
+            b_high: divide(b_high),
+            a_output
+        }
+    }
 }
\ No newline at end of file
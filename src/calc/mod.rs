@@ -1,5 +1,6 @@
 moddef::moddef!(
     pub mod {
+        fir,
         iir
     }
 );
\ No newline at end of file
@@ -0,0 +1,287 @@
+use array_trait::AsSlice;
+use num::{Complex, Float, One, Zero};
+
+use crate::{control::{Margins, margins}, f, rtf::Rtf};
+
+/// Time-domain characteristics of a step response, as computed by [`ClosedLoop::step_info`].
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StepInfo<F>
+where
+    F: Float
+{
+    /// The value the response ends on.
+    pub final_value: F,
+    /// The extreme value of the response in the direction of the step, which is the smallest value if the final value is negative.
+    pub peak: F,
+    /// How far the peak goes beyond the final value, relative to the final value.
+    ///
+    /// This is `None` if the final value is within the tolerance of zero, relative to the peak, like when the loop blocks DC. The overshoot means
+    /// nothing then.
+    pub overshoot: Option<F>,
+    /// The time, in seconds, it takes for the response to go from 10% to 90% of the final value, if it gets there.
+    pub rise_time: Option<F>,
+    /// The time, in seconds, after which the response stays within the tolerance of the final value.
+    pub settling_time: F,
+    /// The difference between the setpoint and the final value.
+    pub steady_state_error: F
+}
+
+/// A controller and a plant, connected in a feedback loop.
+///
+/// Every sample, the controller is fed the difference between the setpoint and the previous output of the plant, and the plant is fed the output of
+/// the controller. The one-sample delay in the feedback path makes the loop computable, like the sensor in a real digital control loop. The first
+/// output of both the controller and the plant is used.
+///
+/// <pre>
+///            C(z)P(z)
+/// T(z) = ---------------
+///        1 + z⁻¹C(z)P(z)
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use core::f64::consts::TAU;
+///
+/// use real_time_fir_iir_filters::{
+///     conf::LowPass,
+///     control::ClosedLoop,
+///     param::{OmegaZeta, PI},
+///     filters::iir::second::{PIDFilter, SecondOrderFilter}
+/// };
+///
+/// const RATE: f64 = 1000.0;
+///
+/// // A mass-spring-damper plant, resonating at 2 Hz
+/// let plant = SecondOrderFilter::<LowPass>::new(OmegaZeta {omega: 2.0*TAU, zeta: 0.2});
+/// let controller = PIDFilter::new(PI {p: 0.1, i: 1.0});
+///
+/// let mut closed_loop = ClosedLoop::new(controller, plant);
+/// let info = closed_loop.step_info(RATE, 1.0, 10000, 0.02);
+///
+/// // The integrator removes the steady-state error
+/// assert!(info.steady_state_error.abs() < 1e-3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClosedLoop<C, P>
+where
+    C: Rtf,
+    P: Rtf<F = C::F>
+{
+    pub controller: C,
+    pub plant: P,
+    measurement: C::F
+}
+
+impl<C, P> ClosedLoop<C, P>
+where
+    C: Rtf,
+    P: Rtf<F = C::F>
+{
+    pub fn new(controller: C, plant: P) -> Self
+    {
+        Self {
+            controller,
+            plant,
+            measurement: Zero::zero()
+        }
+    }
+
+    /// Runs the loop for a single sample with the given setpoint, and returns the output of the plant.
+    pub fn filter(&mut self, rate: C::F, setpoint: C::F) -> C::F
+    {
+        let error = setpoint - self.measurement;
+        let u = self.controller.filter(rate, error).as_slice()[0];
+        self.measurement = self.plant.filter(rate, u).as_slice()[0];
+        self.measurement
+    }
+
+    /// Returns the response of the closed loop, from the setpoint to the output of the plant, for a single z-plane point.
+    pub fn z_response(&mut self, rate: C::F, z: Complex<C::F>) -> Complex<C::F>
+    {
+        let open_loop = self.open_loop_z_response(rate, z);
+        open_loop/(open_loop/z + C::F::one())
+    }
+
+    /// Returns the response of the closed loop for a single frequency point, in radians.
+    pub fn frequency_response(&mut self, rate: C::F, omega: C::F) -> Complex<C::F>
+    {
+        self.z_response(rate, Complex::cis(omega))
+    }
+
+    /// Returns the response of the controller and the plant in series, without feedback, for a single z-plane point.
+    pub fn open_loop_z_response(&mut self, rate: C::F, z: Complex<C::F>) -> Complex<C::F>
+    {
+        self.controller.z_response(rate, z).as_slice()[0]*self.plant.z_response(rate, z).as_slice()[0]
+    }
+
+    /// Finds the stability margins of the loop as it runs, from the open-loop response `L = CPz⁻¹`, which includes the one-sample delay in the feedback
+    /// path. See [`series_margins`](crate::control::series_margins) for the margins without the delay.
+    pub fn margins(&mut self, rate: C::F, points: usize) -> Margins<C::F>
+    {
+        margins::find_margins(|omega| self.open_loop_z_response(rate, Complex::cis(omega))*Complex::cis(-omega), rate, points)
+    }
+
+    /// Fills `nyquist` with points of the open-loop frequency response `L = CPz⁻¹`, including the one-sample delay in the feedback path. See
+    /// [`series_nyquist`](crate::control::series_nyquist) for the response without the delay.
+    pub fn nyquist(&mut self, rate: C::F, nyquist: &mut [(C::F, Complex<C::F>)])
+    {
+        margins::fill_nyquist(|omega| self.open_loop_z_response(rate, Complex::cis(omega))*Complex::cis(-omega), rate, nyquist)
+    }
+
+    /// Resets the loop, then fills `response` with its response to a step to the given setpoint.
+    pub fn step_response(&mut self, rate: C::F, setpoint: C::F, response: &mut [C::F])
+    {
+        self.reset();
+        for y in response.iter_mut()
+        {
+            *y = self.filter(rate, setpoint)
+        }
+        self.reset()
+    }
+
+    /// Simulates the response to a step to the given setpoint for the given number of samples, and measures it.
+    ///
+    /// The response is assumed to have settled by the last sample, which is taken as the final value. The settling time is measured with `tolerance`
+    /// relative to the final value, typically 2%. If the final value is within the tolerance of zero, relative to the peak, there's no overshoot.
+    pub fn step_info(&mut self, rate: C::F, setpoint: C::F, samples: usize, tolerance: C::F) -> StepInfo<C::F>
+    {
+        // First find where it ends up, then measure the way there
+        self.reset();
+        let mut final_value = Zero::zero();
+        for _ in 0..samples
+        {
+            final_value = self.filter(rate, setpoint)
+        }
+        self.reset();
+
+        let low = final_value*f!(0.1; C::F);
+        let high = final_value*f!(0.9; C::F);
+        let band = (final_value*tolerance).abs();
+        let negative = final_value < C::F::zero();
+        let above = |y: C::F, level: C::F| if negative {y <= level} else {y >= level};
+
+        let mut peak = if negative {C::F::infinity()} else {C::F::neg_infinity()};
+        let mut t_low = None;
+        let mut t_high = None;
+        let mut settled = 0;
+        for n in 0..samples
+        {
+            let y = self.filter(rate, setpoint);
+            peak = if negative {peak.min(y)} else {peak.max(y)};
+            if t_low.is_none() && above(y, low)
+            {
+                t_low = Some(n)
+            }
+            if t_high.is_none() && above(y, high)
+            {
+                t_high = Some(n)
+            }
+            if (y - final_value).abs() > band
+            {
+                settled = n + 1
+            }
+        }
+        self.reset();
+
+        let overshoot = if final_value.abs() > (peak*tolerance).abs()
+        {
+            Some((peak - final_value)/final_value)
+        }
+        else
+        {
+            None
+        };
+
+        StepInfo {
+            final_value,
+            peak,
+            overshoot,
+            rise_time: t_low.zip(t_high)
+                .map(|(t_low, t_high)| f!(t_high - t_low; C::F)/rate),
+            settling_time: f!(settled; C::F)/rate,
+            steady_state_error: setpoint - final_value
+        }
+    }
+
+    pub fn reset(&mut self)
+    {
+        self.controller.reset();
+        self.plant.reset();
+        self.measurement = Zero::zero()
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use crate::{conf::{HighPass, LowPass}, filters::iir::{first::FirstOrderFilter, second::{PIDFilter, SecondOrderFilter}}, param::{Omega, OmegaZeta, PI}};
+
+    use super::ClosedLoop;
+
+    #[test]
+    fn mass_spring()
+    {
+        const RATE: f64 = 1000.0;
+
+        let plant = SecondOrderFilter::<LowPass>::new(OmegaZeta {omega: 2.0*TAU, zeta: 0.2});
+        let controller = PIDFilter::new(PI {p: 0.1, i: 1.0});
+        let mut closed_loop = ClosedLoop::new(controller, plant);
+
+        let info = closed_loop.step_info(RATE, 1.0, 20000, 0.02);
+        assert!(info.steady_state_error.abs() < 1e-3, "{info:?}");
+        let overshoot = info.overshoot.unwrap();
+        assert!((0.0..0.1).contains(&overshoot), "{info:?}");
+        let rise_time = info.rise_time.unwrap();
+        assert!(rise_time > 0.0 && rise_time < info.settling_time, "{info:?}");
+
+        // The loop is linear, so a negative setpoint mirrors the response, overshoot and all
+        let negative = closed_loop.step_info(RATE, -1.0, 20000, 0.02);
+        assert!((negative.peak + info.peak).abs() < 1e-9, "{negative:?}");
+        assert!((negative.overshoot.unwrap() - overshoot).abs() < 1e-9, "{negative:?}");
+        assert_eq!(negative.rise_time, info.rise_time);
+        assert_eq!(negative.settling_time, info.settling_time);
+
+        // The analytic response matches the simulation
+        // The integrator has its pole right at DC, so evaluate just above it
+        assert!((closed_loop.frequency_response(RATE, 1e-6).norm() - 1.0).abs() < 1e-6);
+        let omega = 1.5*TAU/RATE;
+        let h = closed_loop.frequency_response(RATE, omega);
+        let mut amplitude: f64 = 0.0;
+        for n in 0..20000
+        {
+            let y = closed_loop.filter(RATE, (omega*n as f64).sin());
+            if n >= 10000
+            {
+                amplitude = amplitude.max(y.abs())
+            }
+        }
+        assert!((amplitude - h.norm()).abs() < 1e-3, "{amplitude} {}", h.norm());
+    }
+
+    #[test]
+    fn zero_final_value()
+    {
+        const RATE: f64 = 1000.0;
+
+        // A plant that blocks DC settles back on zero, so there's no overshoot to speak of
+        let plant = FirstOrderFilter::<HighPass>::new(Omega {omega: 10.0*TAU});
+        let controller = PIDFilter::new(PI {p: 0.5, i: 0.0});
+        let mut closed_loop = ClosedLoop::new(controller, plant);
+
+        let info = closed_loop.step_info(RATE, 1.0, 5000, 0.02);
+        assert!(info.final_value.abs() < 1e-9, "{info:?}");
+        assert!(info.peak > 0.1, "{info:?}");
+        assert_eq!(info.overshoot, None);
+
+        // Neither is there for a step to zero
+        let info = closed_loop.step_info(RATE, 0.0, 5000, 0.02);
+        assert_eq!(info.final_value, 0.0);
+        assert_eq!(info.overshoot, None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,251 @@
+use array_trait::AsSlice;
+use num::{Complex, Float};
+
+use crate::{f, param::FilterFloat, rtf::Rtf};
+
+/// The lowest frequency searched for crossovers, relative to the Nyquist frequency.
+const LOWEST_FREQUENCY: f64 = 1e-6;
+const BISECTIONS: usize = 64;
+
+/// Stability margins of a feedback loop, found from its open-loop frequency response `L`.
+///
+/// All frequencies are in rad/s. If there are several crossovers, the one with the smallest margin is reported.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Margins<F>
+where
+    F: Float
+{
+    /// How much the loop gain can be multiplied before the loop becomes unstable, `1/|L|` at the phase crossover. Take `20log₁₀` for decibels.
+    pub gain_margin: Option<F>,
+    /// The frequency where the phase of `L` crosses -180°.
+    pub phase_crossover: Option<F>,
+    /// How much phase lag can be added before the loop becomes unstable, `180° + ∠L` at the gain crossover, in radians.
+    pub phase_margin: Option<F>,
+    /// The frequency where `|L|` crosses 1.
+    pub gain_crossover: Option<F>
+}
+
+/// Finds the stability margins of a feedback loop with the controller and the plant in series, and unity feedback without any delay.
+///
+/// The open-loop frequency response `L = CP` is sampled at `points` log-spaced frequencies, from just above DC up to the Nyquist frequency, and every
+/// crossover that is bracketed by two neighbouring points is refined by bisection.
+///
+/// This is the loop as it's usually designed, where the output of the plant is fed back within the same sample. A
+/// [`ClosedLoop`](crate::control::ClosedLoop) feeds back the output of the previous sample instead, and the extra phase lag of that delay lowers the
+/// margins. Use [`ClosedLoop::margins`](crate::control::ClosedLoop::margins) for the margins of the loop it runs, `L = CPz⁻¹`.
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use core::f64::consts::TAU;
+///
+/// use real_time_fir_iir_filters::{
+///     conf::LowPass,
+///     control,
+///     param::{Omega, PI},
+///     filters::iir::{first::PIFilter, third::ThirdOrderButterworthFilter}
+/// };
+///
+/// let mut controller = PIFilter::new(PI {p: 2.0, i: 0.0});
+/// let mut plant = ThirdOrderButterworthFilter::<LowPass>::new(Omega {omega: 10.0*TAU});
+///
+/// let margins = control::series_margins(&mut controller, &mut plant, 10000.0, 1000);
+///
+/// // A third-order Butterworth filter has a gain of 1/3 where its phase is -180°
+/// assert!((margins.gain_margin.unwrap() - 1.5).abs() < 1e-6);
+/// ```
+pub fn series_margins<C, P>(controller: &mut C, plant: &mut P, rate: C::F, points: usize) -> Margins<C::F>
+where
+    C: Rtf,
+    P: Rtf<F = C::F>
+{
+    find_margins(|omega| open_loop(controller, plant, rate, omega), rate, points)
+}
+
+/// Fills `nyquist` with points of the open-loop frequency response `L = CP` of the controller and the plant in series, as pairs of the frequency
+/// (in rad/s) and the response, log-spaced from just above DC up to the Nyquist frequency.
+///
+/// Plotted in the complex plane, together with its mirror image, this is the Nyquist plot of the loop. The closed loop is stable if it doesn't
+/// encircle `-1` (when the open loop is stable). Like [`series_margins`], this leaves out the one-sample delay of a
+/// [`ClosedLoop`](crate::control::ClosedLoop), which [`ClosedLoop::nyquist`](crate::control::ClosedLoop::nyquist) includes.
+pub fn series_nyquist<C, P>(controller: &mut C, plant: &mut P, rate: C::F, nyquist: &mut [(C::F, Complex<C::F>)])
+where
+    C: Rtf,
+    P: Rtf<F = C::F>
+{
+    fill_nyquist(|omega| open_loop(controller, plant, rate, omega), rate, nyquist)
+}
+
+fn open_loop<C, P>(controller: &mut C, plant: &mut P, rate: C::F, omega: C::F) -> Complex<C::F>
+where
+    C: Rtf,
+    P: Rtf<F = C::F>
+{
+    controller.frequency_response(rate, omega).as_slice()[0]*plant.frequency_response(rate, omega).as_slice()[0]
+}
+
+/// Log-spaced frequencies in rad/sample.
+fn frequency<F>(n: usize, points: usize) -> F
+where
+    F: FilterFloat
+{
+    let lowest = f!(LOWEST_FREQUENCY);
+    F::PI()*lowest.powf(F::one() - f!(n)/f!(points.max(2) - 1))
+}
+
+pub(crate) fn fill_nyquist<F, L>(mut open_loop: L, rate: F, nyquist: &mut [(F, Complex<F>)])
+where
+    F: FilterFloat,
+    L: FnMut(F) -> Complex<F>
+{
+    let points = nyquist.len();
+    for (n, (omega, l)) in nyquist.iter_mut().enumerate()
+    {
+        let w = frequency(n, points);
+        *omega = w*rate;
+        *l = open_loop(w)
+    }
+}
+
+pub(crate) fn find_margins<F, L>(mut open_loop: L, rate: F, points: usize) -> Margins<F>
+where
+    F: FilterFloat,
+    L: FnMut(F) -> Complex<F>
+{
+    let mut margins = Margins {
+        gain_margin: None,
+        phase_crossover: None,
+        phase_margin: None,
+        gain_crossover: None
+    };
+
+    let gain = |l: Complex<F>| l.norm() - F::one();
+    let phase = |l: Complex<F>| l.im;
+
+    let bisect = |open_loop: &mut L, f: &dyn Fn(Complex<F>) -> F, mut a: F, mut b: F| {
+        let fa = f(open_loop(a));
+        for _ in 0..BISECTIONS
+        {
+            let m = (a + b)/f!(2.0);
+            let fm = f(open_loop(m));
+            if (fm < F::zero()) == (fa < F::zero())
+            {
+                a = m
+            }
+            else
+            {
+                b = m
+            }
+        }
+        (a + b)/f!(2.0)
+    };
+
+    let mut prev: Option<(F, Complex<F>)> = None;
+    for n in 0..points
+    {
+        let w = frequency(n, points);
+        let l = open_loop(w);
+        if let Some((w_prev, l_prev)) = prev
+        {
+            // |L| crosses 1
+            if (gain(l_prev) < F::zero()) != (gain(l) < F::zero())
+            {
+                let w_c = bisect(&mut open_loop, &gain, w_prev, w);
+                let phase_margin = wrap(open_loop(w_c).arg() + F::PI());
+                if margins.phase_margin.is_none_or(|pm| phase_margin < pm)
+                {
+                    margins.phase_margin = Some(phase_margin);
+                    margins.gain_crossover = Some(w_c*rate)
+                }
+            }
+            // L crosses the negative real axis
+            if (phase(l_prev) < F::zero()) != (phase(l) < F::zero()) && (l_prev.re + l.re) < F::zero()
+            {
+                let w_c = bisect(&mut open_loop, &phase, w_prev, w);
+                let l_c = open_loop(w_c);
+                if l_c.re < F::zero()
+                {
+                    let gain_margin = l_c.norm().recip();
+                    if margins.gain_margin.is_none_or(|gm| gain_margin < gm)
+                    {
+                        margins.gain_margin = Some(gain_margin);
+                        margins.phase_crossover = Some(w_c*rate)
+                    }
+                }
+            }
+        }
+        prev = Some((w, l))
+    }
+
+    margins
+}
+
+/// Wraps a phase to (-π, π].
+fn wrap<F>(phase: F) -> F
+where
+    F: FilterFloat
+{
+    let tau = F::TAU();
+    let wrapped = phase - tau*((phase + F::PI())/tau).floor();
+    if wrapped <= -F::PI()
+    {
+        wrapped + tau
+    }
+    else
+    {
+        wrapped
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::{PI, TAU};
+
+    use num::Complex;
+
+    use crate::{conf::LowPass, control::ClosedLoop, filters::iir::{first::PIFilter, third::ThirdOrderButterworthFilter}, param::{Omega, PI as PIParam}};
+
+    #[test]
+    fn butterworth()
+    {
+        const RATE: f64 = 10000.0;
+
+        let omega = 10.0*TAU;
+        let mut controller = PIFilter::new(PIParam {p: 2.0, i: 0.0});
+        let mut plant = ThirdOrderButterworthFilter::<LowPass>::new(Omega {omega});
+
+        let margins = super::series_margins(&mut controller, &mut plant, RATE, 1000);
+
+        // Phase crossover at √2ω, where the gain is 1/3, warped by the bilinear transform
+        let warp = |omega: f64| 2.0*(omega/(2.0*RATE)).atan()*RATE;
+        assert!((margins.gain_margin.unwrap() - 1.5).abs() < 1e-6, "{margins:?}");
+        assert!((margins.phase_crossover.unwrap()/warp(2f64.sqrt()*omega) - 1.0).abs() < 1e-6, "{margins:?}");
+
+        // Gain crossover where 2/√(1 + (ω_c/ω)⁶) = 1
+        let w = 3f64.powf(1.0/6.0);
+        let h = Complex::new(1.0, w)*Complex::new(1.0 - w*w, w);
+        let phase_margin = PI - h.arg();
+        assert!((margins.phase_margin.unwrap() - phase_margin).abs() < 1e-3, "{margins:?}");
+        assert!((margins.gain_crossover.unwrap()/warp(w*omega) - 1.0).abs() < 1e-6, "{margins:?}");
+
+        // The Nyquist plot passes through the crossovers
+        let mut nyquist = [(0.0, Complex::new(0.0, 0.0)); 256];
+        super::series_nyquist(&mut controller, &mut plant, RATE, &mut nyquist);
+        assert!(nyquist.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(nyquist.windows(2).any(|w| {
+            let [(_, a), (_, b)] = [w[0], w[1]];
+            (a.im < 0.0) != (b.im < 0.0) && ((a.re + b.re)/2.0 + 1.0/1.5).abs() < 0.05
+        }));
+
+        // The one-sample delay of the closed loop lags the phase by one sample at the gain crossover, which takes that off the phase margin
+        let delayed = ClosedLoop::new(controller, plant).margins(RATE, 1000);
+        assert!((delayed.gain_crossover.unwrap()/margins.gain_crossover.unwrap() - 1.0).abs() < 1e-6, "{delayed:?}");
+        let lag = margins.gain_crossover.unwrap()/RATE;
+        assert!((margins.phase_margin.unwrap() - delayed.phase_margin.unwrap() - lag).abs() < 1e-6, "{delayed:?}");
+        assert!(delayed.gain_margin.unwrap() < margins.gain_margin.unwrap(), "{delayed:?}");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+moddef::moddef!(
+    flat(pub) mod {
+        closed_loop,
+        margins,
+        relay_auto_tuner for cfg(feature = "pid")
+    }
+);
\ No newline at end of file
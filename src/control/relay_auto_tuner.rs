@@ -0,0 +1,300 @@
+use crate::{f, param::{FilterFloat, PID}};
+
+/// Rules for turning the ultimate gain and period of a loop into [`PID`]-parameters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum TuningRule
+{
+    /// The classic Ziegler–Nichols rules. Fast, but with a large overshoot.
+    ///
+    /// <pre>
+    /// Kp = 0.6Ku, Ti = Tu/2, Td = Tu/8
+    /// </pre>
+    #[default]
+    ZieglerNichols,
+    /// The Tyreus–Luyben rules. More conservative and robust than Ziegler–Nichols, with less overshoot.
+    ///
+    /// <pre>
+    /// Kp = Ku/2.2, Ti = 2.2Tu, Td = Tu/6.3
+    /// </pre>
+    TyreusLuyben,
+    /// Skogestad's SIMC rules for a PI-controller, with the loop approximated as an integrating process with dead time, `k e^(-θs)/s`, that has the
+    /// same ultimate gain and period. The closed-loop time constant is chosen equal to the dead time, as Skogestad recommends.
+    ///
+    /// <pre>
+    /// θ = Tu/4, k = 2π/(KuTu)
+    ///
+    /// Kp = 1/(2kθ) = Ku/π, Ti = 8θ = 2Tu, Td = 0
+    /// </pre>
+    Simc
+}
+
+/// The ultimate gain and period of a loop, where a proportional controller makes it oscillate.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ultimate<F>
+where
+    F: FilterFloat
+{
+    /// The ultimate gain `Ku`.
+    pub gain: F,
+    /// The ultimate period `Tu`, in seconds.
+    pub period: F
+}
+
+impl<F> Ultimate<F>
+where
+    F: FilterFloat
+{
+    /// Proposes [`PID`]-parameters for the loop, using the given tuning rule.
+    pub fn pid(&self, rule: TuningRule) -> PID<F>
+    {
+        let Self {gain: ku, period: tu} = *self;
+        let (kp, ti, td) = match rule
+        {
+            TuningRule::ZieglerNichols => (f!(0.6)*ku, tu/f!(2.0), tu/f!(8.0)),
+            TuningRule::TyreusLuyben => (ku/f!(2.2), f!(2.2)*tu, tu/f!(6.3)),
+            TuningRule::Simc => (ku/F::PI(), tu + tu, F::zero())
+        };
+        PID {
+            p: kp,
+            i: kp/ti,
+            d: kp*td
+        }
+    }
+}
+
+/// Finds the ultimate gain and period of a loop with relay feedback, as proposed by Åström and Hägglund.
+///
+/// Instead of a controller, a relay drives the plant. It outputs `bias + amplitude` while the measurement is below the setpoint, and `bias - amplitude`
+/// while it's above, which makes the loop settle into a limit cycle. The period of the oscillation is the ultimate period, and with the amplitude `a`
+/// of the measurement, the describing function of the relay gives the ultimate gain:
+///
+/// <pre>
+///        4d
+/// Ku = ------
+///      π√(a² - ε²)
+/// </pre>
+///
+/// where `d` is the relay amplitude and `ε` is the hysteresis, which keeps measurement noise from toggling the relay.
+///
+/// The tuner can be put in a live loop, by calling [`relay`](RelayAutoTuner::relay) with every new measurement, or run against a simulation of the
+/// plant with [`run`](RelayAutoTuner::run). The first oscillation period is discarded as a transient, and the result is averaged over the following
+/// periods.
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     control::{RelayAutoTuner, TuningRule},
+///     filters::iir::second::PIDFilter
+/// };
+///
+/// const RATE: f64 = 1000.0;
+///
+/// // A plant that is three cascaded lags, each with a time constant of 1 s
+/// let mut lags = [0.0; 3];
+/// let plant = |u: f64| {
+///     let mut x = u;
+///     for y in lags.iter_mut()
+///     {
+///         *y += (x - *y)/RATE;
+///         x = *y
+///     }
+///     x
+/// };
+///
+/// let mut tuner = RelayAutoTuner::new(0.0, 1.0);
+/// let ultimate = tuner.run(RATE, plant, 100000).unwrap();
+///
+/// let filter = PIDFilter::new(ultimate.pid(TuningRule::ZieglerNichols));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RelayAutoTuner<F = f64>
+where
+    F: FilterFloat
+{
+    pub setpoint: F,
+    pub amplitude: F,
+    pub bias: F,
+    pub hysteresis: F,
+    /// The number of oscillation periods to average over, after the first.
+    pub cycles: usize,
+    #[serde(skip)]
+    high: bool,
+    #[serde(skip)]
+    n: usize,
+    #[serde(skip)]
+    switched: Option<usize>,
+    #[serde(skip)]
+    peaks: [F; 2],
+    #[serde(skip)]
+    periods: usize,
+    #[serde(skip)]
+    period_sum: F,
+    #[serde(skip)]
+    amplitude_sum: F
+}
+
+impl<F> RelayAutoTuner<F>
+where
+    F: FilterFloat
+{
+    pub fn new(setpoint: F, amplitude: F) -> Self
+    {
+        Self {
+            setpoint,
+            amplitude,
+            bias: F::zero(),
+            hysteresis: F::zero(),
+            cycles: 4,
+            high: true,
+            n: 0,
+            switched: None,
+            peaks: [F::infinity(), F::neg_infinity()],
+            periods: 0,
+            period_sum: F::zero(),
+            amplitude_sum: F::zero()
+        }
+    }
+
+    /// Takes the newest measurement of the loop, and returns the output of the relay, to be fed to the plant.
+    pub fn relay(&mut self, rate: F, measurement: F) -> F
+    {
+        let error = self.setpoint - measurement;
+        self.peaks = [self.peaks[0].min(measurement), self.peaks[1].max(measurement)];
+
+        if self.high && error < -self.hysteresis
+        {
+            self.high = false
+        }
+        else if !self.high && error > self.hysteresis
+        {
+            // A full period is over on every switch back up
+            self.high = true;
+            if let Some(switched) = self.switched
+            {
+                // The first period is a transient
+                if self.periods > 0 && self.periods <= self.cycles
+                {
+                    let [min, max] = self.peaks;
+                    self.period_sum = self.period_sum + f!(self.n - switched)/rate;
+                    self.amplitude_sum = self.amplitude_sum + (max - min)/f!(2.0);
+                }
+                self.periods += 1
+            }
+            self.switched = Some(self.n);
+            self.peaks = [measurement, measurement]
+        }
+        self.n += 1;
+
+        if self.high
+        {
+            self.bias + self.amplitude
+        }
+        else
+        {
+            self.bias - self.amplitude
+        }
+    }
+
+    /// Returns the measured ultimate gain and period, once enough oscillation periods have been measured.
+    pub fn ultimate(&self) -> Option<Ultimate<F>>
+    {
+        if self.cycles == 0 || self.periods <= self.cycles
+        {
+            return None
+        }
+        let cycles = f!(self.cycles);
+        let period = self.period_sum/cycles;
+        let a = self.amplitude_sum/cycles;
+        let a2_m_e2 = a*a - self.hysteresis*self.hysteresis;
+        if a2_m_e2 <= F::zero()
+        {
+            return None
+        }
+        Some(Ultimate {
+            gain: f!(4.0)*self.amplitude/(F::PI()*a2_m_e2.sqrt()),
+            period
+        })
+    }
+
+    /// Runs the relay against a simulation of the plant, which takes the output of the relay and returns the next measurement, until the ultimate
+    /// gain and period are found, or `max_samples` samples have passed.
+    pub fn run<P>(&mut self, rate: F, mut plant: P, max_samples: usize) -> Option<Ultimate<F>>
+    where
+        P: FnMut(F) -> F
+    {
+        let mut measurement = plant(self.bias);
+        for _ in 0..max_samples
+        {
+            if let Some(ultimate) = self.ultimate()
+            {
+                return Some(ultimate)
+            }
+            measurement = plant(self.relay(rate, measurement));
+        }
+        self.ultimate()
+    }
+
+    pub fn reset(&mut self)
+    {
+        *self = Self {
+            bias: self.bias,
+            hysteresis: self.hysteresis,
+            cycles: self.cycles,
+            ..Self::new(self.setpoint, self.amplitude)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{filters::iir::second::PIDController, param::PIDF, rtf::Rtf};
+
+    use super::{RelayAutoTuner, TuningRule};
+
+    #[test]
+    fn three_lags()
+    {
+        const RATE: f64 = 1000.0;
+
+        // Three lags of 1 s have Ku = 8 and Tu = 2π/√3
+        let lags = |state: &mut [f64; 3], u: f64| {
+            let mut x = u;
+            for y in state.iter_mut()
+            {
+                *y += (x - *y)/RATE;
+                x = *y
+            }
+            x
+        };
+
+        let mut state = [0.0; 3];
+        let mut tuner = RelayAutoTuner::new(0.5, 1.0);
+        tuner.bias = 0.5;
+        tuner.hysteresis = 0.001;
+        let ultimate = tuner.run(RATE, |u| lags(&mut state, u), 1000000).unwrap();
+
+        // The describing function is only an approximation
+        assert!((ultimate.gain/8.0 - 1.0).abs() < 0.1, "{ultimate:?}");
+        assert!((ultimate.period/(core::f64::consts::TAU/3f64.sqrt()) - 1.0).abs() < 0.05, "{ultimate:?}");
+
+        // Every rule gives a stable loop, which settles on the setpoint
+        for rule in [TuningRule::ZieglerNichols, TuningRule::TyreusLuyben, TuningRule::Simc]
+        {
+            let mut controller = PIDController::new(PIDF::with_n(ultimate.pid(rule), 100.0));
+            let mut state = [0.0; 3];
+            let mut y = 0.0;
+            for _ in 0..100000
+            {
+                let [u] = controller.filter(RATE, 1.0 - y);
+                y = lags(&mut state, u);
+            }
+            assert!((y - 1.0).abs() < 1e-3, "{rule:?}");
+        }
+    }
+}
\ No newline at end of file
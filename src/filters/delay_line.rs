@@ -0,0 +1,112 @@
+use num::{Complex, Float};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::serde::SerdeArray;
+
+/// A ring buffer holding the last `MAX` samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct DelayLine<F, const MAX: usize>
+{
+    buffer: [F; MAX],
+    position: usize
+}
+
+impl<F, const MAX: usize> DelayLine<F, MAX>
+where
+    F: Float
+{
+    const VALID_MAX: () = assert!(MAX > 0, "The maximum delay must be at least one sample");
+
+    pub(crate) fn new() -> Self
+    {
+        let () = Self::VALID_MAX;
+
+        Self {
+            buffer: [F::zero(); MAX],
+            position: 0
+        }
+    }
+
+    /// Clamps a delay in samples to what the line can hold, `1..=MAX`, and splits it into whole samples and the fraction in between.
+    fn samples(delay: F) -> (usize, F)
+    {
+        let delay = if delay.is_nan()
+        {
+            F::one()
+        }
+        else
+        {
+            delay.max(F::one())
+                .min(F::from(MAX).unwrap())
+        };
+        let m = delay.floor();
+        (m.to_usize().unwrap(), delay - m)
+    }
+
+    /// Returns the sample pushed `m` samples ago, where `m` is from 1 to `MAX`.
+    fn get(&self, m: usize) -> F
+    {
+        self.buffer[(self.position + MAX - m) % MAX]
+    }
+
+    /// Returns the signal `delay` samples ago, linearly interpolated between whole samples, so that the delay can be swept smoothly.
+    pub(crate) fn read(&self, delay: F) -> F
+    {
+        let (m, frac) = Self::samples(delay);
+        let y = self.get(m);
+        if frac.is_zero()
+        {
+            return y
+        }
+        y + (self.get(m + 1) - y)*frac
+    }
+
+    /// The transfer function of [`read`](Self::read).
+    pub(crate) fn z_response(delay: F, z: Complex<F>) -> Complex<F>
+    {
+        let (m, frac) = Self::samples(delay);
+        let z_inv = z.inv();
+        z_inv.powu(m as u32)*(z_inv*frac + F::one() - frac)
+    }
+
+    pub(crate) fn push(&mut self, x: F) -> &mut F
+    {
+        let y = &mut self.buffer[self.position];
+        *y = x;
+        self.position = (self.position + 1) % MAX;
+        y
+    }
+
+    pub(crate) fn reset(&mut self)
+    {
+        self.buffer = [F::zero(); MAX];
+        self.position = 0
+    }
+}
+
+// Serialized from the oldest to the newest sample, so that the position in the ring buffer doesn't need to be stored.
+impl<F, const MAX: usize> Serialize for DelayLine<F, MAX>
+where
+    F: Float + Serialize
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        SerdeArray::<F, MAX>(core::array::from_fn(|i| self.buffer[(self.position + i) % MAX])).serialize(serializer)
+    }
+}
+impl<'de, F, const MAX: usize> Deserialize<'de> for DelayLine<F, MAX>
+where
+    F: Float + Deserialize<'de>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        let SerdeArray(buffer) = SerdeArray::deserialize(deserializer)?;
+        let mut delay_line = Self::new();
+        delay_line.buffer = buffer;
+        Ok(delay_line)
+    }
+}
\ No newline at end of file
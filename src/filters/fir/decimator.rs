@@ -0,0 +1,247 @@
+use num::Complex;
+
+use crate::{conf::All, f, filters::fir::{kaiser_beta, kaiser_low_pass}, param::FilterFloat, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+/// Downsamples a signal by an integer factor `M`, with a polyphase low-pass FIR filter with `M*L` taps.
+///
+/// The filter is split into `M` polyphase branches of `L` taps each, that each only see every `M`'th input sample, so each output sample costs `M*L`
+/// multiplications, instead of `M*M*L` for running the whole filter at the higher sample rate and throwing away samples.
+///
+/// For a factor of two, [`HalfBandDecimator`](crate::filters::fir::HalfBandDecimator) is about four times cheaper.
+///
+/// As an [`Rtf`], it's fed one sample at a time at the higher sample rate. A new output sample is made on the last of every `M` input samples, and held
+/// through the next `M - 1`, so every `M`'th output sample is the decimated signal. Its response is that of the decimation filter, which the new output
+/// samples follow exactly. The parameter is the taps of the polyphase branches, where branch `p` holds the taps `h[lM + p]`.
+///
+/// # Example
+///
+/// ```rust
+/// use real_time_fir_iir_filters::{
+///     rtf::Rtf,
+///     filters::fir::Decimator
+/// };
+///
+/// // Downsample by 3, with 3*16 taps and 80dB of attenuation
+/// let mut decimator = Decimator::<f64, 3, 16>::new(80.0);
+///
+/// // One new sample out for every three samples in
+/// decimator.filter(132300.0, 1.0);
+/// decimator.filter(132300.0, 0.0);
+/// let [y] = decimator.filter(132300.0, 0.0);
+///
+/// let mut y = [0.0; 2];
+/// decimator.filter_block(&[0.0; 6], &mut y);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Decimator<F, const M: usize, const L: usize>
+where
+    F: FilterFloat
+{
+    h: [[F; L]; M],
+    w: [[F; L]; M],
+    y: F,
+    phase: usize,
+    denormal_protection: DenormalProtection<F>
+}
+
+impl<F, const M: usize, const L: usize> Decimator<F, M, L>
+where
+    F: FilterFloat
+{
+    /// Delay of the decimator, in samples at the higher sample rate, from the first sample of each block.
+    pub const DELAY: f64 = (M*L - 1) as f64/2.0 - (M - 1) as f64;
+
+    /// Creates a decimator with a Kaiser-windowed sinc low-pass filter, with a stopband attenuation of `attenuation` dB.
+    ///
+    /// The filter is -6dB at the Nyquist frequency of the lower sample rate. The more taps, the narrower the transition band.
+    pub fn new(attenuation: f64) -> Self
+    {
+        let mut h = [[0.0; M]; L];
+        kaiser_low_pass(h.as_flattened_mut(), 1.0/M as f64, kaiser_beta(attenuation));
+        Self::from_taps(h.as_flattened())
+    }
+
+    /// Creates a decimator from the `M*L` taps of a low-pass FIR filter at the higher sample rate.
+    pub fn from_taps(h: &[f64]) -> Self
+    {
+        assert_eq!(h.len(), M*L, "The decimator needs exactly M*L taps");
+        Self::from_param(core::array::from_fn(|p| core::array::from_fn(|l| f!(h[l*M + p]))))
+    }
+
+    fn push(&mut self, x: F) -> F
+    {
+        // Sample `i` of each block goes to branch `M - 1 - i`
+        let w = &mut self.w[M - 1 - self.phase];
+        w.rotate_right(1);
+        w[0] = x;
+
+        self.phase += 1;
+        if self.phase == M
+        {
+            self.phase = 0;
+            self.y = self.h.iter()
+                .zip(self.w.iter())
+                .map(|(h, w)| h.iter()
+                    .zip(w.iter())
+                    .map(|(&h, &w)| h*w)
+                    .fold(F::zero(), |y, t| y + t)
+                ).fold(F::zero(), |y, t| y + t)
+        }
+
+        self.y
+    }
+
+    /// Feeds a block of samples through the decimator. The input block must be `M` times as long as the output block.
+    pub fn filter_block(&mut self, x: &[F], y: &mut [F])
+    {
+        assert_eq!(x.len(), M*y.len(), "The input block must be M times as long as the output block");
+        for (x, y) in x.chunks_exact(M)
+            .zip(y.iter_mut())
+        {
+            for &x in x
+            {
+                *y = self.push(x)
+            }
+        }
+    }
+}
+
+impl<F, const M: usize, const L: usize> RtfBase for Decimator<F, M, L>
+where
+    F: FilterFloat
+{
+    type Param = [[F; L]; M];
+    type Conf = All;
+    type F = F;
+
+    type Outputs<U> = [U; 1];
+
+    fn from_param(h: Self::Param) -> Self
+    {
+        Self {
+            h,
+            w: [[F::zero(); L]; M],
+            y: F::zero(),
+            phase: 0,
+            denormal_protection: DenormalProtection::None
+        }
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.h
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.h
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.h
+    }
+}
+
+impl<F, const M: usize, const L: usize> Rtf for Decimator<F, M, L>
+where
+    F: FilterFloat
+{
+    fn filter(&mut self, _rate: F, x: F) -> [F; 1]
+    {
+        [self.push(x)]
+    }
+
+    fn z_response(&mut self, _rate: F, z: Complex<F>) -> [Complex<F>; 1]
+    {
+        let z_inv = z.inv();
+        [
+            self.h.iter()
+                .enumerate()
+                .flat_map(|(p, h)| h.iter()
+                    .enumerate()
+                    .map(move |(l, &h)| z_inv.powi((l*M + p) as i32)*h)
+                ).fold(Complex::from(F::zero()), |h, t| h + t)
+        ]
+    }
+
+    fn reset(&mut self)
+    {
+        self.w = [[F::zero(); L]; M];
+        self.y = F::zero();
+        self.phase = 0
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<F>
+    {
+        self.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<F>)
+    {
+        self.denormal_protection = denormal_protection
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use num::Complex;
+
+    use crate::{filters::fir::{Decimator, Interpolator}, rtf::Rtf};
+
+    #[test]
+    fn round_trip()
+    {
+        fn test<const M: usize, const L: usize>()
+        {
+            const N: usize = 256;
+            const OMEGA: f64 = 0.1;
+            const RATE: f64 = 44100.0;
+
+            let mut interpolator = Interpolator::<f64, M, L>::new(80.0);
+            let mut decimator = Decimator::<f64, M, L>::new(80.0);
+
+            // The two delays add up to a whole number of samples at the lower sample rate
+            let delay = (Interpolator::<f64, M, L>::DELAY + Decimator::<f64, M, L>::DELAY)/M as f64;
+            assert_eq!(delay, (L - 1) as f64);
+            let delay = L - 1;
+
+            let x: [f64; N] = core::array::from_fn(|n| (OMEGA*n as f64).sin());
+            let mut y_up = vec![0.0; M*N];
+            let mut y = [0.0; N];
+            interpolator.filter_block(&x, &mut y_up);
+            decimator.filter_block(&y_up, &mut y);
+
+            for n in 2*delay..N
+            {
+                assert!((y[n] - x[n - delay]).abs() < 1e-3, "{M}x: {} != {}", y[n], x[n - delay]);
+            }
+
+            // Stopband of the decimator (1.5 times the lower sample rate's Nyquist frequency and up)
+            for i in 0..=16
+            {
+                let omega = (1.5 + i as f64/16.0*(M as f64 - 1.5))/M as f64*TAU/2.0;
+                let [h] = decimator.z_response(RATE*M as f64, Complex::cis(omega));
+                assert!(h.norm() < 10f64.powf(-80.0/20.0), "{M}x: |H({omega})| = {}", h.norm());
+            }
+
+            // The polyphase branches of the interpolator make up the same filter at the higher sample rate, with a gain of M
+            for omega in [0.01, 0.1, 1.0, 3.0]
+            {
+                let z = Complex::cis(omega);
+                let h = interpolator.z_response(RATE, z.powu(M as u32))
+                    .into_iter()
+                    .enumerate()
+                    .map(|(p, h)| h*z.powi(-(p as i32)))
+                    .sum::<Complex<f64>>();
+                let [h_decimator] = decimator.z_response(RATE*M as f64, z);
+                assert!((h - h_decimator*M as f64).norm() < 1e-9, "{M}x: {h} != {}", h_decimator*M as f64);
+            }
+        }
+
+        test::<2, 32>();
+        test::<3, 32>();
+        test::<4, 32>();
+        test::<5, 32>();
+    }
+}
@@ -0,0 +1,152 @@
+use core::f64::consts::PI;
+
+use num::Float;
+
+use crate::f;
+
+/// Returns the Kaiser window shape parameter β that gives a stopband attenuation of `attenuation` dB.
+///
+/// <pre>
+///     ⎧ 0.1102(A - 8.7)                       if A > 50
+/// β = ⎨ 0.5842(A - 21)^0.4 + 0.07886(A - 21)   if 21 ≤ A ≤ 50
+///     ⎩ 0                                     if A < 21
+/// </pre>
+pub fn kaiser_beta(attenuation: f64) -> f64
+{
+    if attenuation > 50.0
+    {
+        0.1102*(attenuation - 8.7)
+    }
+    else if attenuation >= 21.0
+    {
+        0.5842*Float::powf(attenuation - 21.0, 0.4) + 0.07886*(attenuation - 21.0)
+    }
+    else
+    {
+        0.0
+    }
+}
+
+/// Zeroth order modified bessel function of the first kind.
+fn bessel_i0(x: f64) -> f64
+{
+    let mut i0 = 1.0;
+    let mut t = 1.0;
+    let mut k = 1.0;
+    while t > i0*f64::EPSILON
+    {
+        t *= (x/(2.0*k))*(x/(2.0*k));
+        i0 += t;
+        k += 1.0
+    }
+    i0
+}
+
+/// The Kaiser window, for `x` ∈ [-1, 1].
+fn kaiser(x: f64, beta: f64) -> f64
+{
+    bessel_i0(beta*Float::sqrt(Float::max(1.0 - x*x, 0.0)))/bessel_i0(beta)
+}
+
+/// Designs a linear-phase low-pass FIR filter as a Kaiser-windowed sinc, writing its taps into `h`.
+///
+/// `cutoff` is the cutoff frequency relative to the Nyquist frequency (so `1/M` for a decimator or interpolator with a factor of `M`), where the response is
+/// -6dB. `beta` is the shape of the window (see [`kaiser_beta`]). The taps are normalized to a DC gain of exactly one.
+pub fn kaiser_low_pass(h: &mut [f64], cutoff: f64, beta: f64)
+{
+    let c = (h.len() as f64 - 1.0)/2.0;
+    for (n, h) in h.iter_mut()
+        .enumerate()
+    {
+        let t = n as f64 - c;
+        let sinc = if t == 0.0
+        {
+            cutoff
+        }
+        else
+        {
+            Float::sin(PI*cutoff*t)/(PI*t)
+        };
+        *h = sinc*if c > 0.0 {kaiser(t/c, beta)} else {1.0}
+    }
+    let gain: f64 = h.iter().sum();
+    for h in h.iter_mut()
+    {
+        *h /= gain
+    }
+}
+
+/// Designs a linear-phase FIR Hilbert transformer as a Kaiser-windowed ideal response, writing its taps into `h`.
+///
+/// The ideal response is `-j` for positive frequencies and `j` for negative frequencies, whose impulse response is `2/(πn)` for odd `n`, and zero for even `n`.
+/// The length of `h` should be odd, so that the taps are centered on a whole sample. The taps are computed in [`f64`] regardless of `F`.
+pub fn kaiser_hilbert<F>(h: &mut [F], beta: f64)
+where
+    F: Float
+{
+    let c = (h.len() as f64 - 1.0)/2.0;
+    for (n, h) in h.iter_mut()
+        .enumerate()
+    {
+        let t = n as f64 - c;
+        let h_n = if Float::round(t) % 2.0 == 0.0
+        {
+            0.0
+        }
+        else
+        {
+            2.0/(PI*t)*kaiser(t/c, beta)
+        };
+        *h = f!(h_n)
+    }
+}
+
+/// Designs the `K` nonzero side taps of a half-band low-pass filter with `4K - 1` taps, as a Kaiser-windowed sinc.
+///
+/// This is the same as [`kaiser_low_pass`] with a cutoff of `1/2`, except that only the taps `g[m] = h[c ± (2m + 1)]` are returned, since the center tap is
+/// always `1/2` and every other tap is zero.
+///
+/// The half-band designs of this crate (like [`HALF_BAND_80DB`](crate::filters::fir::HALF_BAND_80DB)) are made with this function, using [`kaiser_beta`], with
+/// the least `K` that gives the wanted attenuation from 0.3 times the higher sample rate and up.
+pub fn kaiser_half_band<const K: usize>(beta: f64) -> [f64; K]
+{
+    let c = 2.0*K as f64 - 1.0;
+    let mut g: [f64; K] = core::array::from_fn(|m| {
+        let t = 2.0*m as f64 + 1.0;
+        let sign = if m % 2 == 0 {1.0} else {-1.0};
+        sign/(PI*t)*kaiser(t/c, beta)
+    });
+    let gain: f64 = g.iter().sum();
+    for g in g.iter_mut()
+    {
+        *g *= 0.25/gain
+    }
+    g
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::filters::fir::{HALF_BAND_100DB, HALF_BAND_120DB, HALF_BAND_60DB, HALF_BAND_80DB};
+
+    use super::{kaiser_beta, kaiser_half_band};
+
+    #[test]
+    fn half_band_designs()
+    {
+        fn test<const K: usize>(attenuation: f64, table: [f64; K])
+        {
+            let g = kaiser_half_band::<K>(kaiser_beta(attenuation));
+            for (g, t) in g.into_iter()
+                .zip(table)
+            {
+                assert!((g - t).abs() < 1e-12, "{attenuation}dB: {g} != {t}")
+            }
+        }
+
+        test(60.0, HALF_BAND_60DB);
+        test(80.0, HALF_BAND_80DB);
+        test(100.0, HALF_BAND_100DB);
+        test(120.0, HALF_BAND_120DB);
+    }
+}
@@ -0,0 +1,552 @@
+use num::Complex;
+
+use crate::{conf::All, f, param::FilterFloat, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+// The half-band designs below are made with `kaiser_half_band` and `kaiser_beta`. They're all flat up to 0.2 times the higher sample rate, and attenuate
+// everything from 0.3 times the higher sample rate and up.
+
+/// Half-band design with at least 60dB of attenuation, and 47 taps.
+pub const HALF_BAND_60DB: [f64; 12] = [
+    0.31682135867918,
+    -0.10157595718385058,
+    0.05633618037929001,
+    -0.03568725539023175,
+    0.023551359155706504,
+    -0.015575885120281852,
+    0.010084161230318623,
+    -0.00626748759859388,
+    0.0036580237029601023,
+    -0.0019406127517338594,
+    0.0008783207390887703,
+    -0.0002822058418521321
+];
+/// Half-band design with at least 80dB of attenuation, and 59 taps.
+pub const HALF_BAND_80DB: [f64; 15] = [
+    0.3169242867770424,
+    -0.10200778251685595,
+    0.05703983379034231,
+    -0.03661132539566064,
+    0.024633991724300402,
+    -0.016748294160415317,
+    0.01127615903951435,
+    -0.007413302349024499,
+    0.004701594270283044,
+    -0.0028397524988470354,
+    0.0016072991147403513,
+    -0.0008324897476772974,
+    0.0003786592343787703,
+    -0.0001382125641663116,
+    2.933528204546107e-05
+];
+/// Half-band design with at least 100dB of attenuation, and 75 taps.
+pub const HALF_BAND_100DB: [f64; 19] = [
+    0.31720215347299163,
+    -0.1028203067584362,
+    0.05832525448851474,
+    -0.038274550629644716,
+    0.026557030115274625,
+    -0.0188028117562394,
+    0.013336431566596993,
+    -0.009367894141604827,
+    0.0064623745469084195,
+    -0.004347332188345449,
+    0.0028325510964592703,
+    -0.001774401078835081,
+    0.00105925967155992,
+    -0.000595655641986506,
+    0.00031033671253006824,
+    -0.00014592882587851556,
+    5.905844407931466e-05,
+    -1.845191981872644e-05,
+    2.8828258743380456e-06
+];
+/// Half-band design with at least 120dB of attenuation, and 91 taps.
+pub const HALF_BAND_120DB: [f64; 23] = [
+    0.3173873836967962,
+    -0.1033648879706062,
+    0.05919376737379103,
+    -0.03941212655759445,
+    0.02789446219900162,
+    -0.02026297784431223,
+    0.014841126735762253,
+    -0.01084427991249016,
+    0.0078483532508183,
+    -0.0055953381941387294,
+    0.003911489422962649,
+    -0.0026697202125585064,
+    0.001771380358767016,
+    -0.001137158320039986,
+    0.0007024155387530096,
+    -0.0004146422725351881,
+    0.00023185142720914129,
+    -0.00012131028098509416,
+    5.8332961638373725e-05,
+    -2.5040335189172246e-05,
+    9.094866898571458e-06,
+    -2.465470977902981e-06,
+    2.8953902944225317e-07
+];
+/// The default half-band design.
+pub const HALF_BAND: [f64; HALF_BAND_TAPS] = HALF_BAND_100DB;
+
+/// Number of distinct nonzero side taps of the default half-band design.
+pub const HALF_BAND_TAPS: usize = 19;
+
+/// Returns the response of a half-band filter with the side taps `g` for a single z-plane point, at the higher sample rate.
+///
+/// The full impulse response has `4K - 1` taps, centered at `c = 2K - 1`, with `h[c] = 1/2`, `h[c ± (2m + 1)] = g[m]`, and every other tap zero.
+///
+/// <pre>
+///                  K-1
+/// H(z) = z⁻ᶜ(1/2 + Σ g[m](z²ᵐ⁺¹ + z⁻⁽²ᵐ⁺¹⁾))
+///                  m=0
+/// </pre>
+pub fn half_band_z_response<F>(g: &[F], z: Complex<F>) -> Complex<F>
+where
+    F: FilterFloat
+{
+    let z_inv = z.inv();
+    g.iter()
+        .enumerate()
+        .map(|(m, &g)| (z.powi(2*m as i32 + 1) + z_inv.powi(2*m as i32 + 1))*g)
+        .fold(Complex::from(f!(0.5)), |h, t| h + t)
+        *z_inv.powi(2*g.len() as i32 - 1)
+}
+
+/// Upsamples a signal by a factor of two, with a polyphase half-band filter with `K` side taps.
+///
+/// Only the nonzero taps are ever computed. One of the two polyphase branches is a pure delay, and the other one is symmetric, so each input sample costs `K`
+/// multiplications.
+///
+/// As an [`Rtf`], it's fed one sample at a time at the lower sample rate, and outputs the two resulting samples at the doubled sample rate, in order. Each
+/// output is a polyphase branch, and its response is the response of that branch at the lower sample rate. The parameter is the side taps `g` (see
+/// [`half_band_z_response`]).
+///
+/// <pre>
+///                 K-1
+/// H₀(z) = 2z⁻⁽ᴷ⁻¹⁾ Σ g[m](zᵐ + z⁻⁽ᵐ⁺¹⁾)
+///                 m=0
+///
+/// H₁(z) = z⁻⁽ᴷ⁻¹⁾
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// use real_time_fir_iir_filters::{
+///     rtf::Rtf,
+///     filters::fir::{HalfBandInterpolator, HALF_BAND_60DB}
+/// };
+///
+/// let mut interpolator = HalfBandInterpolator::<f64>::new();
+///
+/// // Two samples out for each sample in
+/// let [y0, y1] = interpolator.filter(44100.0, 1.0);
+///
+/// // A cheaper design
+/// let mut interpolator = HalfBandInterpolator::<f32, _>::from_taps(HALF_BAND_60DB);
+///
+/// let mut y = [0.0; 8];
+/// interpolator.filter_block(&[1.0, 0.0, 0.0, 0.0], &mut y);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HalfBandInterpolator<F, const K: usize = HALF_BAND_TAPS>
+where
+    F: FilterFloat
+{
+    g: [F; K],
+    w: [[F; K]; 2],
+    denormal_protection: DenormalProtection<F>
+}
+
+impl<F> HalfBandInterpolator<F>
+where
+    F: FilterFloat
+{
+    /// Creates an interpolator with the default half-band design, [`HALF_BAND`].
+    pub fn new() -> Self
+    {
+        Self::from_taps(HALF_BAND)
+    }
+}
+
+impl<F, const K: usize> HalfBandInterpolator<F, K>
+where
+    F: FilterFloat
+{
+    /// Delay of the interpolator, in samples at the higher sample rate.
+    pub const DELAY: usize = 2*K - 1;
+
+    /// Creates an interpolator from the side taps of a half-band design (see [`half_band_z_response`]).
+    pub fn from_taps(g: [f64; K]) -> Self
+    {
+        Self::from_param(g.map(|g| f!(g)))
+    }
+
+    fn push(&mut self, x: F) -> [F; 2]
+    {
+        let [w_new, w_old] = &mut self.w;
+        w_old.rotate_right(1);
+        w_old[0] = w_new[K - 1];
+        w_new.rotate_right(1);
+        w_new[0] = x;
+
+        let y0 = self.g.iter()
+            .zip(w_new.iter().rev())
+            .zip(w_old.iter())
+            .map(|((&g, &w1), &w2)| g*(w1 + w2))
+            .fold(F::zero(), |y, t| y + t);
+        let y1 = w_new[K - 1];
+
+        [y0 + y0, y1]
+    }
+
+    /// Feeds a block of samples through the interpolator. The output block must be twice as long as the input block.
+    pub fn filter_block(&mut self, x: &[F], y: &mut [F])
+    {
+        assert_eq!(y.len(), 2*x.len(), "The output block must be twice as long as the input block");
+        for (&x, y) in x.iter()
+            .zip(y.chunks_exact_mut(2))
+        {
+            y.copy_from_slice(&self.push(x))
+        }
+    }
+}
+
+impl<F> Default for HalfBandInterpolator<F>
+where
+    F: FilterFloat
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<F, const K: usize> RtfBase for HalfBandInterpolator<F, K>
+where
+    F: FilterFloat
+{
+    type Param = [F; K];
+    type Conf = All;
+    type F = F;
+
+    type Outputs<U> = [U; 2];
+
+    fn from_param(g: Self::Param) -> Self
+    {
+        Self {
+            g,
+            w: [[F::zero(); K]; 2],
+            denormal_protection: DenormalProtection::None
+        }
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.g
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.g
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.g
+    }
+}
+
+impl<F, const K: usize> Rtf for HalfBandInterpolator<F, K>
+where
+    F: FilterFloat
+{
+    fn filter(&mut self, _rate: F, x: F) -> [F; 2]
+    {
+        self.push(x)
+    }
+
+    fn z_response(&mut self, _rate: F, z: Complex<F>) -> [Complex<F>; 2]
+    {
+        let z_inv = z.inv();
+        let h0 = self.g.iter()
+            .enumerate()
+            .map(|(m, &g)| (z.powi(m as i32) + z_inv.powi(m as i32 + 1))*g)
+            .fold(Complex::from(F::zero()), |h, t| h + t);
+        let delay = z_inv.powi(K as i32 - 1);
+        [
+            (h0 + h0)*delay,
+            delay
+        ]
+    }
+
+    fn reset(&mut self)
+    {
+        self.w = [[F::zero(); K]; 2]
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<F>
+    {
+        self.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<F>)
+    {
+        self.denormal_protection = denormal_protection
+    }
+}
+
+/// Downsamples a signal by a factor of two, with a polyphase half-band filter with `K` side taps.
+///
+/// Only the nonzero taps are ever computed. One of the two polyphase branches is a pure delay, and the other one is symmetric, so each output sample costs `K`
+/// multiplications.
+///
+/// As an [`Rtf`], it's fed one sample at a time at the higher sample rate. A new output sample is made on the first of every two input samples, and held
+/// through the second, so every other output sample is the decimated signal. Its response is that of the decimation filter (see
+/// [`half_band_z_response`]), which the new output samples follow exactly. The parameter is the side taps `g`.
+///
+/// # Example
+///
+/// ```rust
+/// use real_time_fir_iir_filters::{
+///     rtf::Rtf,
+///     filters::fir::{HalfBandDecimator, HALF_BAND_120DB}
+/// };
+///
+/// let mut decimator = HalfBandDecimator::<f64>::new();
+///
+/// // One new sample out for every two samples in
+/// let [y] = decimator.filter(88200.0, 1.0);
+/// let [_] = decimator.filter(88200.0, 0.0);
+///
+/// // A steeper design
+/// let mut decimator = HalfBandDecimator::<f64, _>::from_taps(HALF_BAND_120DB);
+///
+/// let mut y = [0.0; 2];
+/// decimator.filter_block(&[1.0, 0.0, 0.0, 0.0], &mut y);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HalfBandDecimator<F, const K: usize = HALF_BAND_TAPS>
+where
+    F: FilterFloat
+{
+    g: [F; K],
+    even: [[F; K]; 2],
+    odd: [F; K],
+    y: F,
+    phase: usize,
+    denormal_protection: DenormalProtection<F>
+}
+
+impl<F> HalfBandDecimator<F>
+where
+    F: FilterFloat
+{
+    /// Creates a decimator with the default half-band design, [`HALF_BAND`].
+    pub fn new() -> Self
+    {
+        Self::from_taps(HALF_BAND)
+    }
+}
+
+impl<F, const K: usize> HalfBandDecimator<F, K>
+where
+    F: FilterFloat
+{
+    /// Delay of the decimator, in samples at the higher sample rate.
+    pub const DELAY: usize = 2*K - 1;
+
+    /// Creates a decimator from the side taps of a half-band design (see [`half_band_z_response`]).
+    pub fn from_taps(g: [f64; K]) -> Self
+    {
+        Self::from_param(g.map(|g| f!(g)))
+    }
+
+    fn push(&mut self, x: F) -> F
+    {
+        if self.phase == 0
+        {
+            let [e_new, e_old] = &mut self.even;
+            e_old.rotate_right(1);
+            e_old[0] = e_new[K - 1];
+            e_new.rotate_right(1);
+            e_new[0] = x;
+
+            self.y = self.g.iter()
+                .zip(e_new.iter().rev())
+                .zip(e_old.iter())
+                .map(|((&g, &e1), &e2)| g*(e1 + e2))
+                .fold(self.odd[K - 1]*f!(0.5), |y, t| y + t);
+        }
+        else
+        {
+            self.odd.rotate_right(1);
+            self.odd[0] = x;
+        }
+        self.phase = 1 - self.phase;
+
+        self.y
+    }
+
+    /// Feeds a block of samples through the decimator. The input block must be twice as long as the output block.
+    pub fn filter_block(&mut self, x: &[F], y: &mut [F])
+    {
+        assert_eq!(x.len(), 2*y.len(), "The input block must be twice as long as the output block");
+        for (x, y) in x.chunks_exact(2)
+            .zip(y.iter_mut())
+        {
+            self.push(x[0]);
+            *y = self.push(x[1])
+        }
+    }
+}
+
+impl<F> Default for HalfBandDecimator<F>
+where
+    F: FilterFloat
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<F, const K: usize> RtfBase for HalfBandDecimator<F, K>
+where
+    F: FilterFloat
+{
+    type Param = [F; K];
+    type Conf = All;
+    type F = F;
+
+    type Outputs<U> = [U; 1];
+
+    fn from_param(g: Self::Param) -> Self
+    {
+        Self {
+            g,
+            even: [[F::zero(); K]; 2],
+            odd: [F::zero(); K],
+            y: F::zero(),
+            phase: 0,
+            denormal_protection: DenormalProtection::None
+        }
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.g
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.g
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.g
+    }
+}
+
+impl<F, const K: usize> Rtf for HalfBandDecimator<F, K>
+where
+    F: FilterFloat
+{
+    fn filter(&mut self, _rate: F, x: F) -> [F; 1]
+    {
+        [self.push(x)]
+    }
+
+    fn z_response(&mut self, _rate: F, z: Complex<F>) -> [Complex<F>; 1]
+    {
+        [half_band_z_response(&self.g, z)]
+    }
+
+    fn reset(&mut self)
+    {
+        self.even = [[F::zero(); K]; 2];
+        self.odd = [F::zero(); K];
+        self.y = F::zero();
+        self.phase = 0
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<F>
+    {
+        self.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<F>)
+    {
+        self.denormal_protection = denormal_protection
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use num::Complex;
+
+    use crate::rtf::Rtf;
+
+    use super::{half_band_z_response, HalfBandDecimator, HalfBandInterpolator, HALF_BAND_100DB, HALF_BAND_120DB, HALF_BAND_60DB, HALF_BAND_80DB};
+
+    #[test]
+    fn round_trip()
+    {
+        fn test<const K: usize>(attenuation: f64, g: [f64; K])
+        {
+            const N: usize = 512;
+            const OMEGA: f64 = 0.1;
+            const RATE: f64 = 44100.0;
+
+            let mut interpolator = HalfBandInterpolator::from_taps(g);
+            let mut decimator = HalfBandDecimator::from_taps(g);
+            let delay = HalfBandDecimator::<f64, K>::DELAY;
+
+            let x = |n: usize| (OMEGA*n as f64).sin();
+
+            let mut x_block = [0.0; N];
+            let mut y_up = [0.0; 2*N];
+            let mut y = [0.0; N];
+            for (n, x_block) in x_block.iter_mut()
+                .enumerate()
+            {
+                *x_block = x(n)
+            }
+            interpolator.filter_block(&x_block, &mut y_up);
+            decimator.filter_block(&y_up, &mut y);
+
+            // Passband is flat, and the two filters delay the signal by one half-band delay at the lower rate
+            for (n, &y) in y.iter()
+                .enumerate()
+                .skip(2*delay)
+            {
+                assert!((y - x(n - delay)).abs() < 1e-3, "{attenuation}dB");
+            }
+            let h = half_band_z_response(&g, Complex::cis(OMEGA/2.0));
+            assert!((h.norm() - 1.0).abs() < 1e-3, "{attenuation}dB");
+
+            // Stopband
+            for omega in [0.3, 0.35, 0.4, 0.45, 0.5]
+            {
+                let h = half_band_z_response(&g, Complex::cis(omega*TAU));
+                assert!(h.norm() < 10f64.powf(-attenuation/20.0), "{attenuation}dB: |H({omega})| = {}", h.norm());
+            }
+
+            // The polyphase branches of the interpolator make up the half-band filter at the doubled sample rate, with a gain of 2, and the decimator
+            // follows the half-band filter itself
+            for omega in [0.01, 0.1, 0.3, 0.5]
+            {
+                let z = Complex::cis(omega*TAU);
+                let [h0, h1] = interpolator.z_response(RATE, z*z);
+                let [h_decimator] = decimator.z_response(2.0*RATE, z);
+                let h = half_band_z_response(&g, z);
+                assert!((h0 + h1/z - h*2.0).norm() < 1e-9, "{attenuation}dB: {} != {}", h0 + h1/z, h*2.0);
+                assert_eq!(h_decimator, h);
+            }
+        }
+
+        test(60.0, HALF_BAND_60DB);
+        test(80.0, HALF_BAND_80DB);
+        test(100.0, HALF_BAND_100DB);
+        test(120.0, HALF_BAND_120DB);
+    }
+}
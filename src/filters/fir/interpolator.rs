@@ -0,0 +1,165 @@
+use num::Complex;
+
+use crate::{conf::All, f, filters::fir::{kaiser_beta, kaiser_low_pass}, param::FilterFloat, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+/// Upsamples a signal by an integer factor `M`, with a polyphase low-pass FIR filter with `M*L` taps.
+///
+/// The filter is split into `M` polyphase branches of `L` taps each, one for each output sample, so that none of the stuffed zeros are ever multiplied. Each input
+/// sample costs `M*L` multiplications.
+///
+/// For a factor of two, [`HalfBandInterpolator`](crate::filters::fir::HalfBandInterpolator) is about four times cheaper.
+///
+/// As an [`Rtf`], it's fed one sample at a time at the lower sample rate, and outputs the `M` resulting samples at the higher sample rate, in order. Each
+/// output is a polyphase branch, and its response is the response of that branch at the lower sample rate. The parameter is the taps of the polyphase
+/// branches, where branch `p` holds the taps `M·h[lM + p]`.
+///
+/// <pre>
+///         L-1
+/// Hₚ(z) =  Σ  M·h[lM + p]z⁻ˡ
+///         l=0
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// use real_time_fir_iir_filters::{
+///     rtf::Rtf,
+///     filters::fir::Interpolator
+/// };
+///
+/// // Upsample by 3, with 3*16 taps and 80dB of attenuation
+/// let mut interpolator = Interpolator::<f64, 3, 16>::new(80.0);
+///
+/// // Three samples out for each sample in
+/// let [y0, y1, y2] = interpolator.filter(44100.0, 1.0);
+///
+/// let mut y = [0.0; 6];
+/// interpolator.filter_block(&[0.0; 2], &mut y);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interpolator<F, const M: usize, const L: usize>
+where
+    F: FilterFloat
+{
+    h: [[F; L]; M],
+    w: [F; L],
+    denormal_protection: DenormalProtection<F>
+}
+
+impl<F, const M: usize, const L: usize> Interpolator<F, M, L>
+where
+    F: FilterFloat
+{
+    /// Delay of the interpolator, in samples at the higher sample rate, from the first sample of each block.
+    pub const DELAY: f64 = (M*L - 1) as f64/2.0;
+
+    /// Creates an interpolator with a Kaiser-windowed sinc low-pass filter, with a stopband attenuation of `attenuation` dB.
+    ///
+    /// The filter is -6dB at the Nyquist frequency of the lower sample rate. The more taps, the narrower the transition band.
+    pub fn new(attenuation: f64) -> Self
+    {
+        let mut h = [[0.0; M]; L];
+        kaiser_low_pass(h.as_flattened_mut(), 1.0/M as f64, kaiser_beta(attenuation));
+        Self::from_taps(h.as_flattened())
+    }
+
+    /// Creates an interpolator from the `M*L` taps of a low-pass FIR filter at the higher sample rate, with a DC gain of one.
+    ///
+    /// The taps are scaled by `M` to make up for the energy lost to the stuffed zeros.
+    pub fn from_taps(h: &[f64]) -> Self
+    {
+        assert_eq!(h.len(), M*L, "The interpolator needs exactly M*L taps");
+        Self::from_param(core::array::from_fn(|p| core::array::from_fn(|l| f!(h[l*M + p]*M as f64))))
+    }
+
+    fn push(&mut self, x: F) -> [F; M]
+    {
+        self.w.rotate_right(1);
+        self.w[0] = x;
+        self.h.each_ref()
+            .map(|h| h.iter()
+                .zip(self.w.iter())
+                .map(|(&h, &w)| h*w)
+                .fold(F::zero(), |y, t| y + t)
+            )
+    }
+
+    /// Feeds a block of samples through the interpolator. The output block must be `M` times as long as the input block.
+    pub fn filter_block(&mut self, x: &[F], y: &mut [F])
+    {
+        assert_eq!(y.len(), M*x.len(), "The output block must be M times as long as the input block");
+        for (&x, y) in x.iter()
+            .zip(y.chunks_exact_mut(M))
+        {
+            y.copy_from_slice(&self.push(x))
+        }
+    }
+}
+
+impl<F, const M: usize, const L: usize> RtfBase for Interpolator<F, M, L>
+where
+    F: FilterFloat
+{
+    type Param = [[F; L]; M];
+    type Conf = All;
+    type F = F;
+
+    type Outputs<U> = [U; M];
+
+    fn from_param(h: Self::Param) -> Self
+    {
+        Self {
+            h,
+            w: [F::zero(); L],
+            denormal_protection: DenormalProtection::None
+        }
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.h
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.h
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.h
+    }
+}
+
+impl<F, const M: usize, const L: usize> Rtf for Interpolator<F, M, L>
+where
+    F: FilterFloat
+{
+    fn filter(&mut self, _rate: F, x: F) -> [F; M]
+    {
+        self.push(x)
+    }
+
+    fn z_response(&mut self, _rate: F, z: Complex<F>) -> [Complex<F>; M]
+    {
+        let z_inv = z.inv();
+        self.h.each_ref()
+            .map(|h| h.iter()
+                .enumerate()
+                .map(|(l, &h)| z_inv.powi(l as i32)*h)
+                .fold(Complex::from(F::zero()), |h, t| h + t)
+            )
+    }
+
+    fn reset(&mut self)
+    {
+        self.w = [F::zero(); L]
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<F>
+    {
+        self.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<F>)
+    {
+        self.denormal_protection = denormal_protection
+    }
+}
@@ -1,5 +1,9 @@
 moddef::moddef!(
     flat(pub) mod {
+        decimator,
+        design,
+        half_band,
+        interpolator,
         nth
     }
 );
\ No newline at end of file
@@ -0,0 +1,194 @@
+use num::Complex;
+
+use crate::{conf::All, filters::delay_line::DelayLine, param::{DelayGain, FilterFloat, Param}, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+/// A feedforward comb filter, with a delay of up to `MAX` samples.
+///
+/// Adds a delayed copy of the signal to itself, which puts `M` evenly spaced notches (or peaks, for positive `g`) between DC and the sample rate. Since it has no
+/// feedback, it is always stable, and the delay can be swept freely, which makes it the building block of flangers and chorus effects.
+///
+/// The delay `M` is clamped to `1..=MAX`, and linearly interpolated between whole samples, so that it can be swept without zipper noise. The delay line is
+/// stored inline, so take care with large `MAX` on the stack.
+///
+/// <pre>
+/// H(z) = 1 + g z⁻ᴹ
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     param::DelayGain,
+///     rtf::Rtf,
+///     filters::fir::FeedforwardCombFilter
+/// };
+///
+/// let mut filter = FeedforwardCombFilter::<1024>::new(DelayGain {delay: 441.0, gain: 0.7});
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+/// ```
+///
+/// # Frequency response
+///
+/// M = 8, g = 0.7
+///
+/// <div>
+/// <img alt="Feedforward comb filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/feedforward_comb_filter.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeedforwardCombFilter<const MAX: usize, F = f64>
+where
+    F: FilterFloat
+{
+    pub param: Param<DelayGain<F>>,
+    delay_line: DelayLine<F, MAX>,
+    denormal_protection: DenormalProtection<F>,
+    #[serde(skip, default = "DenormalProtection::<F>::seed")]
+    seed: u32
+}
+
+impl<const MAX: usize, F> FeedforwardCombFilter<MAX, F>
+where
+    F: FilterFloat
+{
+    pub fn new(param: DelayGain<F>) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            delay_line: DelayLine::new(),
+            denormal_protection: DenormalProtection::None,
+            seed: DenormalProtection::<F>::seed()
+        }
+    }
+}
+
+impl<const MAX: usize, F> RtfBase for FeedforwardCombFilter<MAX, F>
+where
+    F: FilterFloat
+{
+    type Param = DelayGain<F>;
+    type Conf = All;
+    type F = F;
+
+    type Outputs<U> = [U; 1];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<const MAX: usize, F> Rtf for FeedforwardCombFilter<MAX, F>
+where
+    F: FilterFloat
+{
+    fn filter(&mut self, _rate: F, x: F) -> [F; 1]
+    {
+        let DelayGain {delay, gain} = *self.param;
+
+        let y = x + gain*self.delay_line.read(delay);
+        self.delay_line.push(x);
+        [y]
+    }
+
+    fn z_response(&mut self, _rate: F, z: Complex<F>) -> [Complex<F>; 1]
+    {
+        let DelayGain {delay, gain} = *self.param;
+
+        [DelayLine::<F, MAX>::z_response(delay, z)*gain + F::one()]
+    }
+
+    fn reset(&mut self)
+    {
+        self.delay_line.reset()
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<F>
+    {
+        self.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<F>)
+    {
+        self.denormal_protection = denormal_protection
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{param::DelayGain, rtf::Rtf};
+
+    use super::FeedforwardCombFilter;
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = FeedforwardCombFilter::<16>::new(DelayGain {delay: 8.0, gain: 0.7});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn impulse_response()
+    {
+        const RATE: f64 = 44100.0;
+        const M: usize = 5;
+
+        let mut filter = FeedforwardCombFilter::<16>::new(DelayGain {delay: M as f64, gain: 0.5});
+
+        for n in 0..32
+        {
+            let [y] = filter.filter(RATE, if n == 0 {1.0} else {0.0});
+            let h = match n
+            {
+                0 => 1.0,
+                M => 0.5,
+                _ => 0.0
+            };
+            assert_eq!(y, h, "n = {n}");
+        }
+    }
+    #[test]
+    fn fractional_delay()
+    {
+        const RATE: f64 = 44100.0;
+        const N: usize = 16;
+
+        let mut filter = FeedforwardCombFilter::<16>::new(DelayGain {delay: 4.25, gain: 0.5});
+
+        let mut h = [0.0; N];
+        for (n, h) in h.iter_mut()
+            .enumerate()
+        {
+            [*h] = filter.filter(RATE, if n == 0 {1.0} else {0.0});
+        }
+        assert_eq!(h[..7], [1.0, 0.0, 0.0, 0.0, 0.375, 0.125, 0.0]);
+
+        // The transfer function must match the interpolated impulse response
+        for k in 0..=8
+        {
+            let omega = k as f64/8.0*core::f64::consts::PI;
+            let [h_z] = filter.frequency_response(RATE, omega);
+            let h_n = h.iter()
+                .enumerate()
+                .map(|(n, &h)| num::Complex::cis(-omega*n as f64)*h)
+                .sum::<num::Complex<f64>>();
+            assert!((h_z - h_n).norm() < 1e-12, "omega = {omega}");
+        }
+    }
+}
\ No newline at end of file
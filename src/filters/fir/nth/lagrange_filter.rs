@@ -0,0 +1,184 @@
+use core::fmt::Debug;
+
+use array_trait::AsSlice;
+
+use crate::{array_plus1, calc::fir::nth::LagrangeCalc, conf::All, internals::{AInternalsFor, BInternalsFor, RtfInternalsFor}, param::{Delay, FilterFloat, Param}, rtf::{RtfBase, StaticRtf}};
+
+type Internals<F, const N: usize> = crate::rtfinternals!(F, 1, 1, 1, 0, N, false);
+
+/// A Lagrange-interpolation FIR fractional delay filter of order `N`.
+///
+/// Delays the signal by a fractional number of samples `D`, by fitting an `N`-th order polynomial through the last `N + 1` samples. The response is maximally flat
+/// at DC, and rolls off towards the Nyquist frequency (unless `D` is an integer). Since it has no feedback, `D` can be modulated as fast as you like without
+/// transients, which makes it a good fit for chorus, flanger and vibrato effects.
+///
+/// It works best for `(N - 1)/2 ≤ D ≤ (N + 1)/2`, where the interpolation is centered. For longer delays, use an integer delay line for the rest.
+///
+/// <pre>
+///         N
+/// H(z) =  Σ  hₙz⁻ⁿ
+///        n=0
+///
+///           D - k
+/// hₙ =  Π   -----
+///      k≠n  n - k
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     change::Change,
+///     param::Delay,
+///     rtf::Rtf,
+///     filters::fir::LagrangeFilter
+/// };
+///
+/// // A 3. order Lagrange filter, delaying by 1.3 samples
+/// let mut filter = LagrangeFilter::<3>::new(Delay {delay: 1.3});
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+///
+/// // Smoothly modulate the delay
+/// filter.param.change(Delay {delay: 1.8}, 0.01);
+/// ```
+///
+/// # Frequency response
+///
+/// N = 3, D = 1.3
+///
+/// <div>
+/// <img alt="Lagrange filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/lagrange_filter.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LagrangeFilter<const N: usize, F = f64>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    pub param: Param<Delay<F>>,
+    pub internals: Internals<F, N>
+}
+
+impl<const N: usize, F> LagrangeFilter<N, F>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    pub const fn new(param: Delay<F>) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            internals: Internals::<F, N>::new()
+        }
+    }
+}
+
+impl<const N: usize, F> RtfBase for LagrangeFilter<N, F>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    type Param = Delay<F>;
+    type Conf = All;
+    type F = F;
+    type Outputs<U> = [U; 1];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<const N: usize, F> StaticRtf for LagrangeFilter<N, F>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    type IsIir<U> = [U; 0];
+    type Order<U> = [U; N];
+    type OutputBufs<U> = [U; 1];
+    type SosBufs<U> = [U; 1];
+    type SosStages<U> = [U; 0];
+
+    #[allow(clippy::type_complexity)]
+    fn get_internals(&self) -> (&RtfInternalsFor<Self>, &Param<Self::Param>)
+    {
+        (&self.internals, &self.param)
+    }
+    #[allow(clippy::type_complexity)]
+    fn get_internals_mut(&mut self) -> (&mut RtfInternalsFor<Self>, &mut Param<Self::Param>)
+    {
+        (&mut self.internals, &mut self.param)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn make_coeffs(param: &Self::Param, _rate: Self::F) -> (
+        BInternalsFor<Self>,
+        Self::IsIir<AInternalsFor<Self>>
+    )
+    {
+        let calc = LagrangeCalc::new(*param);
+        let mut b: array_plus1!([F; N]) = unsafe {core::mem::zeroed()};
+        calc.b(b.as_mut_slice());
+        (
+            ([], [], [
+                b
+            ]),
+            []
+        )
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{param::Delay, rtf::Rtf};
+
+    use super::LagrangeFilter;
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = LagrangeFilter::<3>::new(Delay {delay: 1.3});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn interpolation()
+    {
+        const RATE: f64 = 44100.0;
+        const OMEGA: f64 = 0.01;
+        const N: usize = 64;
+
+        for delay in [0.0, 1.0, 1.3, 1.5, 2.0]
+        {
+            let mut filter = LagrangeFilter::<3>::new(Delay {delay});
+
+            let x = |n: f64| if n >= 0.0 {(OMEGA*n).sin()} else {0.0};
+            for n in 0..N
+            {
+                let [y] = filter.filter(RATE, x(n as f64));
+                if n > 3
+                {
+                    assert!((y - x(n as f64 - delay)).abs() < 1e-6, "D = {delay}");
+                }
+            }
+        }
+    }
+}
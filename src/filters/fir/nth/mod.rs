@@ -1,6 +1,13 @@
+moddef::moddef!(
+    flat(pub) mod {
+        feedforward_comb_filter for cfg(feature = "comb"),
+        fir_hilbert_filter for cfg(feature = "hilbert"),
+        lagrange_filter for cfg(feature = "lagrange")
+    }
+);
+
 /*moddef::moddef!(
     flat(pub) mod {
         impulse_filter
     }
 );*/
-
@@ -0,0 +1,105 @@
+use crate::{calc::iir::first::DcBlockerCalc, param::{DcBlockerFilterParam, PoleRadius}};
+
+crate::def_rtf!(
+    {
+        /// # Configuration
+        /// 
+        /// A leaky differentiator, removing DC offset with a zero at `z = 1`, and a pole at `z = R` just inside of it. The closer `R` is to one, the lower the
+        /// cutoff frequency. It can also be parameterized by the cutoff frequency in Hz, with [`Cutoff`](crate::param::Cutoff), which is mapped to
+        /// `R = exp(-2πf/rate)`. This is accurate for cutoff frequencies well below the sample rate, which is always the case for a DC blocker.
+        /// 
+        /// The gain at the Nyquist frequency is `2/(1 + R)`, which is slightly more than one. The second output compensates for this, so that the gain is
+        /// at most exactly one.
+        /// 
+        /// <pre>
+        /// 0) DC-BLOCKER:
+        /// 
+        ///          1 - z⁻¹
+        /// H(z) = ---------
+        ///        1 - Rz⁻¹
+        /// 
+        /// 1) GAIN COMPENSATED DC-BLOCKER:
+        /// 
+        ///        1 + R  1 - z⁻¹
+        /// H(z) = ----- ---------
+        ///          2   1 - Rz⁻¹
+        /// </pre>
+        /// 
+        /// # Frequency response
+        /// 
+        /// R = 0.995
+        /// 
+        /// <div>
+        /// <img alt="DC blocker filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/dc_blocker_filter.png" height="500">
+        /// </div>
+    }
+    DcBlockerFilter
+    {
+        type Param: DcBlockerFilterParam = PoleRadius;
+
+        const OUTPUTS: usize = 2;
+        const OUTPUT_BUFS: usize = 1;
+        const SOS_BUFS: usize = 1;
+        const SOS_STAGES: usize = 0;
+        const ORDER: usize = 1;
+        const IS_IIR: bool = true;
+
+        fn make_coeffs(param, rate) -> _
+        {
+            let calc = DcBlockerCalc::new(param.pole_radius(rate));
+            (
+                ([], [], [
+                    calc.b(),
+                    calc.b_compensated()
+                ]),
+                [([], [
+                    calc.a()
+                ])]
+            )
+        }
+    }
+);
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::{FRAC_1_SQRT_2, PI, TAU};
+
+    use crate::{param::Cutoff, rtf::Rtf};
+
+    use super::{DcBlockerFilter, PoleRadius};
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = DcBlockerFilter::new(PoleRadius {radius: 0.995});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn dc_blocker()
+    {
+        const RATE: f64 = 44100.0;
+
+        let mut filter = DcBlockerFilter::new(Cutoff {cutoff: 10.0});
+
+        // No DC, and about -3dB at the cutoff
+        let [h0, h1] = filter.frequency_response(RATE, 0.0);
+        assert!(h0.norm() < 1e-12 && h1.norm() < 1e-12);
+        let [_, h1] = filter.frequency_response(RATE, 10.0*TAU/RATE);
+        assert!((h1.norm() - FRAC_1_SQRT_2).abs() < 1e-2, "{}", h1.norm());
+
+        // Gain compensated at Nyquist
+        let [h0, h1] = filter.frequency_response(RATE, PI);
+        assert!(h0.norm() > 1.0);
+        assert!((h1.norm() - 1.0).abs() < 1e-12);
+
+        // A constant offset decays away
+        let mut y = [0.0; 2];
+        for _ in 0..RATE as usize
+        {
+            y = filter.filter(RATE, 1.0);
+        }
+        assert!(y.iter().all(|y| y.abs() < 1e-6), "{y:?}");
+    }
+}
\ No newline at end of file
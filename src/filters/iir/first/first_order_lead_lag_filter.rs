@@ -0,0 +1,94 @@
+use crate::{calc::iir::first::FirstOrderLeadLagCalc, param::{FirstOrderLeadLagFilterParam, ZeroPole}};
+
+crate::def_rtf!(
+    {
+        /// # Configuration
+        /// 
+        /// <pre>
+        ///          1 + s/ω₀
+        /// H(s) = K --------
+        ///          1 + s/ωₚ
+        /// </pre>
+        /// 
+        /// A lead-compensator when the zero is below the pole, and a lag-compensator when the pole is below the zero.
+        /// 
+        /// # Frequency response
+        /// 
+        /// ω₀ = 100 Hz
+        /// 
+        /// ωₚ = 1 kHz
+        /// 
+        /// K = 1
+        /// 
+        /// <div>
+        /// <img alt="First order lead-lag filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/first_order_lead_lag_filter.png" height="500">
+        /// </div>
+    }
+    FirstOrderLeadLagFilter
+    {
+        type Param: FirstOrderLeadLagFilterParam = ZeroPole;
+
+        const OUTPUTS: usize = 1;
+        const OUTPUT_BUFS: usize = 1;
+        const SOS_BUFS: usize = 1;
+        const SOS_STAGES: usize = 0;
+        const ORDER: usize = 1;
+        const IS_IIR: bool = true;
+
+        fn make_coeffs(param, rate) -> _
+        {
+            let calc = FirstOrderLeadLagCalc::new(param.zero_pole(), rate);
+            (
+                ([], [], [
+                    calc.b()
+                ]),
+                [([], [
+                    calc.a()
+                ])]
+            )
+        }
+    }
+);
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::{FRAC_PI_4, TAU};
+
+    use crate::{param::PhaseLead, rtf::Rtf};
+
+    use super::{FirstOrderLeadLagFilter, ZeroPole};
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = FirstOrderLeadLagFilter::new(ZeroPole {zero: 100.0*TAU, pole: 1000.0*TAU, gain: 1.0});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn phase_lead()
+    {
+        const RATE: f64 = 44100.0;
+
+        let omega = 100.0*TAU;
+        let mut filter = FirstOrderLeadLagFilter::new(PhaseLead {omega, phase: FRAC_PI_4, gain: 2.0});
+
+        // The phase lead and gain at the centre frequency are as specified
+        let h = filter.frequency_response(RATE, omega/RATE)[0];
+        assert!((h.arg() - FRAC_PI_4).abs() < 1e-3, "{h}");
+        assert!((h.norm() - 2.0).abs() < 1e-3, "{h}");
+
+        // ...and it's the maximum phase lead
+        for f in [0.5, 0.8, 1.25, 2.0]
+        {
+            let h_f = filter.frequency_response(RATE, omega*f/RATE)[0];
+            assert!(h_f.arg() < h.arg());
+        }
+
+        // A negative phase gives a lag
+        let mut lag = FirstOrderLeadLagFilter::new(PhaseLead {omega, phase: -FRAC_PI_4, gain: 2.0});
+        let h = lag.frequency_response(RATE, omega/RATE)[0];
+        assert!((h.arg() + FRAC_PI_4).abs() < 1e-3, "{h}");
+    }
+}
\ No newline at end of file
@@ -1,7 +1,9 @@
 moddef::moddef!(
     flat(pub) mod {
+        dc_blocker_filter for cfg(feature = "dc_blocker"),
         first_order_all_pass_filter for cfg(feature = "first_order_all_pass"),
         first_order_filter for cfg(feature = "first_order"),
+        first_order_lead_lag_filter for cfg(feature = "lead_lag"),
         first_order_lr_filter for cfg(feature = "first_order_lr"),
         first_order_rc_filter for cfg(feature = "first_order_rc"),
         pi_filter for cfg(feature = "pi")
@@ -33,7 +33,7 @@ crate::def_rtf!(
 
         fn make_coeffs(param, rate) -> _
         {
-            let calc = PICalc::new(param.pi(), rate);
+            let calc = PICalc::new(param.pi(), rate, param.discretization());
             (
                 ([], [], [
                     calc.b()
@@ -49,6 +49,8 @@ crate::def_rtf!(
 #[cfg(test)]
 mod test
 {
+    use crate::{param::{Discretization, Discretized}, rtf::Rtf};
+
     use super::{PIFilter, PI};
 
     #[test]
@@ -57,4 +59,36 @@ mod test
         let mut filter = PIFilter::new(PI {p: 1.0, i: 0.001});
         crate::tests::plot_freq(&mut filter).unwrap();
     }
+
+    #[test]
+    fn discretization()
+    {
+        const RATE: f64 = 100.0;
+        const T: f64 = 1.0/RATE;
+
+        let param = PI {p: 2.0, i: 3.0};
+        for discretization in [Discretization::ForwardEuler, Discretization::BackwardEuler, Discretization::Tustin]
+        {
+            let mut filter = PIFilter::new(Discretized {param, discretization});
+
+            // u[n] = u[n - 1] + P(e[n] - e[n - 1]) + IT(αe[n] + βe[n - 1])
+            let (alpha, beta) = match discretization
+            {
+                Discretization::ForwardEuler => (0.0, 1.0),
+                Discretization::BackwardEuler => (1.0, 0.0),
+                Discretization::Tustin => (0.5, 0.5)
+            };
+            let mut u_prev = 0.0;
+            let mut e_prev = 0.0;
+            for n in 0..64
+            {
+                let e = (0.3*n as f64).cos();
+                let u = u_prev + param.p*(e - e_prev) + param.i*T*(alpha*e + beta*e_prev);
+                let [y] = filter.filter(RATE, e);
+                assert!((y - u).abs() < 1e-9, "{discretization:?}, n = {n}");
+                u_prev = u;
+                e_prev = e;
+            }
+        }
+    }
 }
\ No newline at end of file
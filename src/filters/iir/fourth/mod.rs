@@ -1,5 +1,6 @@
 moddef::moddef!(
     flat(pub) mod {
+        moog_ladder_filter for cfg(feature = "moog_ladder"),
         wah_filter for cfg(feature = "wah")
     }
 );
\ No newline at end of file
@@ -0,0 +1,268 @@
+use num::{Complex, Zero};
+
+use crate::{calc::iir::fourth::MoogLadderCalc, conf::All, param::{FilterFloat, OmegaResonance, Param}, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+/// How the stages of a [`MoogLadderFilter`] respond to large signals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum LadderMode
+{
+    /// Every stage is linear, so the filter is exactly described by its transfer function, at any level.
+    Linear,
+    /// The input of every stage is saturated by `tanh`, like the differential pairs of the transistor ladder.
+    ///
+    /// Small signals (well below `1`) pass through just like in [`Linear`](LadderMode::Linear) mode, while louder signals are compressed, and
+    /// self-oscillation settles at a finite level instead of blowing up.
+    #[default]
+    Tanh
+}
+
+/// A Moog-style four-pole transistor ladder low-pass filter.
+///
+/// Four one-pole low-pass stages in cascade, with the output fed back, inverted, to the input. As the resonance approaches `1`, the feedback builds a sharp
+/// peak at the cutoff frequency, and at `1` the filter self-oscillates. The passband gain drops to `1/(1 + 4k)` as the resonance is turned up, just like in
+/// the original circuit.
+///
+/// Each stage is discretized with the topology-preserving transform (trapezoidal integration with prewarping), and the delay-free feedback loop is solved
+/// instantly, so the cutoff and resonance are accurate all the way up to the Nyquist frequency, and can be modulated at audio rate. In
+/// [`LadderMode::Tanh`] (the default), the feedback is solved for the linearized loop, and each stage then saturates its own input.
+///
+/// The frequency response is the one of [`LadderMode::Linear`], which is also the small-signal response in [`LadderMode::Tanh`].
+///
+/// <pre>
+///               1
+/// H(s) = ---------------
+///        (1 + s/ω)⁴ + 4k
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use core::f64::consts::TAU;
+///
+/// use real_time_fir_iir_filters::{
+///     param::OmegaResonance,
+///     rtf::Rtf,
+///     filters::iir::fourth::{LadderMode, MoogLadderFilter}
+/// };
+///
+/// let mut filter = MoogLadderFilter::new(OmegaResonance {omega: 1000.0*TAU, resonance: 0.8});
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+///
+/// // Use the linear model instead
+/// filter.mode = LadderMode::Linear;
+/// ```
+///
+/// # Frequency response
+///
+/// ω = 1 kHz, k = 0.8
+///
+/// <div>
+/// <img alt="Moog ladder filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/moog_ladder_filter.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MoogLadderFilter<F = f64>
+where
+    F: FilterFloat
+{
+    pub param: Param<OmegaResonance<F>>,
+    pub mode: LadderMode,
+    denormal_protection: DenormalProtection<F>,
+    s: [F; 4],
+    #[serde(skip, default = "DenormalProtection::<F>::seed")]
+    seed: u32,
+    #[serde(skip)]
+    rate: Option<F>,
+    #[serde(skip)]
+    g: F,
+    #[serde(skip)]
+    feedback: F
+}
+
+impl<F> MoogLadderFilter<F>
+where
+    F: FilterFloat
+{
+    pub fn new(param: OmegaResonance<F>) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            mode: LadderMode::default(),
+            denormal_protection: DenormalProtection::None,
+            s: [F::zero(); 4],
+            seed: DenormalProtection::<F>::seed(),
+            rate: None,
+            g: F::zero(),
+            feedback: F::zero()
+        }
+    }
+
+    fn update(&mut self, rate: F)
+    {
+        if !self.param.is_unchanged_then_set() || self.rate != Some(rate)
+        {
+            let calc = MoogLadderCalc::new(*self.param, rate);
+            self.g = calc.g();
+            self.feedback = calc.feedback();
+            self.rate = Some(rate)
+        }
+    }
+}
+
+impl<F> RtfBase for MoogLadderFilter<F>
+where
+    F: FilterFloat
+{
+    type Param = OmegaResonance<F>;
+    type Conf = All;
+    type F = F;
+
+    type Outputs<U> = [U; 1];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<F> Rtf for MoogLadderFilter<F>
+where
+    F: FilterFloat
+{
+    fn filter(&mut self, rate: F, x: F) -> [F; 1]
+    {
+        self.update(rate);
+
+        let g = self.g;
+        let big_g = g/(F::one() + g);
+        let beta = F::one() - big_g;
+
+        // Output of the linear ladder as a function of its input: y = G⁴u + σ
+        let sigma = self.s.iter()
+            .fold(F::zero(), |sigma, &s| sigma*big_g + beta*s);
+        let big_g2 = big_g*big_g;
+        let big_g4 = big_g2*big_g2;
+        let y = (big_g4*x + sigma)/(F::one() + self.feedback*big_g4);
+
+        let denormal_protection = self.denormal_protection;
+        let offset = denormal_protection.offset(&mut self.seed);
+
+        let mut u = x - self.feedback*y + offset;
+        for s in self.s.iter_mut()
+        {
+            let v = match self.mode
+            {
+                LadderMode::Linear => (u - *s)*big_g,
+                LadderMode::Tanh => (u.tanh() - s.tanh())*big_g
+            };
+            u = v + *s;
+            *s = u + v;
+        }
+
+        denormal_protection.flush(&mut self.s);
+
+        [u]
+    }
+
+    fn z_response(&mut self, rate: F, z: Complex<F>) -> [Complex<F>; 1]
+    {
+        let calc = MoogLadderCalc::new(*self.param, rate);
+        let z_inv = z.inv();
+        let horner = |c: [F; 5]| c.into_iter()
+            .rev()
+            .fold(Complex::<F>::zero(), |h, c| h*z_inv + c);
+        [horner(calc.b())/horner(calc.a())]
+    }
+
+    fn reset(&mut self)
+    {
+        self.s = [F::zero(); 4]
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<F>
+    {
+        self.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<F>)
+    {
+        self.denormal_protection = denormal_protection
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use crate::{param::OmegaResonance, rtf::Rtf};
+
+    use super::{LadderMode, MoogLadderFilter};
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = MoogLadderFilter::new(OmegaResonance {omega: 1000.0*TAU, resonance: 0.8});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn linear()
+    {
+        const RATE: f64 = 44100.0;
+        const N: usize = 64;
+
+        let param = OmegaResonance {omega: 5000.0*TAU, resonance: 0.9};
+        let mut filter = MoogLadderFilter::new(param);
+        filter.mode = LadderMode::Linear;
+
+        // The impulse response must match the transfer function
+        let calc = crate::calc::iir::fourth::MoogLadderCalc::new(param, RATE);
+        let (b, a) = (calc.b(), calc.a());
+        let mut y_ref = [0.0; N];
+        for n in 0..N
+        {
+            let x = |n: usize| if n == 0 {1.0} else {0.0};
+            y_ref[n] = ((0..5).filter(|&k| k <= n).map(|k| b[k]*x(n - k)).sum::<f64>()
+                - (1..5).filter(|&k| k <= n).map(|k| a[k]*y_ref[n - k]).sum::<f64>())/a[0];
+
+            let [y] = filter.filter(RATE, x(n));
+            assert!((y - y_ref[n]).abs() < 1e-12, "n = {n}");
+        }
+
+        // Small signals behave linearly in tanh mode too
+        let mut linear = MoogLadderFilter::new(param);
+        linear.mode = LadderMode::Linear;
+        let mut tanh = MoogLadderFilter::new(param);
+        for n in 0..N
+        {
+            let x = 1e-4*(0.1*n as f64).sin();
+            let [y_linear] = linear.filter(RATE, x);
+            let [y_tanh] = tanh.filter(RATE, x);
+            assert!((y_linear - y_tanh).abs() < 1e-10);
+        }
+
+        // Self-oscillation stays bounded in tanh mode
+        let mut filter = MoogLadderFilter::new(OmegaResonance {omega: 1000.0*TAU, resonance: 1.5});
+        for n in 0..1 << 16
+        {
+            let [y] = filter.filter(RATE, if n == 0 {1.0} else {0.0});
+            assert!(y.abs() < 2.0);
+        }
+    }
+}
\ No newline at end of file
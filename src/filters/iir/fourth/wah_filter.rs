@@ -1,72 +1,264 @@
-use crate::{calc::iir::fourth::WahCalc, param::{CrybabyGCB95, WahFilterParam}};
-
-// TODO: make it SOS
-crate::def_rtf!(
-    {
-        /// # Configuration
-        /// 
-        /// <pre>
-        ///                  V꜀꜀
-        ///                   |
-        ///                 [R꜀₁]
-        ///                   |
-        ///                   o-----o--------------------o----------o    V꜀꜀
-        ///                   |     |                    |          |     |
-        ///                   /    [Rⱼ]                 [C∞]       [Rⱼ] [R꜀₂]
-        ///                 |/      |                    |          |     |
-        /// X-[Cᵢ]-[Rᵢ]-o---| β     |                Y---o          |     /
-        ///             |   |\      |                    |   x      |   |/
-        ///            [Rₛ]   v     o----o----o----o   [Rₚₒₜ]<-[C∞]-o---| β
-        ///             |     |     |    |    |    |     |              |\
-        ///             |   [Rₑ₁]  [L]  [Rₚ] [R₉] [C₉]   V                v
-        ///             |     |     |    |    |    |                      |
-        ///             |     ⏚     |    |    ⏚    ⏚                      |
-        ///             |           |    |                                |
-        ///             o-----------o----o---------------------------[Cբ]-o
-        ///                                                               |
-        ///                                                             [Rₑ₂]
-        ///                                                               |
-        ///                                                               ⏚
-        /// </pre>
-        /// 
-        /// # Frequency response
-        /// 
-        /// x = 0.3
-        /// 
-        /// <div>
-        /// <img alt="Wah filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/wah_filter.png" height="500">
-        /// </div>
-    }
-    WahFilter
-    {
-        type Param: WahFilterParam = CrybabyGCB95;
-
-        const OUTPUTS: usize = 1;
-        const OUTPUT_BUFS: usize = 1;
-        const SOS_BUFS: usize = 1;
-        const SOS_STAGES: usize = 0;
-        const ORDER: usize = 4;
-        const IS_IIR: bool = true;
-
-        fn make_coeffs(param, rate) -> _
+use core::iter::Sum;
+use core::ops::MulAssign;
+
+use num::{Complex, Zero};
+
+use crate::{calc::iir::fourth::WahNonlinearCalc, conf::All, param::{CrybabyGCB95, FilterFloat, FilterParam, WahFilterParam}, rtf::{DenormalProtection, Rtf, RtfBase, StaticRtf, Structure}};
+
+/// How Q1, the transistor in the feedback loop of a [`WahFilter`], responds to large signals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum WahMode
+{
+    /// Q1 is linearized around its operating point, so the filter is exactly described by its transfer function, at any level.
+    ///
+    /// The transfer function is factored into second-order sections whenever the parameters change, and run in the selected
+    /// [structure](WahFilter::set_structure).
+    #[default]
+    Linear,
+    /// The small-signal gain of Q1 follows its collector current, sample by sample.
+    ///
+    /// The collector current is estimated from the previous output sample, and the filter is linearized around it again for every sample. Since Q1 is inside
+    /// the feedback loop, this changes the resonance as well as the gain: as the signal drives Q1 towards cutoff or saturation, its gain drops, and the peak
+    /// is damped. Small signals pass through just like in [`Linear`](WahMode::Linear) mode.
+    ///
+    /// The coefficients change every sample, so they are run in transposed direct form II rather than as factored sections.
+    Nonlinear
+}
+
+mod linear
+{
+    use crate::{calc::iir::fourth::WahCalc, param::{CrybabyGCB95, WahFilterParam}, rtf::factor_sos};
+
+    crate::def_rtf!(
+        SosWahFilter
+        {
+            type Param: WahFilterParam = CrybabyGCB95;
+
+            const OUTPUTS: usize = 1;
+            const OUTPUT_BUFS: usize = 1;
+            const SOS_BUFS: usize = 1;
+            const SOS_STAGES: usize = 2;
+            const ORDER: usize = 0;
+            const IS_IIR: bool = true;
+
+            fn make_coeffs(param, rate) -> _
+            {
+                let calc = WahCalc::new(param, rate);
+                // Only fails for parameters that aren't finite, which make the output NaN in any form
+                let (([b1, b2], b), ([a1, a2], a)) = factor_sos(&calc.b(), &calc.a())
+                    .unwrap_or_else(|| {
+                        let nan = F::nan();
+                        (([[nan; 3]; 2], [nan]), ([[nan; 3]; 2], [nan]))
+                    });
+                (
+                    ([[b1]], [[b2]], [
+                        b
+                    ]),
+                    [([[a1], [a2]], [
+                        a
+                    ])]
+                )
+            }
+        }
+    );
+}
+
+/// # Configuration
+/// 
+/// <pre>
+///                  V꜀꜀
+///                   |
+///                 [R꜀₁]
+///                   |
+///                   o-----o--------------------o----------o    V꜀꜀
+///                   |     |                    |          |     |
+///                   /    [Rⱼ]                 [C∞]       [Rⱼ] [R꜀₂]
+///                 |/      |                    |          |     |
+/// X-[Cᵢ]-[Rᵢ]-o---| β     |                Y---o          |     /
+///             |   |\      |                    |   x      |   |/
+///            [Rₛ]   v     o----o----o----o   [Rₚₒₜ]<-[C∞]-o---| β
+///             |     |     |    |    |    |     |              |\
+///             |   [Rₑ₁]  [L]  [Rₚ] [R₉] [C₉]   V                v
+///             |     |     |    |    |    |                      |
+///             |     ⏚     |    |    ⏚    ⏚                      |
+///             |           |    |                                |
+///             o-----------o----o---------------------------[Cբ]-o
+///                                                               |
+///                                                             [Rₑ₂]
+///                                                               |
+///                                                               ⏚
+/// </pre>
+/// 
+/// In [`WahMode::Nonlinear`], the gain of Q1 follows its collector current inside the feedback loop. The frequency response is the small-signal one, which is
+/// the same in both [modes](WahMode).
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     param::VoxV847,
+///     rtf::Rtf,
+///     filters::iir::fourth::{WahFilter, WahMode}
+/// };
+///
+/// let mut filter = WahFilter::new(VoxV847 {x: 0.3});
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+///
+/// // Let Q1 saturate in the feedback loop
+/// filter.mode = WahMode::Nonlinear;
+/// ```
+///
+/// # Frequency response
+/// 
+/// x = 0.3
+/// 
+/// <div>
+/// <img alt="Wah filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/wah_filter.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WahFilter<F = f64, P = CrybabyGCB95<F>>
+where
+    F: FilterFloat,
+    P: WahFilterParam + FilterParam<F = F>
+{
+    pub mode: WahMode,
+    linear: linear::SosWahFilter<F, P>,
+    w: [F; 4],
+    y: F,
+    #[serde(skip, default = "DenormalProtection::<F>::seed")]
+    seed: u32
+}
+
+impl<P> WahFilter<<P as FilterParam>::F, P>
+where
+    P: WahFilterParam
+{
+    pub fn new(param: P) -> Self
+    {
+        Self {
+            mode: WahMode::default(),
+            linear: linear::SosWahFilter::new(param),
+            w: [P::F::zero(); 4],
+            y: P::F::zero(),
+            seed: DenormalProtection::<P::F>::seed()
+        }
+    }
+
+    /// Returns the realization structure of [`WahMode::Linear`].
+    pub fn structure(&self) -> Structure
+    {
+        self.linear.structure()
+    }
+
+    /// Selects the realization structure of [`WahMode::Linear`]. See [`StaticRtf::set_structure`].
+    pub fn set_structure(&mut self, structure: Structure)
+    {
+        self.linear.set_structure(structure)
+    }
+
+    fn filter_nonlinear(&mut self, rate: P::F, x: P::F) -> P::F
+    {
+        let calc = WahNonlinearCalc::new(&self.linear.param.circuit()).calc(self.y, rate);
+        let a = calc.a();
+        let a0 = a[0];
+        let [b0, b1, b2, b3, b4] = calc.b().map(|b| b/a0);
+        let [_, a1, a2, a3, a4] = a.map(|a| a/a0);
+
+        let denormal_protection = self.linear.internals.denormal_protection;
+        let x = x + denormal_protection.offset(&mut self.seed);
+
+        let [w0, w1, w2, w3] = self.w;
+        let y = b0*x + w0;
+        self.w = [
+            b1*x - a1*y + w1,
+            b2*x - a2*y + w2,
+            b3*x - a3*y + w3,
+            b4*x - a4*y
+        ];
+        self.y = y;
+
+        denormal_protection.flush(&mut self.w);
+
+        y
+    }
+}
+
+impl<P> RtfBase for WahFilter<<P as FilterParam>::F, P>
+where
+    P: WahFilterParam
+{
+    type Param = P;
+    type Conf = All;
+    type F = P::F;
+
+    type Outputs<U> = [U; 1];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        self.linear.get_param()
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        self.linear.get_param_mut()
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.linear.into_param()
+    }
+}
+
+impl<P> Rtf for WahFilter<<P as FilterParam>::F, P>
+where
+    P: WahFilterParam,
+    P::F: Sum,
+    Complex<P::F>: MulAssign
+{
+    fn filter(&mut self, rate: P::F, x: P::F) -> [P::F; 1]
+    {
+        match self.mode
         {
-            let calc = WahCalc::new(param, rate);
-            (
-                ([], [], [
-                    calc.b()
-                ]),
-                [([], [
-                    calc.a()
-                ])]
-            )
+            WahMode::Linear => self.linear.filter(rate, x),
+            WahMode::Nonlinear => [self.filter_nonlinear(rate, x)]
         }
     }
-);
+
+    /// Returns the response of [`WahMode::Linear`], which is also the small-signal response of [`WahMode::Nonlinear`].
+    fn z_response(&mut self, rate: P::F, z: Complex<P::F>) -> [Complex<P::F>; 1]
+    {
+        self.linear.z_response(rate, z)
+    }
+
+    fn reset(&mut self)
+    {
+        self.linear.reset();
+        self.w = [P::F::zero(); 4];
+        self.y = P::F::zero()
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<P::F>
+    {
+        self.linear.denormal_protection()
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<P::F>)
+    {
+        self.linear.set_denormal_protection(denormal_protection)
+    }
+}
 
 #[cfg(test)]
 mod test
 {
-    use super::{WahFilter, CrybabyGCB95};
+    use crate::{param::{ColorsoundWow, CrybabyGCB95, VoxV847, WahFilterParam}, rtf::{Rtf, Structure}};
+
+    use super::{WahFilter, WahMode};
 
     #[test]
     fn plot()
@@ -74,4 +266,81 @@ mod test
         let mut filter = WahFilter::new(CrybabyGCB95 {x: 0.3});
         crate::tests::plot_freq(&mut filter).unwrap();
     }
+
+    #[test]
+    fn structures()
+    {
+        const RATE: f64 = 44100.0;
+        const N: usize = 256;
+
+        let mut filter = WahFilter::new(CrybabyGCB95 {x: 0.3});
+        let responses = [
+            Structure::DirectFormI,
+            Structure::DirectFormII,
+            Structure::TransposedDirectFormII,
+            Structure::Lattice
+        ].map(|structure| {
+            filter.set_structure(structure);
+            assert_eq!(filter.structure(), structure);
+            core::array::from_fn::<_, N, _>(|n| filter.filter(RATE, if n == 0 {1.0} else {0.0})[0])
+        });
+        let peak = responses[1].iter()
+            .fold(0.0f64, |peak, y| peak.max(y.abs()));
+
+        for response in responses.iter()
+        {
+            for (y, y_ref) in response.iter()
+                .zip(responses[1].iter())
+            {
+                assert!((y - y_ref).abs() <= peak*1e-9, "{y} != {y_ref}")
+            }
+        }
+    }
+
+    #[test]
+    fn nonlinear()
+    {
+        const RATE: f64 = 44100.0;
+        const N: usize = 4096;
+
+        fn test<P>(param: P)
+        where
+            P: WahFilterParam<F = f64> + Copy
+        {
+            let x = |n: usize, level: f64| level*(440.0*core::f64::consts::TAU*n as f64/RATE).sin();
+
+            // Small signals behave linearly
+            let mut linear = WahFilter::new(param);
+            let mut nonlinear = WahFilter::new(param);
+            nonlinear.mode = WahMode::Nonlinear;
+            let mut peak = 0.0f64;
+            for n in 0..N
+            {
+                let [y_linear] = linear.filter(RATE, x(n, 1e-4));
+                let [y_nonlinear] = nonlinear.filter(RATE, x(n, 1e-4));
+                peak = peak.max(y_linear.abs());
+                assert!((y_linear - y_nonlinear).abs() < 1e-2*peak.max(1e-9));
+            }
+
+            // Hot signals are compressed, and stay bounded
+            let mut linear = WahFilter::new(param);
+            let mut nonlinear = WahFilter::new(param);
+            nonlinear.mode = WahMode::Nonlinear;
+            let mut peak_linear = 0.0f64;
+            let mut peak_nonlinear = 0.0f64;
+            for n in 0..N
+            {
+                let [y_linear] = linear.filter(RATE, x(n, 10.0));
+                let [y_nonlinear] = nonlinear.filter(RATE, x(n, 10.0));
+                assert!(y_nonlinear.is_finite());
+                peak_linear = peak_linear.max(y_linear.abs());
+                peak_nonlinear = peak_nonlinear.max(y_nonlinear.abs());
+            }
+            assert!(peak_nonlinear < peak_linear);
+        }
+
+        test(CrybabyGCB95 {x: 0.3});
+        test(VoxV847 {x: 0.3});
+        test(ColorsoundWow {x: 0.3});
+    }
 }
\ No newline at end of file
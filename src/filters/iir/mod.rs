@@ -4,7 +4,7 @@ moddef::moddef!(
         second,
         third,
         fourth,
-        //nth
+        nth
     }
 );
 
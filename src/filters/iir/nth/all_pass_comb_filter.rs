@@ -0,0 +1,178 @@
+use num::Complex;
+
+use crate::{conf::All, filters::delay_line::DelayLine, param::{DelayGain, FilterFloat, Param}, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+/// A Schroeder all-pass comb filter, with a delay of up to `MAX` samples.
+///
+/// Combines a feedback and a feedforward comb around the same delay line, so that the peaks and notches cancel, and the magnitude response is flat. It smears
+/// transients into a dense train of echoes without coloring the spectrum, which is why it's used as the diffusor in Schroeder and Freeverb style reverbs.
+/// It is stable as long as `|g| < 1`.
+///
+/// The delay `M` is clamped to `1..=MAX`, and linearly interpolated between whole samples, so that it can be swept without zipper noise. The interpolation
+/// dampens the highs slightly, so the magnitude response is only exactly flat for whole-sample delays. The delay line is stored inline, so take care with
+/// large `MAX` on the stack.
+///
+/// <pre>
+///        -g + z⁻ᴹ
+/// H(z) = ---------
+///        1 - g z⁻ᴹ
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     param::DelayGain,
+///     rtf::Rtf,
+///     filters::iir::nth::AllPassCombFilter
+/// };
+///
+/// let mut filter = AllPassCombFilter::<1024>::new(DelayGain {delay: 556.0, gain: 0.5});
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+/// ```
+///
+/// # Frequency response
+///
+/// M = 8, g = 0.7
+///
+/// <div>
+/// <img alt="All-pass comb filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/all_pass_comb_filter.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AllPassCombFilter<const MAX: usize, F = f64>
+where
+    F: FilterFloat
+{
+    pub param: Param<DelayGain<F>>,
+    delay_line: DelayLine<F, MAX>,
+    denormal_protection: DenormalProtection<F>,
+    #[serde(skip, default = "DenormalProtection::<F>::seed")]
+    seed: u32
+}
+
+impl<const MAX: usize, F> AllPassCombFilter<MAX, F>
+where
+    F: FilterFloat
+{
+    pub fn new(param: DelayGain<F>) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            delay_line: DelayLine::new(),
+            denormal_protection: DenormalProtection::None,
+            seed: DenormalProtection::<F>::seed()
+        }
+    }
+}
+
+impl<const MAX: usize, F> RtfBase for AllPassCombFilter<MAX, F>
+where
+    F: FilterFloat
+{
+    type Param = DelayGain<F>;
+    type Conf = All;
+    type F = F;
+
+    type Outputs<U> = [U; 1];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<const MAX: usize, F> Rtf for AllPassCombFilter<MAX, F>
+where
+    F: FilterFloat
+{
+    fn filter(&mut self, _rate: F, x: F) -> [F; 1]
+    {
+        let DelayGain {delay, gain} = *self.param;
+
+        let denormal_protection = self.denormal_protection;
+        let offset = denormal_protection.offset(&mut self.seed);
+
+        let v_m = self.delay_line.read(delay);
+        let v = x + gain*v_m;
+        denormal_protection.flush(core::slice::from_mut(self.delay_line.push(v + offset)));
+        [v_m - gain*v]
+    }
+
+    fn z_response(&mut self, _rate: F, z: Complex<F>) -> [Complex<F>; 1]
+    {
+        let DelayGain {delay, gain} = *self.param;
+
+        let z_m = DelayLine::<F, MAX>::z_response(delay, z);
+        [(z_m - gain)/(-z_m*gain + F::one())]
+    }
+
+    fn reset(&mut self)
+    {
+        self.delay_line.reset()
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<F>
+    {
+        self.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<F>)
+    {
+        self.denormal_protection = denormal_protection
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{param::DelayGain, rtf::Rtf};
+
+    use super::AllPassCombFilter;
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = AllPassCombFilter::<16>::new(DelayGain {delay: 8.0, gain: 0.7});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn all_pass()
+    {
+        const RATE: f64 = 44100.0;
+
+        let mut filter = AllPassCombFilter::<16>::new(DelayGain {delay: 5.0, gain: 0.5});
+
+        for k in 0..=64
+        {
+            let omega = k as f64/64.0*core::f64::consts::PI;
+            let [h] = filter.frequency_response(RATE, omega);
+            assert!((h.norm() - 1.0).abs() < 1e-12, "omega = {omega}");
+        }
+
+        // Energy is preserved
+        let mut energy = 0.0;
+        for n in 0..1024
+        {
+            let [y] = filter.filter(RATE, if n == 0 {1.0} else {0.0});
+            energy += y*y;
+        }
+        assert!((energy - 1.0).abs() < 1e-12);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,186 @@
+use num::Complex;
+
+use crate::{conf::All, filters::delay_line::DelayLine, param::{DelayGain, FilterFloat, Param}, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+/// A feedback comb filter, with a delay of up to `MAX` samples.
+///
+/// Feeds the delayed output back into the input, which gives a train of echoes decaying by `g` every `M` samples, and resonant peaks at multiples of `rate/M`.
+/// It is stable as long as `|g| < 1`. This is the comb filter of Schroeder and Moorer reverbs, and of Karplus-Strong string synthesis.
+///
+/// The delay `M` is clamped to `1..=MAX`, and linearly interpolated between whole samples, so that it can be swept without zipper noise. The delay line is
+/// stored inline, so take care with large `MAX` on the stack.
+///
+/// <pre>
+///            1
+/// H(z) = ---------
+///        1 - g z⁻ᴹ
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     param::DelayGain,
+///     rtf::Rtf,
+///     filters::iir::nth::FeedbackCombFilter
+/// };
+///
+/// let mut filter = FeedbackCombFilter::<2048>::new(DelayGain {delay: 1557.0, gain: 0.84});
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+/// ```
+///
+/// # Frequency response
+///
+/// M = 8, g = 0.7
+///
+/// <div>
+/// <img alt="Feedback comb filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/feedback_comb_filter.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeedbackCombFilter<const MAX: usize, F = f64>
+where
+    F: FilterFloat
+{
+    pub param: Param<DelayGain<F>>,
+    delay_line: DelayLine<F, MAX>,
+    denormal_protection: DenormalProtection<F>,
+    #[serde(skip, default = "DenormalProtection::<F>::seed")]
+    seed: u32
+}
+
+impl<const MAX: usize, F> FeedbackCombFilter<MAX, F>
+where
+    F: FilterFloat
+{
+    pub fn new(param: DelayGain<F>) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            delay_line: DelayLine::new(),
+            denormal_protection: DenormalProtection::None,
+            seed: DenormalProtection::<F>::seed()
+        }
+    }
+}
+
+impl<const MAX: usize, F> RtfBase for FeedbackCombFilter<MAX, F>
+where
+    F: FilterFloat
+{
+    type Param = DelayGain<F>;
+    type Conf = All;
+    type F = F;
+
+    type Outputs<U> = [U; 1];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<const MAX: usize, F> Rtf for FeedbackCombFilter<MAX, F>
+where
+    F: FilterFloat
+{
+    fn filter(&mut self, _rate: F, x: F) -> [F; 1]
+    {
+        let DelayGain {delay, gain} = *self.param;
+
+        let denormal_protection = self.denormal_protection;
+        let offset = denormal_protection.offset(&mut self.seed);
+
+        let y = x + gain*self.delay_line.read(delay);
+        denormal_protection.flush(core::slice::from_mut(self.delay_line.push(y + offset)));
+        [y]
+    }
+
+    fn z_response(&mut self, _rate: F, z: Complex<F>) -> [Complex<F>; 1]
+    {
+        let DelayGain {delay, gain} = *self.param;
+
+        [(-DelayLine::<F, MAX>::z_response(delay, z)*gain + F::one()).inv()]
+    }
+
+    fn reset(&mut self)
+    {
+        self.delay_line.reset()
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<F>
+    {
+        self.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<F>)
+    {
+        self.denormal_protection = denormal_protection
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{param::DelayGain, rtf::Rtf};
+
+    use super::FeedbackCombFilter;
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = FeedbackCombFilter::<16>::new(DelayGain {delay: 8.0, gain: 0.7});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn impulse_response()
+    {
+        const RATE: f64 = 44100.0;
+        const M: usize = 5;
+        const G: f64 = 0.5;
+
+        let mut filter = FeedbackCombFilter::<16>::new(DelayGain {delay: M as f64, gain: G});
+
+        for n in 0..64
+        {
+            let [y] = filter.filter(RATE, if n == 0 {1.0} else {0.0});
+            let h = if n % M == 0 {G.powi((n/M) as i32)} else {0.0};
+            assert_eq!(y, h, "n = {n}");
+        }
+    }
+    #[test]
+    fn serde_round_trip()
+    {
+        const RATE: f64 = 44100.0;
+
+        let mut filter = FeedbackCombFilter::<16>::new(DelayGain {delay: 5.5, gain: 0.7});
+        for n in 0..21
+        {
+            filter.filter(RATE, (0.3*n as f64).sin());
+        }
+
+        // The delay line picks up where it left off
+        let mut copy: FeedbackCombFilter<16> = serde_json::from_str(&serde_json::to_string(&filter).unwrap()).unwrap();
+        for n in 0..64
+        {
+            let x = if n == 0 {1.0} else {0.0};
+            assert_eq!(copy.filter(RATE, x), filter.filter(RATE, x), "n = {n}");
+        }
+    }
+}
\ No newline at end of file
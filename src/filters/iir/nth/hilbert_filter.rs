@@ -0,0 +1,238 @@
+use core::fmt::Debug;
+
+use array_trait::AsSlice;
+
+use crate::{array_min1, array_minus1, calc::iir::nth::HilbertCalc, conf::All, internals::{AInternalsFor, BInternalsFor, RtfInternalsFor}, param::{FilterFloat, Param, Transition}, rtf::{RtfBase, StaticRtf}};
+
+type Internals<F, const N: usize> = crate::rtfinternals!(F, 2, 2, 2, N, 1, true);
+
+/// An IIR Hilbert transformer, made from two parallel chains of `N` all-pass sections each.
+///
+/// The two outputs have the same magnitude, and a phase difference of 90° within the band from `transition` to `0.5 - transition` times the sample rate. The
+/// second output lags the first, so `y₀ + jy₁` is the analytic signal, which is what's needed for frequency shifting (single-sideband modulation) and envelope
+/// detection. The phase of each output on its own is not linear, only their difference is constant.
+///
+/// The sections are the polyphase branches of an elliptic half-band filter, shifted by a quarter of the sample rate. Each section is second order in `z⁻¹`,
+/// so the total order is `4N`. A narrower `transition` (wider band) gives a larger phase ripple, which is reduced by adding more sections:
+///
+/// | N | Transition = 0.001 | Transition = 0.01 | Transition = 0.05 |
+/// |---|--------------------|-------------------|-------------------|
+/// | 2 | ±10°               | ±2.3°             | ±0.24°            |
+/// | 4 | ±0.65°             | ±0.040°           | ±0.00054°         |
+/// | 6 | ±0.041°            | ±0.00068°         | ±1.2·10⁻⁶°        |
+/// | 8 | ±0.0028°           | ±0.000012°        | ±2.7·10⁻⁹°        |
+///
+/// At 44.1kHz, a transition of 0.001 covers 44Hz to 22kHz. For a linear phase alternative, see the
+/// [`FirHilbertFilter`](crate::filters::fir::FirHilbertFilter).
+///
+/// <pre>
+///          N-1 c<sub>2k</sub> - z⁻²
+/// H₀(z) =  Π   ----------
+///          k=0 1 - c<sub>2k</sub>z⁻²
+///
+///               N-1 c<sub>2k+1</sub> - z⁻²
+/// H₁(z) = z⁻¹  Π   ------------
+///              k=0 1 - c<sub>2k+1</sub>z⁻²
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     param::Transition,
+///     rtf::Rtf,
+///     filters::iir::nth::HilbertFilter
+/// };
+///
+/// let mut filter = HilbertFilter::<4>::new(Transition {transition: 0.001});
+///
+/// // In-phase and quadrature
+/// let [i, q] = filter.filter(44100.0, 1.0);
+///
+/// // Envelope
+/// let envelope = (i*i + q*q).sqrt();
+/// ```
+///
+/// # Frequency response
+///
+/// N = 4, transition = 0.001
+///
+/// <div>
+/// <img alt="Hilbert filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/hilbert_filter.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HilbertFilter<const N: usize, F = f64>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    pub param: Param<Transition<F>>,
+    pub internals: Internals<F, N>
+}
+
+impl<const N: usize, F> HilbertFilter<N, F>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    pub const fn new(param: Transition<F>) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            internals: Internals::<F, N>::new()
+        }
+    }
+}
+
+impl<const N: usize, F> RtfBase for HilbertFilter<N, F>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    type Param = Transition<F>;
+    type Conf = All;
+    type F = F;
+    type Outputs<U> = [U; 2];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<const N: usize, F> StaticRtf for HilbertFilter<N, F>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    type IsIir<U> = [U; 1];
+    type Order<U> = [U; 1];
+    type OutputBufs<U> = [U; 2];
+    type SosBufs<U> = [U; 2];
+    type SosStages<U> = [U; N];
+
+    #[allow(clippy::type_complexity)]
+    fn get_internals(&self) -> (&RtfInternalsFor<Self>, &Param<Self::Param>)
+    {
+        (&self.internals, &self.param)
+    }
+    #[allow(clippy::type_complexity)]
+    fn get_internals_mut(&mut self) -> (&mut RtfInternalsFor<Self>, &mut Param<Self::Param>)
+    {
+        (&mut self.internals, &mut self.param)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn make_coeffs(param: &Self::Param, _rate: Self::F) -> (
+        BInternalsFor<Self>,
+        Self::IsIir<AInternalsFor<Self>>
+    )
+    {
+        let calc = HilbertCalc::new(*param);
+        let mut c = [[F::zero(); 2]; N];
+        calc.c(c.as_flattened_mut());
+
+        let mut b_stages: array_minus1!([[[F; 3]; 2]; N]) = unsafe {core::mem::zeroed()};
+        let mut b_last_stage: array_min1!([[[F; 3]; 2]; N]) = unsafe {core::mem::zeroed()};
+        for (b, c) in b_stages.as_mut_slice()
+            .iter_mut()
+            .chain(b_last_stage.as_mut_slice())
+            .zip(c)
+        {
+            *b = c.map(HilbertCalc::b)
+        }
+        let zero = F::zero();
+        let one = F::one();
+        (
+            (b_stages, b_last_stage, [
+                [one, zero],
+                [zero, one]
+            ]),
+            [(c.map(|c| c.map(HilbertCalc::a)), [
+                [one, zero],
+                [one, zero]
+            ])]
+        )
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::{FRAC_PI_2, TAU};
+
+    use num::Complex;
+
+    use crate::{param::Transition, rtf::Rtf};
+
+    use super::HilbertFilter;
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = HilbertFilter::<4>::new(Transition {transition: 0.001});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn quadrature()
+    {
+        const RATE: f64 = 44100.0;
+
+        fn test<T>(mut filter: T, ripple: f64)
+        where
+            T: Rtf<F = f64, Param = Transition<f64>, Outputs<Complex<f64>> = [Complex<f64>; 2], Outputs<f64> = [f64; 2]>
+        {
+            let Transition {transition} = *filter.get_param();
+            for m in 0..=100
+            {
+                let omega = (transition + (0.5 - 2.0*transition)*m as f64/100.0)*TAU;
+                let [h0, h1] = filter.frequency_response(RATE, omega);
+                assert!((h0.norm() - 1.0).abs() < 1e-9 && (h1.norm() - 1.0).abs() < 1e-9);
+                let phase_error = (h0/h1).arg() - FRAC_PI_2;
+                assert!(phase_error.abs() < ripple.to_radians(), "{omega}: {}°", phase_error.to_degrees());
+            }
+
+            // The envelope of a sine is flat, once the transient has decayed
+            let omega = 0.1*TAU;
+            for n in 0..32768
+            {
+                let [y0, y1] = filter.filter(RATE, (omega*n as f64).sin());
+                if n >= 16384
+                {
+                    let envelope = (y0*y0 + y1*y1).sqrt();
+                    assert!((envelope - 1.0).abs() < ripple.to_radians(), "{n}: {envelope}");
+                }
+            }
+        }
+
+        test(HilbertFilter::<2>::new(Transition {transition: 0.05}), 0.25);
+        test(HilbertFilter::<4>::new(Transition {transition: 0.01}), 0.05);
+        test(HilbertFilter::<4>::new(Transition {transition: 0.001}), 0.7);
+        test(HilbertFilter::<8>::new(Transition {transition: 0.001}), 0.003);
+
+        // Transitions out of range are clamped, instead of hanging or giving NaN
+        for transition in [0.0, 0.25, 0.5, -1.0, 2.0, f64::NAN, f64::INFINITY]
+        {
+            let mut filter = HilbertFilter::<4>::new(Transition {transition});
+            let [h0, h1] = filter.frequency_response(RATE, 0.1*TAU);
+            assert!(h0.is_finite() && h1.is_finite(), "{transition}");
+            assert!(filter.filter(RATE, 1.0).iter().all(|y| y.is_finite()), "{transition}");
+        }
+    }
+}
\ No newline at end of file
@@ -1,7 +1,18 @@
+moddef::moddef!(
+    flat(pub) mod {
+        all_pass_comb_filter for cfg(feature = "comb"),
+        feedback_comb_filter for cfg(feature = "comb"),
+        hilbert_filter for cfg(feature = "hilbert"),
+        phaser_filter for cfg(feature = "phaser"),
+        state_space_filter for cfg(feature = "state_space"),
+        thiran_filter for cfg(feature = "thiran")
+    }
+);
+
 /*moddef::moddef!(
     flat(pub) mod {
         inverse,
         cascade,
         paralell
     }
-);*/
\ No newline at end of file
+);*/
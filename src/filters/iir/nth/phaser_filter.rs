@@ -0,0 +1,248 @@
+use num::{Complex, One};
+
+use crate::{calc::iir::nth::PhaserCalc, conf::All, filters::iir::first::FirstOrderAllPassFilter, param::{FilterFloat, Param, TauSpreadMix}, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+/// A cascade of `N` first-order all-pass stages, like in a phaser.
+///
+/// The first output is the all-passed signal, and the second output is the all-passed signal mixed with the dry signal. With `mix = 0.5`, this gives
+/// the classic phaser sound, with a notch wherever the total phase shift is an odd multiple of π, which is about one notch for every two stages. Sweep `tau` to
+/// move the notches.
+///
+/// The time constant of stage `k` is `τₖ = τ·spread^(k/(N - 1) - 1/2)`, so a `spread` other than one spaces the notches more unevenly.
+///
+/// The stages are run in series as separate [`FirstOrderAllPassFilter`]s, since a single transfer function of high order with clustered poles is numerically
+/// fragile.
+///
+/// <pre>
+///         N-1 τₖs - 1
+/// H₀(s) =  Π  -------
+///         k=0 τₖs + 1
+///
+/// H₁(s) = (1 - mix) + mix·H₀(s)
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     change::Change,
+///     param::TauSpreadMix,
+///     rtf::Rtf,
+///     filters::iir::nth::PhaserFilter
+/// };
+///
+/// // A 6-stage phaser
+/// let mut filter = PhaserFilter::<6>::new(TauSpreadMix {tau: 0.0002, spread: 1.0, mix: 0.5});
+///
+/// let [wet, y] = filter.filter(44100.0, 1.0);
+///
+/// // Sweep the notches
+/// filter.param.change(TauSpreadMix {tau: 0.0001, spread: 1.0, mix: 0.5}, 0.01);
+/// ```
+///
+/// # Frequency response
+///
+/// N = 6, τ = 0.2 ms, spread = 4, mix = 0.5
+///
+/// <div>
+/// <img alt="Phaser filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/phaser_filter.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhaserFilter<const N: usize, F = f64>
+where
+    F: FilterFloat
+{
+    pub param: Param<TauSpreadMix<F>>,
+    stages: [FirstOrderAllPassFilter<All, F>; N]
+}
+
+impl<const N: usize, F> PhaserFilter<N, F>
+where
+    F: FilterFloat,
+    FirstOrderAllPassFilter<All, F>: Rtf<F = F, Outputs<F> = [F; 1], Outputs<Complex<F>> = [Complex<F>; 1]>
+{
+    pub fn new(param: TauSpreadMix<F>) -> Self
+    {
+        let calc = PhaserCalc::new(param);
+        Self {
+            param: Param::new(param),
+            stages: core::array::from_fn(|k| FirstOrderAllPassFilter::new(calc.tau(k, N)))
+        }
+    }
+
+    fn update_stages(&mut self)
+    {
+        if !self.param.is_unchanged_then_set()
+        {
+            let calc = PhaserCalc::new(*self.param);
+            for (k, stage) in self.stages.iter_mut()
+                .enumerate()
+            {
+                stage.param.assign(calc.tau(k, N))
+            }
+        }
+    }
+
+    /// The all-pass stage `k`, or [`None`] if `k` is out of bounds.
+    pub fn stage(&self, k: usize) -> Option<&FirstOrderAllPassFilter<All, F>>
+    {
+        self.stages.get(k)
+    }
+}
+
+impl<const N: usize, F> RtfBase for PhaserFilter<N, F>
+where
+    F: FilterFloat,
+    FirstOrderAllPassFilter<All, F>: Rtf<F = F, Outputs<F> = [F; 1], Outputs<Complex<F>> = [Complex<F>; 1]>
+{
+    type Param = TauSpreadMix<F>;
+    type Conf = All;
+    type F = F;
+
+    type Outputs<U> = [U; 2];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<const N: usize, F> Rtf for PhaserFilter<N, F>
+where
+    F: FilterFloat,
+    FirstOrderAllPassFilter<All, F>: Rtf<F = F, Outputs<F> = [F; 1], Outputs<Complex<F>> = [Complex<F>; 1]>
+{
+    fn filter(&mut self, rate: F, x: F) -> [F; 2]
+    {
+        self.update_stages();
+        let wet = self.stages.iter_mut()
+            .fold(x, |x, stage| {
+                let [y] = stage.filter(rate, x);
+                y
+            });
+        let TauSpreadMix {mix, ..} = *self.param;
+        [
+            wet,
+            x*(F::one() - mix) + wet*mix
+        ]
+    }
+
+    fn z_response(&mut self, rate: F, z: Complex<F>) -> [Complex<F>; 2]
+    {
+        self.update_stages();
+        let wet = self.stages.iter_mut()
+            .fold(Complex::one(), |h, stage| {
+                let [h_stage] = stage.z_response(rate, z);
+                h*h_stage
+            });
+        let TauSpreadMix {mix, ..} = *self.param;
+        [
+            wet,
+            wet*mix + (F::one() - mix)
+        ]
+    }
+
+    fn reset(&mut self)
+    {
+        for stage in self.stages.iter_mut()
+        {
+            stage.reset()
+        }
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<F>
+    {
+        self.stages.first()
+            .map(|stage| stage.denormal_protection())
+            .unwrap_or_default()
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<F>)
+    {
+        for stage in self.stages.iter_mut()
+        {
+            stage.set_denormal_protection(denormal_protection)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use num::Complex;
+
+    use crate::{conf::All, filters::iir::first::FirstOrderAllPassFilter, param::{Tau, TauSpreadMix}, rtf::Rtf};
+
+    use super::PhaserFilter;
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = PhaserFilter::<6>::new(TauSpreadMix {tau: 0.0002, spread: 4.0, mix: 0.5});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn cascade()
+    {
+        const RATE: f64 = 44100.0;
+
+        fn test<const N: usize>(param: TauSpreadMix<f64>)
+        {
+            let TauSpreadMix {tau, spread, mix} = param;
+            let mut filter = PhaserFilter::<N>::new(param);
+            for f in [10.0, 100.0, 1000.0, 10000.0]
+            {
+                let omega = f*TAU/RATE;
+                let [wet, y] = filter.frequency_response(RATE, omega);
+
+                // Stages spread geometrically around τ
+                let expected = (0..N).map(|k| {
+                        let exponent = if N > 1 {k as f64/(N - 1) as f64 - 0.5} else {0.0};
+                        let [h] = FirstOrderAllPassFilter::<All>::new(Tau {tau: tau*spread.powf(exponent)})
+                            .frequency_response(RATE, omega);
+                        h
+                    }).fold(Complex::from(1.0), |h, t| h*t);
+                assert!((wet - expected).norm() < 1e-9, "N = {N}, {f}Hz: {wet} != {expected}");
+                assert!((y - (expected*mix + (1.0 - mix))).norm() < 1e-9, "N = {N}, {f}Hz");
+            }
+
+            // Filtering agrees with the response to a sine
+            let omega = 1000.0*TAU/RATE;
+            let h = filter.frequency_response(RATE, omega);
+            filter.reset();
+            let mut y = [0.0; 2];
+            for n in 0..4096
+            {
+                y = filter.filter(RATE, (omega*n as f64).sin());
+            }
+            for (y, h) in y.into_iter()
+                .zip(h)
+            {
+                let y_expected = h.norm()*(omega*4095.0 + h.arg()).sin();
+                assert!((y - y_expected).abs() < 1e-6, "N = {N}: {y} != {y_expected}");
+            }
+        }
+
+        test::<1>(TauSpreadMix {tau: 0.001, spread: 1.0, mix: 0.5});
+        test::<4>(TauSpreadMix {tau: 0.0002, spread: 1.0, mix: 0.5});
+        test::<6>(TauSpreadMix {tau: 0.0002, spread: 4.0, mix: 0.7});
+        test::<8>(TauSpreadMix {tau: 0.0005, spread: 0.25, mix: 1.0});
+    }
+}
\ No newline at end of file
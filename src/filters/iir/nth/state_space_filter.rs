@@ -0,0 +1,255 @@
+use num::Complex;
+
+use crate::{conf::All, param::{FilterFloat, Param, StateSpace}, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+/// A filter that runs a discrete-time [`StateSpace`]-model directly, updating its state vector every sample.
+///
+/// Unlike the direct forms, the states of the model keep their meaning, so the filter can be given any realization, like a physical model where the
+/// states are positions and velocities, or a [canonical form](crate::param::CanonicalForm) of some other filter.
+///
+/// <pre>
+/// x[n + 1] = Ax[n] + Bu[n]
+///     y[n] = Cx[n] + Du[n]
+///
+/// H(z) = C(zI - A)⁻¹B + D
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use core::f64::consts::TAU;
+///
+/// use real_time_fir_iir_filters::{
+///     conf::LowPass,
+///     param::{CanonicalForm, OmegaZeta, StateSpace},
+///     rtf::Rtf,
+///     filters::iir::{nth::StateSpaceFilter, second::SecondOrderFilter}
+/// };
+///
+/// const RATE: f64 = 44100.0;
+///
+/// // Realize a second order low-pass filter in observable canonical form
+/// let mut second_order = SecondOrderFilter::<LowPass>::new(OmegaZeta {omega: 1000.0*TAU, zeta: 0.5});
+/// let model = StateSpace::<2, _>::from_rtf(&mut second_order, RATE, 0, CanonicalForm::Observable).unwrap();
+///
+/// let mut filter = StateSpaceFilter::new(model);
+///
+/// let [y] = filter.filter(RATE, 1.0);
+/// let [y_ref] = second_order.filter(RATE, 1.0);
+///
+/// assert!((y - y_ref).abs() < 1e-12);
+/// ```
+///
+/// # Frequency response
+///
+/// Controllable canonical form of a second order low-pass filter, ω = 1 kHz, ζ = 0.2
+///
+/// <div>
+/// <img alt="State-space filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/state_space_filter.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateSpaceFilter<const N: usize, F = f64>
+where
+    F: FilterFloat
+{
+    pub param: Param<StateSpace<N, F>>,
+    #[serde(with = "crate::serde::array")]
+    x: [F; N],
+    denormal_protection: DenormalProtection<F>,
+    #[serde(skip, default = "DenormalProtection::<F>::seed")]
+    seed: u32
+}
+
+impl<const N: usize, F> StateSpaceFilter<N, F>
+where
+    F: FilterFloat
+{
+    pub fn new(param: StateSpace<N, F>) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            x: [F::zero(); N],
+            denormal_protection: DenormalProtection::None,
+            seed: DenormalProtection::<F>::seed()
+        }
+    }
+
+    /// Returns the current state vector.
+    pub fn state(&self) -> &[F; N]
+    {
+        &self.x
+    }
+}
+
+impl<const N: usize, F> RtfBase for StateSpaceFilter<N, F>
+where
+    F: FilterFloat
+{
+    type Param = StateSpace<N, F>;
+    type Conf = All;
+    type F = F;
+
+    type Outputs<U> = [U; 1];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<const N: usize, F> Rtf for StateSpaceFilter<N, F>
+where
+    F: FilterFloat
+{
+    fn filter(&mut self, _rate: F, u: F) -> [F; 1]
+    {
+        let StateSpace {a, b, c, d} = *self.param;
+
+        let y = c.iter()
+            .zip(self.x.iter())
+            .map(|(&c, &x)| c*x)
+            .fold(F::zero(), |s, x| s + x) + d*u;
+
+        let denormal_protection = self.denormal_protection;
+        let offset = denormal_protection.offset(&mut self.seed);
+
+        let x = self.x;
+        for ((x_next, a), &b) in self.x.iter_mut()
+            .zip(a.iter())
+            .zip(b.iter())
+        {
+            *x_next = a.iter()
+                .zip(x.iter())
+                .map(|(&a, &x)| a*x)
+                .fold(F::zero(), |s, x| s + x) + b*u + offset
+        }
+
+        denormal_protection.flush(&mut self.x);
+
+        [y]
+    }
+
+    fn z_response(&mut self, _rate: F, z: Complex<F>) -> [Complex<F>; 1]
+    {
+        [self.param.z_response(z)]
+    }
+
+    fn reset(&mut self)
+    {
+        self.x = [F::zero(); N]
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<F>
+    {
+        self.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<F>)
+    {
+        self.denormal_protection = denormal_protection
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use num::Complex;
+
+    use crate::{conf::{All, LowPass}, filters::iir::second::SecondOrderFilter, param::{CanonicalForm, OmegaZeta, StateSpace}, rtf::Rtf};
+
+    use super::StateSpaceFilter;
+
+    #[test]
+    fn plot()
+    {
+        let mut second_order = SecondOrderFilter::<LowPass>::new(OmegaZeta {omega: 1000.0*TAU, zeta: 0.2});
+        let model = StateSpace::<2, _>::from_rtf(&mut second_order, 44100.0, 0, CanonicalForm::Controllable).unwrap();
+        let mut filter = StateSpaceFilter::new(model);
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn canonical_forms()
+    {
+        const RATE: f64 = 44100.0;
+
+        let param = OmegaZeta {omega: 3000.0*TAU, zeta: 0.3};
+
+        // Each output of the filter, in both forms, behaves just like the original
+        for output in 0..3
+        {
+            for form in [CanonicalForm::Controllable, CanonicalForm::Observable]
+            {
+                let mut second_order = SecondOrderFilter::<All>::new(param);
+                let model = StateSpace::<2, _>::from_rtf(&mut second_order, RATE, output, form).unwrap();
+                let mut filter = StateSpaceFilter::new(model);
+
+                for n in 0..64
+                {
+                    let x = if n == 0 {1.0} else {(0.3*n as f64).sin()};
+                    let [y] = filter.filter(RATE, x);
+                    let y_ref = second_order.filter(RATE, x)[output];
+                    assert!((y - y_ref).abs() < 1e-12, "{form:?}, output {output}, n = {n}");
+                }
+
+                for omega in [0.01, 0.3, 1.0, 3.0]
+                {
+                    let [h] = filter.frequency_response(RATE, omega);
+                    let h_ref = second_order.frequency_response(RATE, omega)[output];
+                    assert!((h - h_ref).norm() < 1e-9, "{form:?}, output {output}");
+                }
+
+                // Back to the same transfer function
+                let (b, a) = model.transfer_function();
+                let same = StateSpace::<2, _>::canonical(form, b, a);
+                assert!(model.a.iter().flatten().zip(same.a.iter().flatten()).all(|(x, y)| (x - y).abs() < 1e-12));
+                assert!(model.c.iter().zip(same.c.iter()).all(|(x, y)| (x - y).abs() < 1e-12));
+            }
+        }
+
+        // Too low an order
+        assert!(StateSpace::<1, _>::from_rtf(&mut SecondOrderFilter::<LowPass>::new(param), RATE, 0, CanonicalForm::Controllable).is_none());
+
+        // A damped rotation, which isn't in any canonical form
+        let (r, theta) = (0.9, 0.4);
+        let model = StateSpace {
+            a: [
+                [r*f64::cos(theta), -r*f64::sin(theta), 0.0],
+                [r*f64::sin(theta), r*f64::cos(theta), 0.0],
+                [0.5, 0.0, 0.2]
+            ],
+            b: [1.0, 0.5, -0.3],
+            c: [0.2, -1.0, 0.7],
+            d: 0.1
+        };
+        let (b, a) = model.transfer_function();
+        for z in [Complex::new(1.1, 0.2), Complex::new(-0.3, 0.8), Complex::cis(2.0)]
+        {
+            let z_inv = z.inv();
+            let horner = |c: [f64; 4]| c.into_iter()
+                .rev()
+                .fold(Complex::new(0.0, 0.0), |h, c| h*z_inv + c);
+            assert!((horner(b)/horner(a) - model.z_response(z)).norm() < 1e-12);
+        }
+
+        let json = serde_json::to_string(&model).unwrap();
+        assert_eq!(serde_json::from_str::<StateSpace<3>>(&json).unwrap(), model);
+    }
+}
\ No newline at end of file
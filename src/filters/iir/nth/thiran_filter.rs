@@ -0,0 +1,195 @@
+use core::fmt::Debug;
+
+use array_trait::AsSlice;
+
+use crate::{array_plus1, calc::iir::nth::ThiranCalc, conf::All, internals::{AInternalsFor, BInternalsFor, RtfInternalsFor}, param::{Delay, FilterFloat, Param}, rtf::{RtfBase, StaticRtf}};
+
+type Internals<F, const N: usize> = crate::rtfinternals!(F, 1, 1, 1, 0, N, true);
+
+/// A Thiran all-pass fractional delay filter of order `N`.
+///
+/// Delays the signal by a fractional number of samples `D`, with a maximally flat group delay at DC. The magnitude response is exactly one at all frequencies, so
+/// it's well suited for tuning feedback loops in physical models (like waveguides), where any loss would change the decay.
+///
+/// It's stable for `D > N - 1`, and works best for `N - 0.5 ≤ D ≤ N + 0.5`. For longer delays, use an integer delay line for the rest. Being an IIR filter, it has
+/// a transient when `D` changes quickly, so for heavy modulation (like chorus) the [`LagrangeFilter`](crate::filters::fir::LagrangeFilter) may be a better fit.
+///
+/// <pre>
+///        a<sub>N</sub> + a<sub>N-1</sub>z⁻¹ + ... + z⁻ᴺ
+/// H(z) = ------------------------
+///        1 + a<sub>1</sub>z⁻¹ + ... + a<sub>N</sub>z⁻ᴺ
+///
+///                     N     D - N + i
+/// aₖ = (-1)ᵏ (N k)  Π   -------------
+///                    i=0  D - N + k + i
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     change::Change,
+///     param::Delay,
+///     rtf::Rtf,
+///     filters::iir::nth::ThiranFilter
+/// };
+///
+/// // A 3. order Thiran filter, delaying by 3.3 samples
+/// let mut filter = ThiranFilter::<3>::new(Delay {delay: 3.3});
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+///
+/// // Smoothly modulate the delay
+/// filter.param.change(Delay {delay: 2.8}, 0.01);
+/// ```
+///
+/// # Frequency response
+///
+/// N = 3, D = 3.3
+///
+/// <div>
+/// <img alt="Thiran filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/thiran_filter.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThiranFilter<const N: usize, F = f64>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    pub param: Param<Delay<F>>,
+    pub internals: Internals<F, N>
+}
+
+impl<const N: usize, F> ThiranFilter<N, F>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    pub const fn new(param: Delay<F>) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            internals: Internals::<F, N>::new()
+        }
+    }
+}
+
+impl<const N: usize, F> RtfBase for ThiranFilter<N, F>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    type Param = Delay<F>;
+    type Conf = All;
+    type F = F;
+    type Outputs<U> = [U; 1];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<const N: usize, F> StaticRtf for ThiranFilter<N, F>
+where
+    F: FilterFloat,
+    Internals<F, N>: Copy + Debug + Default + PartialEq
+{
+    type IsIir<U> = [U; 1];
+    type Order<U> = [U; N];
+    type OutputBufs<U> = [U; 1];
+    type SosBufs<U> = [U; 1];
+    type SosStages<U> = [U; 0];
+
+    #[allow(clippy::type_complexity)]
+    fn get_internals(&self) -> (&RtfInternalsFor<Self>, &Param<Self::Param>)
+    {
+        (&self.internals, &self.param)
+    }
+    #[allow(clippy::type_complexity)]
+    fn get_internals_mut(&mut self) -> (&mut RtfInternalsFor<Self>, &mut Param<Self::Param>)
+    {
+        (&mut self.internals, &mut self.param)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn make_coeffs(param: &Self::Param, _rate: Self::F) -> (
+        BInternalsFor<Self>,
+        Self::IsIir<AInternalsFor<Self>>
+    )
+    {
+        let calc = ThiranCalc::new(*param);
+        let mut b: array_plus1!([F; N]) = unsafe {core::mem::zeroed()};
+        let mut a: array_plus1!([F; N]) = unsafe {core::mem::zeroed()};
+        calc.b(b.as_mut_slice());
+        calc.a(a.as_mut_slice());
+        (
+            ([], [], [
+                b
+            ]),
+            [([], [
+                a
+            ])]
+        )
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use num::Complex;
+
+    use crate::{param::Delay, rtf::Rtf};
+
+    use super::ThiranFilter;
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = ThiranFilter::<3>::new(Delay {delay: 3.3});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn group_delay()
+    {
+        const RATE: f64 = 44100.0;
+        const OMEGA: f64 = 1e-3;
+
+        fn test<T>(mut filter: T)
+        where
+            T: Rtf<F = f64, Param = Delay<f64>, Outputs<Complex<f64>> = [Complex<f64>; 1]>
+        {
+            let Delay {delay} = *filter.get_param();
+
+            // All-pass, with the given group delay at DC
+            let [h] = filter.frequency_response(RATE, OMEGA);
+            assert!((h.norm() - 1.0).abs() < 1e-9);
+            let [h_lo] = filter.frequency_response(RATE, OMEGA - 1e-6);
+            let [h_hi] = filter.frequency_response(RATE, OMEGA + 1e-6);
+            let group_delay = -(h_hi.arg() - h_lo.arg())/2e-6;
+            assert!((group_delay - delay).abs() < 1e-4, "D = {delay}: {group_delay}");
+        }
+
+        test(ThiranFilter::<1>::new(Delay {delay: 0.7}));
+        test(ThiranFilter::<1>::new(Delay {delay: 1.3}));
+        test(ThiranFilter::<2>::new(Delay {delay: 2.2}));
+        test(ThiranFilter::<3>::new(Delay {delay: 3.3}));
+        test(ThiranFilter::<4>::new(Delay {delay: 3.6}));
+    }
+}
@@ -0,0 +1,442 @@
+use num::{Complex, Float, One, Zero};
+
+use crate::{calc::iir::second::PIDCalc, conf::All, param::{FilterFloat, FilterParam, PIDFFilterParam, Param, PID}, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+/// How a [`PIDController`] keeps its integrator from winding up while the output is saturated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum AntiWindup<F>
+{
+    /// The integrator keeps integrating, even when the output is saturated.
+    None,
+    /// Conditional integration. The integrator is held whenever the output is saturated and the error would drive it further into saturation.
+    #[default]
+    Clamping,
+    /// Back-calculation. The difference between the saturated and the unsaturated output is fed back into the integrator, scaled by the tracking
+    /// gain (in 1/s).
+    ///
+    /// The correction per sample, `tracking/rate`, is limited to `0..=1`. At 1, the integrator catches up with the saturated output in a single sample,
+    /// and any more would overshoot it, which makes the integrator diverge for `tracking > 2·rate`.
+    BackCalculation {
+        tracking: F
+    }
+}
+
+/// Whether a [`PIDController`] computes its output, or passes on a manually set output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ControlMode<F>
+{
+    /// The output is computed from the control error.
+    #[default]
+    Auto,
+    /// The output is set by hand. The integrator tracks it, so switching back to [`Auto`](ControlMode::Auto) is bumpless.
+    Manual {
+        output: F
+    }
+}
+
+/// Setpoint weights of a [`PIDController`].
+///
+/// The proportional term acts on `b·r - y`, and the derivative term on `c·r - y`, where `r` is the setpoint and `y` is the measurement. The integral
+/// term always acts on the full error `r - y`, so the steady state is unaffected. With `b = c = 1`, the controller acts on the error alone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetpointWeights<F>
+where
+    F: Float
+{
+    pub b: F,
+    pub c: F
+}
+impl<F> Default for SetpointWeights<F>
+where
+    F: Float
+{
+    fn default() -> Self
+    {
+        Self {
+            b: F::one(),
+            c: F::one()
+        }
+    }
+}
+
+/// A PID-controller with output limits and anti-windup.
+///
+/// Takes the control error as input, and outputs the control signal, which is limited to the range `min..=max`. While the output is within its
+/// limits, the controller behaves exactly like a [`PIDFilter`](crate::filters::iir::second::PIDFilter) with the same parameters. When the
+/// output saturates, the integrator is kept from winding up according to the chosen [`AntiWindup`] strategy.
+///
+/// [`filter`](Rtf::filter) takes the control error. To weight the setpoint in the proportional and derivative terms, use
+/// [`control`](PIDController::control) with the setpoint and the measurement instead. In [`ControlMode::Manual`], the output is set by hand, while
+/// the integrator is initialized so that switching back to [`ControlMode::Auto`] doesn't make the output jump.
+///
+/// The integrator and the (optionally band-limited) derivative are discretized like in [`PIDFilter`](crate::filters::iir::second::PIDFilter), with
+/// the bilinear transform by default, or the method chosen with [`Discretized`](crate::param::Discretized). The frequency response is the one of the
+/// linear, unsaturated controller.
+///
+/// <pre>
+///            I      Ds
+/// H(s) = P + - + --------
+///            s   1 + sTf
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     param::PID,
+///     rtf::Rtf,
+///     filters::iir::second::{AntiWindup, ControlMode, PIDController}
+/// };
+///
+/// let mut controller = PIDController::new(PID::<f64> {p: 2.0, i: 10.0, d: 0.0});
+/// controller.min = -1.0;
+/// controller.max = 1.0;
+/// controller.anti_windup = AntiWindup::BackCalculation {tracking: 10.0};
+///
+/// let [u] = controller.filter(1000.0, 3.0);
+///
+/// assert_eq!(u, 1.0);
+///
+/// // Take over by hand, then hand back control without a bump
+/// controller.mode = ControlMode::Manual {output: 0.2};
+/// let [u] = controller.filter(1000.0, 0.0);
+/// assert_eq!(u, 0.2);
+///
+/// controller.mode = ControlMode::Auto;
+/// let [u] = controller.filter(1000.0, 0.0);
+/// assert!((u - 0.2).abs() < 1e-12);
+/// ```
+///
+/// # Frequency response
+///
+/// P = 1
+///
+/// I = 1 mHz
+///
+/// D = 10 μs
+///
+/// <div>
+/// <img alt="PID-controller response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/p_i_d_controller.png" height="500">
+/// </div>
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PIDController<F = f64, P = PID<F>>
+where
+    F: FilterFloat,
+    P: PIDFFilterParam + FilterParam<F = F>
+{
+    pub param: Param<P>,
+    pub min: F,
+    pub max: F,
+    pub anti_windup: AntiWindup<F>,
+    pub mode: ControlMode<F>,
+    pub weights: SetpointWeights<F>,
+    denormal_protection: DenormalProtection<F>,
+    integral: F,
+    derivative: F,
+    error: F,
+    error_d: F,
+    #[serde(skip)]
+    rate: Option<F>,
+    #[serde(skip)]
+    p: F,
+    #[serde(skip)]
+    i_a: F,
+    #[serde(skip)]
+    i_b: F,
+    #[serde(skip)]
+    d_a: F,
+    #[serde(skip)]
+    d_b: F
+}
+
+impl<P> PIDController<<P as FilterParam>::F, P>
+where
+    P: PIDFFilterParam
+{
+    pub fn new(param: P) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            min: P::F::neg_infinity(),
+            max: P::F::infinity(),
+            anti_windup: AntiWindup::default(),
+            mode: ControlMode::default(),
+            weights: SetpointWeights::default(),
+            denormal_protection: DenormalProtection::None,
+            integral: P::F::zero(),
+            derivative: P::F::zero(),
+            error: P::F::zero(),
+            error_d: P::F::zero(),
+            rate: None,
+            p: P::F::zero(),
+            i_a: P::F::zero(),
+            i_b: P::F::zero(),
+            d_a: P::F::zero(),
+            d_b: P::F::zero()
+        }
+    }
+
+    /// Computes the next output from the setpoint and the measurement, with the setpoint weighted in the proportional and derivative terms.
+    pub fn control(&mut self, rate: P::F, setpoint: P::F, measurement: P::F) -> P::F
+    {
+        let SetpointWeights {b, c} = self.weights;
+        self.step(
+            rate,
+            b*setpoint - measurement,
+            setpoint - measurement,
+            c*setpoint - measurement
+        )
+    }
+
+    /// The current state of the integrator.
+    pub fn integral(&self) -> P::F
+    {
+        self.integral
+    }
+
+    fn update(&mut self, rate: P::F)
+    {
+        if !self.param.is_unchanged_then_set() || self.rate != Some(rate)
+        {
+            let pidf = self.param.pidf();
+            let discretization = self.param.discretization();
+            let (k_i, alpha_i, beta_i) = discretization.integral(rate);
+            let (k_d, alpha_d, beta_d) = discretization.derivative(rate, pidf.tf);
+            let c0 = alpha_d + k_d*pidf.tf;
+            let c1 = beta_d - k_d*pidf.tf;
+            self.p = pidf.p;
+            self.i_a = pidf.i*alpha_i/k_i;
+            self.i_b = pidf.i*beta_i/k_i;
+            self.d_a = -c1/c0;
+            self.d_b = k_d*pidf.d/c0;
+            self.rate = Some(rate)
+        }
+    }
+
+    fn step(&mut self, rate: P::F, e_p: P::F, e_i: P::F, e_d: P::F) -> P::F
+    {
+        self.update(rate);
+
+        let step = self.i_a*e_i + self.i_b*self.error;
+        self.derivative = self.d_a*self.derivative + self.d_b*(e_d - self.error_d);
+        self.error = e_i;
+        self.error_d = e_d;
+
+        let pd = self.p*e_p + self.derivative;
+        let u = match self.mode
+        {
+            ControlMode::Auto => {
+                let mut v = pd + self.integral + step;
+                match self.anti_windup
+                {
+                    AntiWindup::Clamping if (v > self.max && step > P::F::zero()) || (v < self.min && step < P::F::zero()) => v = pd + self.integral,
+                    _ => self.integral = self.integral + step
+                }
+                let u = v.max(self.min).min(self.max);
+                if let AntiWindup::BackCalculation {tracking} = self.anti_windup
+                {
+                    let correction = (tracking/rate).max(P::F::zero()).min(P::F::one());
+                    self.integral = self.integral + correction*(u - v)
+                }
+                u
+            },
+            ControlMode::Manual {output} => {
+                // Track the manual output, so the next automatic output continues from it
+                let u = output.max(self.min).min(self.max);
+                self.integral = u - pd;
+                u
+            }
+        };
+
+        let denormal_protection = self.denormal_protection;
+        denormal_protection.flush(core::slice::from_mut(&mut self.integral));
+        denormal_protection.flush(core::slice::from_mut(&mut self.derivative));
+
+        u
+    }
+}
+
+impl<P> RtfBase for PIDController<<P as FilterParam>::F, P>
+where
+    P: PIDFFilterParam
+{
+    type Param = P;
+    type Conf = All;
+    type F = P::F;
+
+    type Outputs<U> = [U; 1];
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<P> Rtf for PIDController<<P as FilterParam>::F, P>
+where
+    P: PIDFFilterParam
+{
+    fn filter(&mut self, rate: P::F, x: P::F) -> [P::F; 1]
+    {
+        [self.step(rate, x, x, x)]
+    }
+
+    fn z_response(&mut self, rate: P::F, z: Complex<P::F>) -> [Complex<P::F>; 1]
+    {
+        let calc = PIDCalc::new(self.param.pidf(), rate, self.param.discretization());
+        let z_inv = z.inv();
+        let horner = |c: [P::F; 3]| c.into_iter()
+            .rev()
+            .fold(Complex::<P::F>::zero(), |h, c| h*z_inv + c);
+        [horner(calc.b())/horner(calc.a())]
+    }
+
+    fn reset(&mut self)
+    {
+        self.integral = P::F::zero();
+        self.derivative = P::F::zero();
+        self.error = P::F::zero();
+        self.error_d = P::F::zero()
+    }
+
+    fn denormal_protection(&self) -> DenormalProtection<P::F>
+    {
+        self.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<P::F>)
+    {
+        self.denormal_protection = denormal_protection
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::{filters::iir::second::PIDFilter, param::{Discretization, Discretized, PIDF, PI, PID}, rtf::Rtf};
+
+    use super::{AntiWindup, ControlMode, PIDController, SetpointWeights};
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = PIDController::new(PID {p: 1.0, i: 0.001, d: 0.00001});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn anti_windup()
+    {
+        const RATE: f64 = 1000.0;
+
+        // Unsaturated, it is the same as the linear filter
+        for discretization in [Discretization::ForwardEuler, Discretization::BackwardEuler, Discretization::Tustin]
+        {
+            let param = Discretized {
+                param: PIDF::with_n(PID {p: 1.0, i: 5.0, d: 0.01}, 200.0),
+                discretization
+            };
+            let mut controller = PIDController::new(param);
+            let mut filter = PIDFilter::new(param);
+            for n in 0..256
+            {
+                let x = (0.05*n as f64).sin();
+                let [u] = controller.filter(RATE, x);
+                let [y] = filter.filter(RATE, x);
+                assert!((u - y).abs() < 1e-9, "{discretization:?}, n = {n}");
+            }
+        }
+
+        // A long positive error saturates the output, then the error reverses
+        let recovery = |anti_windup| {
+            let mut controller = PIDController::new(PI {p: 0.5, i: 10.0});
+            controller.min = -1.0;
+            controller.max = 1.0;
+            controller.anti_windup = anti_windup;
+            (0..2000).map(|n| if n < 1000 {1.0} else {-1.0})
+                .map(|x| controller.filter(RATE, x)[0])
+                .enumerate()
+                .find(|&(n, u)| n >= 1000 && u < 1.0)
+                .map(|(n, _)| n - 1000)
+                .unwrap()
+        };
+        let none = recovery(AntiWindup::None);
+        let clamping = recovery(AntiWindup::Clamping);
+        let back_calculation = recovery(AntiWindup::BackCalculation {tracking: 100.0});
+
+        // Without anti-windup, the wound-up integrator keeps the output saturated for a long time
+        assert!(none > 500);
+        assert!(clamping < 10);
+        assert!(back_calculation < 10);
+
+        // Tracking far faster than the sample rate must not make the integrator diverge
+        let mut controller = PIDController::new(PI {p: 0.5, i: 10.0});
+        controller.max = 1.0;
+        controller.anti_windup = AntiWindup::BackCalculation {tracking: 1e6};
+        let mut u = 0.0;
+        for _ in 0..1000
+        {
+            [u] = controller.filter(RATE, 1.0);
+            assert!(controller.integral().abs() <= 1.0);
+        }
+        assert_eq!(u, 1.0);
+    }
+
+    #[test]
+    fn bumpless()
+    {
+        const RATE: f64 = 1000.0;
+
+        let param = PIDF::with_n(PID {p: 2.0, i: 5.0, d: 0.05}, 100.0);
+
+        // Switching from manual to auto continues from the manual output
+        let mut controller = PIDController::new(param);
+        controller.mode = ControlMode::Manual {output: 0.7};
+        let mut measurement = 0.0;
+        let mut u = 0.0;
+        for _ in 0..100
+        {
+            u = controller.control(RATE, 1.0, measurement);
+            measurement += 0.001;
+        }
+        assert_eq!(u, 0.7);
+        controller.mode = ControlMode::Auto;
+        let next = controller.control(RATE, 1.0, measurement);
+        assert!((next - u).abs() < 0.05);
+
+        // With b = c = 0, a setpoint step doesn't kick the output
+        let mut weighted = PIDController::new(param);
+        weighted.weights = SetpointWeights {b: 0.0, c: 0.0};
+        let mut unweighted = PIDController::new(param);
+        let u_weighted = weighted.control(RATE, 1.0, 0.0);
+        let u_unweighted = unweighted.control(RATE, 1.0, 0.0);
+        assert!(u_weighted.abs() < 0.01);
+        assert!(u_unweighted > 2.0);
+
+        // Once settled, only the proportional terms differ, by P·(1 - b)·r
+        for _ in 0..1000
+        {
+            weighted.control(RATE, 1.0, 0.5);
+            unweighted.control(RATE, 1.0, 0.5);
+        }
+        let u_weighted = weighted.control(RATE, 1.0, 0.5) - weighted.integral();
+        let u_unweighted = unweighted.control(RATE, 1.0, 0.5) - unweighted.integral();
+        assert!((u_weighted + 2.0 - u_unweighted).abs() < 1e-9);
+    }
+}
\ No newline at end of file
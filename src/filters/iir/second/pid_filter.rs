@@ -1,4 +1,4 @@
-use crate::{calc::iir::second::PIDCalc, param::{PIDFilterParam, PID}};
+use crate::{calc::iir::second::PIDCalc, param::{PIDFFilterParam, PID}};
 
 crate::def_rtf!(
     {
@@ -10,6 +10,14 @@ crate::def_rtf!(
         ///            s
         /// </pre>
         /// 
+        /// With [`PIDF`](crate::param::PIDF)-parameters, the derivative term is band-limited:
+        /// 
+        /// <pre>
+        ///            I      Ds
+        /// H(s) = P + - + --------
+        ///            s   1 + sTf
+        /// </pre>
+        /// 
         /// # Frequency response
         /// 
         /// P = 1
@@ -24,7 +32,7 @@ crate::def_rtf!(
     }
     PIDFilter
     {
-        type Param: PIDFilterParam = PID;
+        type Param: PIDFFilterParam = PID;
 
         const OUTPUTS: usize = 1;
         const OUTPUT_BUFS: usize = 1;
@@ -35,7 +43,7 @@ crate::def_rtf!(
 
         fn make_coeffs(param, rate) -> _
         {
-            let calc = PIDCalc::new(param.pid(), rate);
+            let calc = PIDCalc::new(param.pidf(), rate, param.discretization());
             (
                 ([], [], [
                     calc.b()
@@ -51,6 +59,8 @@ crate::def_rtf!(
 #[cfg(test)]
 mod test
 {
+    use crate::{param::{Discretization, Discretized, PIDF}, rtf::Rtf};
+
     use super::{PIDFilter, PID};
 
     #[test]
@@ -59,4 +69,84 @@ mod test
         let mut filter = PIDFilter::new(PID {p: 1.0, i: 0.001, d: 0.00001});
         crate::tests::plot_freq(&mut filter).unwrap();
     }
+
+    #[test]
+    fn derivative_filter()
+    {
+        const RATE: f64 = 8000.0;
+
+        let pid = PID {p: 1.0, i: 0.001, d: 0.001};
+        let mut unfiltered = PIDFilter::new(pid);
+        let mut filtered = PIDFilter::new(PIDF::with_n(pid, 100.0));
+        let mut equivalent = PIDFilter::new(PIDF::from(pid));
+
+        // High-frequency gain is bounded by P + D/Tf instead of rising without limit
+        let omega = core::f64::consts::PI*0.9;
+        let h_unfiltered = unfiltered.frequency_response(RATE, omega)[0].norm();
+        let h_filtered = filtered.frequency_response(RATE, omega)[0].norm();
+        assert!(h_unfiltered > 10.0);
+        assert!(h_filtered < 1.0 + 0.001*100.0 + 1e-3);
+
+        // Tf = 0 is the same as plain PID
+        for x in [1.0, 0.0, -0.5, 0.25, 0.0, 0.0]
+        {
+            let [y1] = unfiltered.filter(RATE, x);
+            let [y2] = equivalent.filter(RATE, x);
+            assert!((y1 - y2).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn discretization()
+    {
+        const RATE: f64 = 100.0;
+        const T: f64 = 1.0/RATE;
+
+        let param = PIDF {p: 2.0, i: 3.0, d: 0.1, tf: 0.05};
+        let PIDF {p, i, d, tf} = param;
+        for discretization in [Discretization::ForwardEuler, Discretization::BackwardEuler, Discretization::Tustin]
+        {
+            let mut filter = PIDFilter::new(Discretized {param, discretization});
+
+            let mut integral = 0.0;
+            let mut derivative = 0.0;
+            let mut e_prev = 0.0;
+            for n in 0..64
+            {
+                let e = (0.3*n as f64).cos();
+                match discretization
+                {
+                    Discretization::ForwardEuler => {
+                        integral += i*T*e_prev;
+                        derivative = ((tf - T)*derivative + d*(e - e_prev))/tf;
+                    },
+                    Discretization::BackwardEuler => {
+                        integral += i*T*e;
+                        derivative = (tf*derivative + d*(e - e_prev))/(tf + T);
+                    },
+                    Discretization::Tustin => {
+                        integral += i*T*(e + e_prev)/2.0;
+                        derivative = ((2.0*tf - T)*derivative + 2.0*d*(e - e_prev))/(2.0*tf + T);
+                    }
+                }
+                let u = p*e + integral + derivative;
+                let [y] = filter.filter(RATE, e);
+                assert!((y - u).abs() < 1e-9, "{discretization:?}, n = {n}");
+                e_prev = e;
+            }
+        }
+
+        // Without a derivative filter, forward Euler falls back to backward differences for the derivative term
+        let param = PIDF {tf: 0.0, ..param};
+        let mut forward = PIDFilter::new(Discretized {param, discretization: Discretization::ForwardEuler});
+        let mut backward = PIDFilter::new(Discretized {param, discretization: Discretization::BackwardEuler});
+        for n in 0..64
+        {
+            let e = (0.3*n as f64).cos();
+            let [y_forward] = forward.filter(RATE, e);
+            let [y_backward] = backward.filter(RATE, e);
+            // Only the integral term differs, by the latest error
+            assert!((y_backward - y_forward - i*T*e).abs() < 1e-9, "n = {n}");
+        }
+    }
 }
\ No newline at end of file
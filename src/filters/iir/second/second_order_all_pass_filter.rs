@@ -0,0 +1,90 @@
+use crate::{calc::iir::second::SecondOrderAllPassCalc, conf::All, param::{OmegaZeta, SecondOrderAllPassFilterConf, SecondOrderAllPassFilterParam}};
+
+crate::def_rtf!(
+    {
+        /// # Configurations
+        /// 
+        /// [`All`](crate::conf::All), [`AllPass`](crate::conf::AllPass)
+        /// 
+        /// ## All-pass
+        /// 
+        /// <pre>
+        ///        s² - 2ζωs + ω²
+        /// H(s) = --------------
+        ///        s² + 2ζωs + ω²
+        /// </pre>
+        /// 
+        /// The phase goes from 0 to -2π, passing -π at ω. A smaller ζ makes the phase shift steeper around ω.
+        /// 
+        /// # Frequency response
+        /// 
+        /// ω = 1 kHz 2π
+        /// 
+        /// ζ = 0.5
+        /// 
+        /// <div>
+        /// <img alt="Second order all-pass filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/second_order_all_pass_filter.png" height="500">
+        /// </div>
+    }
+    SecondOrderAllPassFilter
+    {
+        type Conf: SecondOrderAllPassFilterConf;
+        type Param: SecondOrderAllPassFilterParam = OmegaZeta;
+
+        const OUTPUT_BUFS: usize = 1;
+        const SOS_BUFS: usize = 1;
+        const SOS_STAGES: usize = 0;
+        const ORDER: usize = 2;
+        const IS_IIR: bool = true;
+
+        fn make_coeffs<All>(param, rate) -> _
+        {
+            let calc = SecondOrderAllPassCalc::new(param.omega_zeta(), rate);
+            (
+                ([], [], [
+                    calc.b()
+                ]),
+                [([], [
+                    calc.a()
+                ])]
+            )
+        }
+    }
+);
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::{PI, TAU};
+
+    use crate::{conf::All, param::OmegaZeta, rtf::Rtf};
+
+    use super::SecondOrderAllPassFilter;
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = SecondOrderAllPassFilter::<All>::new(OmegaZeta {omega: 1e3*TAU, zeta: 0.5});
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn phase()
+    {
+        const RATE: f64 = 44100.0;
+
+        let omega = 1e3*TAU;
+        let mut filter = SecondOrderAllPassFilter::<All>::new(OmegaZeta {omega, zeta: 0.5});
+
+        for f in [10.0, 100.0, 1000.0, 10000.0, 20000.0]
+        {
+            let [h] = filter.frequency_response(RATE, f*TAU/RATE);
+            assert!((h.norm() - 1.0).abs() < 1e-9, "{f}Hz: |H| = {}", h.norm());
+        }
+
+        // Phase is -π at ω, after frequency warping
+        let omega_warped = 2.0*(omega/(2.0*RATE)).atan();
+        let [h] = filter.frequency_response(RATE, omega_warped);
+        assert!((h.arg().abs() - PI).abs() < 1e-9, "{}", h.arg());
+    }
+}
\ No newline at end of file
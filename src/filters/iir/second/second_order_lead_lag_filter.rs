@@ -0,0 +1,101 @@
+use crate::{calc::iir::second::SecondOrderLeadLagCalc, param::{LeadLag, SecondOrderLeadLagFilterParam}};
+
+crate::def_rtf!(
+    {
+        /// # Configuration
+        /// 
+        /// <pre>
+        ///             1 + s/ω₀₁  1 + s/ω₀₂
+        /// H(s) = K₁K₂ ---------  ---------
+        ///             1 + s/ωₚ₁  1 + s/ωₚ₂
+        /// </pre>
+        /// 
+        /// Typically a lead-compensator in series with a lag-compensator. The lag section raises the low-frequency gain, while the lead section adds
+        /// phase around the crossover frequency.
+        /// 
+        /// # Frequency response
+        /// 
+        /// Lead: ω₀ = 1 kHz, ωₚ = 10 kHz, K = 1
+        /// 
+        /// Lag: ω₀ = 100 Hz, ωₚ = 10 Hz, K = 1
+        /// 
+        /// <div>
+        /// <img alt="Second order lead-lag filter response" src="https://raw.githubusercontent.com/sigurd4/real_time_fir_iir_filters/refs/heads/master/plots/second_order_lead_lag_filter.png" height="500">
+        /// </div>
+    }
+    SecondOrderLeadLagFilter
+    {
+        type Param: SecondOrderLeadLagFilterParam = LeadLag;
+
+        const OUTPUTS: usize = 1;
+        const OUTPUT_BUFS: usize = 1;
+        const SOS_BUFS: usize = 1;
+        const SOS_STAGES: usize = 0;
+        const ORDER: usize = 2;
+        const IS_IIR: bool = true;
+
+        fn make_coeffs(param, rate) -> _
+        {
+            let calc = SecondOrderLeadLagCalc::new(param.zero_pole2(), rate);
+            (
+                ([], [], [
+                    calc.b()
+                ]),
+                [([], [
+                    calc.a()
+                ])]
+            )
+        }
+    }
+);
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use crate::{filters::iir::first::FirstOrderLeadLagFilter, param::ZeroPole, rtf::Rtf};
+
+    use super::{LeadLag, SecondOrderLeadLagFilter};
+
+    #[test]
+    fn plot()
+    {
+        let mut filter = SecondOrderLeadLagFilter::new(LeadLag {
+            lead: ZeroPole {zero: 1000.0*TAU, pole: 10000.0*TAU, gain: 1.0},
+            lag: ZeroPole {zero: 100.0*TAU, pole: 10.0*TAU, gain: 1.0}
+        });
+        crate::tests::plot_freq(&mut filter).unwrap();
+    }
+
+    #[test]
+    fn series()
+    {
+        const RATE: f64 = 44100.0;
+
+        let lead = ZeroPole {zero: 1000.0*TAU, pole: 10000.0*TAU, gain: 2.0};
+        let lag = ZeroPole {zero: 100.0*TAU, pole: 10.0*TAU, gain: 3.0};
+        let mut filter = SecondOrderLeadLagFilter::new(LeadLag {lead, lag});
+        let mut lead = FirstOrderLeadLagFilter::new(lead);
+        let mut lag = FirstOrderLeadLagFilter::new(lag);
+
+        // Same as the two first-order sections in series
+        for n in 0..64
+        {
+            let x = if n == 0 {1.0} else {0.0};
+            let [y] = filter.filter(RATE, x);
+            let [y_series] = lag.filter(RATE, lead.filter(RATE, x)[0]);
+            assert!((y - y_series).abs() < 1e-9, "n = {n}");
+        }
+
+        // DC gain is the product of the gains
+        let h = filter.frequency_response(RATE, 0.0)[0];
+        assert!((h.re - 6.0).abs() < 1e-9 && h.im.abs() < 1e-9, "{h}");
+
+        // A first-order parameter works too
+        let mut filter = SecondOrderLeadLagFilter::new(ZeroPole {zero: 100.0*TAU, pole: 1000.0*TAU, gain: 1.0});
+        let mut first = FirstOrderLeadLagFilter::new(ZeroPole {zero: 100.0*TAU, pole: 1000.0*TAU, gain: 1.0});
+        let omega = 300.0*TAU/RATE;
+        assert!((filter.frequency_response(RATE, omega)[0] - first.frequency_response(RATE, omega)[0]).norm() < 1e-9);
+    }
+}
\ No newline at end of file
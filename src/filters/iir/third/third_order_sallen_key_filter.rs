@@ -1,13 +1,13 @@
 use core::fmt::Debug;
 
-use crate::{calc::iir::third::ThirdOrderSallenKeyCalc, conf::{All, BandPass, HighPass, LowPass}, param::{FilterFloat, FilterParam, FirstOrderRCFilterConf, Param, RC3GSallenKey, SecondOrderSallenKeyFilterConf, ThirdOrderSallenKeyFilterConf, ThirdOrderSallenKeyFilterParam}, rtf::StaticRtf, util::ArrayMul};
+use crate::{calc::iir::third::ThirdOrderSallenKeyCalc, conf::{All, BandPass, HighPass, LowPass}, param::{FilterFloat, FilterParam, FirstOrderRCFilterConf, Param, RC3GSallenKey, SecondOrderSallenKeyFilterConf, ThirdOrderSallenKeyFilterConf, ThirdOrderSallenKeyFilterParam}, rtf::{RtfBase, StaticRtf}, util::ArrayMul};
 
 crate::rtfinternals!(
     type Conf: ThirdOrderSallenKeyFilterConf;
 
-    const SOS_BUFS: usize = 1;
-    const SOS_STAGES: usize = 0;
-    const ORDER: usize = 3;
+    type SosBufs<U> = <C as ThirdOrderSallenKeyFilterConf>::OutputBufs<U>;
+    const SOS_STAGES: usize = 1;
+    const ORDER: usize = 1;
     const IS_IIR: bool = true;
 );
 
@@ -171,7 +171,7 @@ macro_rules! c {
         )*
     ) => {
         $(
-            impl<P, C> StaticRtf for ThirdOrderSallenKeyFilter<C, <P as FilterParam>::F, P, $conf1, $conf2>
+            impl<P, C> RtfBase for ThirdOrderSallenKeyFilter<C, <P as FilterParam>::F, P, $conf1, $conf2>
             where
                 P: ThirdOrderSallenKeyFilterParam<C, Conf = C>,
                 C: ThirdOrderSallenKeyFilterConf<
@@ -179,8 +179,8 @@ macro_rules! c {
                     S1Conf = $conf1,
                     S2Conf = $conf2,
                     OutputBufs<[P::F; 3]> = <$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 3]>,
-                    OutputBufs<[P::F; 4]> = <$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 4]>,
-                    Outputs<[P::F; 4]> = <<$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 4]> as ArrayMul<<$conf1 as FirstOrderRCFilterConf>::Outputs<[P::F; 4]>>>::Product
+                    OutputBufs<[P::F; 2]> = <$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 2]>,
+                    Outputs<[P::F; 2]> = <<$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 2]> as ArrayMul<<$conf1 as FirstOrderRCFilterConf>::Outputs<[P::F; 2]>>>::Product
                 >,
                 //
                 $conf1: FirstOrderRCFilterConf<Conf = $conf1>,
@@ -191,14 +191,8 @@ macro_rules! c {
                 type Param = P;
                 type Conf = C;
                 type F = <P as FilterParam>::F;
-
-                type IsIir<U> = <C as private::_Helper>::IsIir<U>;
                 type Outputs<U> = <C as private::_Helper>::Outputs<U>;
-                type Order<U> = <C as private::_Helper>::Order<U>;
-                type OutputBufs<U> = <C as private::_Helper>::OutputBufs<U>;
-                type SosBufs<U> = <C as private::_Helper>::SosBufs<U>;
-                type SosStages<U> = <C as private::_Helper>::SosStages<U>;
-                
+
                 fn from_param(param: Self::Param) -> Self
                 {
                     Self::new(param)
@@ -215,6 +209,30 @@ macro_rules! c {
                 {
                     self.param.into_value()
                 }
+            }
+
+            impl<P, C> StaticRtf for ThirdOrderSallenKeyFilter<C, <P as FilterParam>::F, P, $conf1, $conf2>
+            where
+                P: ThirdOrderSallenKeyFilterParam<C, Conf = C>,
+                C: ThirdOrderSallenKeyFilterConf<
+                    Conf = C,
+                    S1Conf = $conf1,
+                    S2Conf = $conf2,
+                    OutputBufs<[P::F; 3]> = <$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 3]>,
+                    OutputBufs<[P::F; 2]> = <$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 2]>,
+                    Outputs<[P::F; 2]> = <<$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 2]> as ArrayMul<<$conf1 as FirstOrderRCFilterConf>::Outputs<[P::F; 2]>>>::Product
+                >,
+                //
+                $conf1: FirstOrderRCFilterConf<Conf = $conf1>,
+                $conf2: SecondOrderSallenKeyFilterConf<Conf = $conf2>,
+                Internals<P::F, C>: Copy + Debug + PartialEq,
+                $($($where_c)+)?
+            {
+                type IsIir<U> = <C as private::_Helper>::IsIir<U>;
+                type Order<U> = <C as private::_Helper>::Order<U>;
+                type OutputBufs<U> = <C as private::_Helper>::OutputBufs<U>;
+                type SosBufs<U> = <C as private::_Helper>::SosBufs<U>;
+                type SosStages<U> = <C as private::_Helper>::SosStages<U>;
                 
                 fn get_internals(&self) -> (&$crate::internals::RtfInternalsFor<Self>, &Param<Self::Param>)
                 {
@@ -240,8 +258,8 @@ macro_rules! c {
                         C: ThirdOrderSallenKeyFilterConf<
                             Conf = C, S1Conf = $conf1, S2Conf = $conf2,
                             OutputBufs<[P::F; 3]> = <$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 3]>,
-                            OutputBufs<[P::F; 4]> = <$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 4]>,
-                            Outputs<[P::F; 4]> = <<$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 4]> as ArrayMul<<$conf1 as FirstOrderRCFilterConf>::Outputs<[P::F; 4]>>>::Product
+                            OutputBufs<[P::F; 2]> = <$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 2]>,
+                            Outputs<[P::F; 2]> = <<$conf2 as SecondOrderSallenKeyFilterConf>::Outputs<[P::F; 2]> as ArrayMul<<$conf1 as FirstOrderRCFilterConf>::Outputs<[P::F; 2]>>>::Product
                         >,
                         $conf1: FirstOrderRCFilterConf<Conf = $conf1>,
                         $conf2: SecondOrderSallenKeyFilterConf<Conf = $conf2>,
@@ -260,58 +278,70 @@ c!(
     fn make_coeffs<All, All>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_high_low(),
-                calc.b_low_band1(),
-                calc.b_high_band1(),
-                calc.b_low_band2(),
-                calc.b_high_band2(),
-                calc.b_low_high(),
-                calc.b_high_high()
+            ([], [[low.b, band1.b, band2.b, high.b]], [
+                low.b_low,
+                low.b_high,
+                band1.b_low,
+                band1.b_high,
+                band2.b_low,
+                band2.b_high,
+                high.b_low,
+                high.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1(),
-                calc.a_band2(),
-                calc.a_high()
+            [([[low.a, band1.a, band2.a, high.a]], [
+                low.a_output,
+                band1.a_output,
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, All>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_low_band1(),
-                calc.b_low_band2(),
-                calc.b_low_high()
+            ([], [[low.b, band1.b, band2.b, high.b]], [
+                low.b_low,
+                band1.b_low,
+                band2.b_low,
+                high.b_low
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1(),
-                calc.a_band2(),
-                calc.a_high()
+            [([[low.a, band1.a, band2.a, high.a]], [
+                low.a_output,
+                band1.a_output,
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, All>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_high_low(),
-                calc.b_high_band1(),
-                calc.b_high_band2(),
-                calc.b_high_high()
+            ([], [[low.b, band1.b, band2.b, high.b]], [
+                low.b_high,
+                band1.b_high,
+                band2.b_high,
+                high.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1(),
-                calc.a_band2(),
-                calc.a_high()
+            [([[low.a, band1.a, band2.a, high.a]], [
+                low.a_output,
+                band1.a_output,
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
@@ -319,37 +349,40 @@ c!(
     fn make_coeffs<All, LowPass>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_high_low()
+            ([], [[low.b]], [
+                low.b_low,
+                low.b_high
             ]),
-            [([], [
-                calc.a_low()
+            [([[low.a]], [
+                low.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, LowPass>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
         (
-            ([], [], [
-                calc.b_low_low()
+            ([], [[low.b]], [
+                low.b_low
             ]),
-            [([], [
-                calc.a_low()
+            [([[low.a]], [
+                low.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, LowPass>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
         (
-            ([], [], [
-                calc.b_high_low()
+            ([], [[low.b]], [
+                low.b_high
             ]),
-            [([], [
-                calc.a_low()
+            [([[low.a]], [
+                low.a_output
             ])]
         )
     }
@@ -357,37 +390,40 @@ c!(
     fn make_coeffs<All, BandPass<1>>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
         (
-            ([], [], [
-                calc.b_low_band1(),
-                calc.b_high_band1()
+            ([], [[band1.b]], [
+                band1.b_low,
+                band1.b_high
             ]),
-            [([], [
-                calc.a_band1()
+            [([[band1.a]], [
+                band1.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, BandPass<1>>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
         (
-            ([], [], [
-                calc.b_low_band1()
+            ([], [[band1.b]], [
+                band1.b_low
             ]),
-            [([], [
-                calc.a_band1()
+            [([[band1.a]], [
+                band1.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, BandPass<1>>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
         (
-            ([], [], [
-                calc.b_high_band1()
+            ([], [[band1.b]], [
+                band1.b_high
             ]),
-            [([], [
-                calc.a_band1()
+            [([[band1.a]], [
+                band1.a_output
             ])]
         )
     }
@@ -395,37 +431,40 @@ c!(
     fn make_coeffs<All, BandPass<2>>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_low_band2(),
-                calc.b_high_band2()
+            ([], [[band2.b]], [
+                band2.b_low,
+                band2.b_high
             ]),
-            [([], [
-                calc.a_band2()
+            [([[band2.a]], [
+                band2.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, BandPass<2>>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_low_band2()
+            ([], [[band2.b]], [
+                band2.b_low
             ]),
-            [([], [
-                calc.a_band2()
+            [([[band2.a]], [
+                band2.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, BandPass<2>>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_high_band2()
+            ([], [[band2.b]], [
+                band2.b_high
             ]),
-            [([], [
-                calc.a_band2()
+            [([[band2.a]], [
+                band2.a_output
             ])]
         )
     }
@@ -433,37 +472,40 @@ c!(
     fn make_coeffs<All, HighPass>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_high(),
-                calc.b_high_high()
+            ([], [[high.b]], [
+                high.b_low,
+                high.b_high
             ]),
-            [([], [
-                calc.a_high()
+            [([[high.a]], [
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, HighPass>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_high()
+            ([], [[high.b]], [
+                high.b_low
             ]),
-            [([], [
-                calc.a_high()
+            [([[high.a]], [
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, HighPass>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_high_high()
+            ([], [[high.b]], [
+                high.b_high
             ]),
-            [([], [
-                calc.a_high()
+            [([[high.a]], [
+                high.a_output
             ])]
         )
     }
@@ -473,44 +515,50 @@ c!(
     fn make_coeffs<All, (LowPass, BandPass<1>)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_high_low(),
-                calc.b_low_band1(),
-                calc.b_high_band1()
+            ([], [[low.b, band1.b]], [
+                low.b_low,
+                low.b_high,
+                band1.b_low,
+                band1.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1()
+            [([[low.a, band1.a]], [
+                low.a_output,
+                band1.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, (LowPass, BandPass<1>)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_low_band1()
+            ([], [[low.b, band1.b]], [
+                low.b_low,
+                band1.b_low
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1()
+            [([[low.a, band1.a]], [
+                low.a_output,
+                band1.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, (LowPass, BandPass<1>)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
         (
-            ([], [], [
-                calc.b_high_low(),
-                calc.b_high_band1()
+            ([], [[low.b, band1.b]], [
+                low.b_high,
+                band1.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1()
+            [([[low.a, band1.a]], [
+                low.a_output,
+                band1.a_output
             ])]
         )
     }
@@ -518,44 +566,50 @@ c!(
     fn make_coeffs<All, (LowPass, BandPass<2>)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_high_low(),
-                calc.b_low_band2(),
-                calc.b_high_band2()
+            ([], [[low.b, band2.b]], [
+                low.b_low,
+                low.b_high,
+                band2.b_low,
+                band2.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band2()
+            [([[low.a, band2.a]], [
+                low.a_output,
+                band2.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, (LowPass, BandPass<2>)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_low_band2()
+            ([], [[low.b, band2.b]], [
+                low.b_low,
+                band2.b_low
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band2()
+            [([[low.a, band2.a]], [
+                low.a_output,
+                band2.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, (LowPass, BandPass<2>)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_high_low(),
-                calc.b_high_band2()
+            ([], [[low.b, band2.b]], [
+                low.b_high,
+                band2.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band2()
+            [([[low.a, band2.a]], [
+                low.a_output,
+                band2.a_output
             ])]
         )
     }
@@ -563,44 +617,50 @@ c!(
     fn make_coeffs<All, (LowPass, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_high_low(),
-                calc.b_low_high(),
-                calc.b_high_high()
+            ([], [[low.b, high.b]], [
+                low.b_low,
+                low.b_high,
+                high.b_low,
+                high.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_high()
+            [([[low.a, high.a]], [
+                low.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, (LowPass, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_low_high()
+            ([], [[low.b, high.b]], [
+                low.b_low,
+                high.b_low
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_high()
+            [([[low.a, high.a]], [
+                low.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, (LowPass, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_high_low(),
-                calc.b_high_high()
+            ([], [[low.b, high.b]], [
+                low.b_high,
+                high.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_high()
+            [([[low.a, high.a]], [
+                low.a_output,
+                high.a_output
             ])]
         )
     }
@@ -608,44 +668,50 @@ c!(
     fn make_coeffs<All, BandPass>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_low_band1(),
-                calc.b_high_band1(),
-                calc.b_low_band2(),
-                calc.b_high_band2()
+            ([], [[band1.b, band2.b]], [
+                band1.b_low,
+                band1.b_high,
+                band2.b_low,
+                band2.b_high
             ]),
-            [([], [
-                calc.a_band1(),
-                calc.a_band2()
+            [([[band1.a, band2.a]], [
+                band1.a_output,
+                band2.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, BandPass>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_low_band1(),
-                calc.b_low_band2()
+            ([], [[band1.b, band2.b]], [
+                band1.b_low,
+                band2.b_low
             ]),
-            [([], [
-                calc.a_band1(),
-                calc.a_band2()
+            [([[band1.a, band2.a]], [
+                band1.a_output,
+                band2.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, BandPass>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_high_band1(),
-                calc.b_high_band2()
+            ([], [[band1.b, band2.b]], [
+                band1.b_high,
+                band2.b_high
             ]),
-            [([], [
-                calc.a_band1(),
-                calc.a_band2()
+            [([[band1.a, band2.a]], [
+                band1.a_output,
+                band2.a_output
             ])]
         )
     }
@@ -653,44 +719,50 @@ c!(
     fn make_coeffs<All, (BandPass<1>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_band1(),
-                calc.b_high_band1(),
-                calc.b_low_high(),
-                calc.b_high_high()
+            ([], [[band1.b, high.b]], [
+                band1.b_low,
+                band1.b_high,
+                high.b_low,
+                high.b_high
             ]),
-            [([], [
-                calc.a_band1(),
-                calc.a_high()
+            [([[band1.a, high.a]], [
+                band1.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, (BandPass<1>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_band1(),
-                calc.b_low_high()
+            ([], [[band1.b, high.b]], [
+                band1.b_low,
+                high.b_low
             ]),
-            [([], [
-                calc.a_band1(),
-                calc.a_high()
+            [([[band1.a, high.a]], [
+                band1.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, (BandPass<1>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_high_band1(),
-                calc.b_high_high()
+            ([], [[band1.b, high.b]], [
+                band1.b_high,
+                high.b_high
             ]),
-            [([], [
-                calc.a_band1(),
-                calc.a_band2()
+            [([[band1.a, high.a]], [
+                band1.a_output,
+                high.a_output
             ])]
         )
     }
@@ -698,44 +770,50 @@ c!(
     fn make_coeffs<All, (BandPass<2>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_band2(),
-                calc.b_high_band2(),
-                calc.b_low_high(),
-                calc.b_high_high()
+            ([], [[band2.b, high.b]], [
+                band2.b_low,
+                band2.b_high,
+                high.b_low,
+                high.b_high
             ]),
-            [([], [
-                calc.a_band2(),
-                calc.a_high()
+            [([[band2.a, high.a]], [
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, (BandPass<2>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_band2(),
-                calc.b_low_high()
+            ([], [[band2.b, high.b]], [
+                band2.b_low,
+                high.b_low
             ]),
-            [([], [
-                calc.a_band2(),
-                calc.a_high()
+            [([[band2.a, high.a]], [
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, (BandPass<2>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_high_band2(),
-                calc.b_high_high()
+            ([], [[band2.b, high.b]], [
+                band2.b_high,
+                high.b_high
             ]),
-            [([], [
-                calc.a_band2(),
-                calc.a_high()
+            [([[band2.a, high.a]], [
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
@@ -743,51 +821,60 @@ c!(
     fn make_coeffs<All, (LowPass, BandPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_high_low(),
-                calc.b_low_band1(),
-                calc.b_high_band1(),
-                calc.b_low_band2(),
-                calc.b_high_band2()
+            ([], [[low.b, band1.b, band2.b]], [
+                low.b_low,
+                low.b_high,
+                band1.b_low,
+                band1.b_high,
+                band2.b_low,
+                band2.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1(),
-                calc.a_band2()
+            [([[low.a, band1.a, band2.a]], [
+                low.a_output,
+                band1.a_output,
+                band2.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, (LowPass, BandPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_low_band1(),
-                calc.b_low_band2()
+            ([], [[low.b, band1.b, band2.b]], [
+                low.b_low,
+                band1.b_low,
+                band2.b_low
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1(),
-                calc.a_band2()
+            [([[low.a, band1.a, band2.a]], [
+                low.a_output,
+                band1.a_output,
+                band2.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, (LowPass, BandPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
         (
-            ([], [], [
-                calc.b_high_low(),
-                calc.b_high_band1(),
-                calc.b_high_band2()
+            ([], [[low.b, band1.b, band2.b]], [
+                low.b_high,
+                band1.b_high,
+                band2.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1(),
-                calc.a_band2()
+            [([[low.a, band1.a, band2.a]], [
+                low.a_output,
+                band1.a_output,
+                band2.a_output
             ])]
         )
     }
@@ -795,51 +882,60 @@ c!(
     fn make_coeffs<All, (LowPass, BandPass<1>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_high_low(),
-                calc.b_low_band1(),
-                calc.b_high_band1(),
-                calc.b_low_high(),
-                calc.b_high_high()
+            ([], [[low.b, band1.b, high.b]], [
+                low.b_low,
+                low.b_high,
+                band1.b_low,
+                band1.b_high,
+                high.b_low,
+                high.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1(),
-                calc.a_high()
+            [([[low.a, band1.a, high.a]], [
+                low.a_output,
+                band1.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, (LowPass, BandPass<1>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_low_band1(),
-                calc.b_low_high()
+            ([], [[low.b, band1.b, high.b]], [
+                low.b_low,
+                band1.b_low,
+                high.b_low
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1(),
-                calc.a_high()
+            [([[low.a, band1.a, high.a]], [
+                low.a_output,
+                band1.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, (LowPass, BandPass<1>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band1 = calc.sos_band1();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_high_low(),
-                calc.b_high_band1(),
-                calc.b_high_high()
+            ([], [[low.b, band1.b, high.b]], [
+                low.b_high,
+                band1.b_high,
+                high.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band1(),
-                calc.a_high()
+            [([[low.a, band1.a, high.a]], [
+                low.a_output,
+                band1.a_output,
+                high.a_output
             ])]
         )
     }
@@ -847,51 +943,60 @@ c!(
     fn make_coeffs<All, (LowPass, BandPass<2>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_high_low(),
-                calc.b_low_band2(),
-                calc.b_high_band2(),
-                calc.b_low_high(),
-                calc.b_high_high()
+            ([], [[low.b, band2.b, high.b]], [
+                low.b_low,
+                low.b_high,
+                band2.b_low,
+                band2.b_high,
+                high.b_low,
+                high.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band2(),
-                calc.a_high()
+            [([[low.a, band2.a, high.a]], [
+                low.a_output,
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, (LowPass, BandPass<2>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_low(),
-                calc.b_low_band2(),
-                calc.b_low_high()
+            ([], [[low.b, band2.b, high.b]], [
+                low.b_low,
+                band2.b_low,
+                high.b_low
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band2(),
-                calc.a_high()
+            [([[low.a, band2.a, high.a]], [
+                low.a_output,
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, (LowPass, BandPass<2>, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let low = calc.sos_low();
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_high_low(),
-                calc.b_high_band2(),
-                calc.b_high_high()
+            ([], [[low.b, band2.b, high.b]], [
+                low.b_high,
+                band2.b_high,
+                high.b_high
             ]),
-            [([], [
-                calc.a_low(),
-                calc.a_band2(),
-                calc.a_high()
+            [([[low.a, band2.a, high.a]], [
+                low.a_output,
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
@@ -899,51 +1004,60 @@ c!(
     fn make_coeffs<All, (BandPass, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_band1(),
-                calc.b_high_band1(),
-                calc.b_low_band2(),
-                calc.b_high_band2(),
-                calc.b_low_high(),
-                calc.b_high_high()
+            ([], [[band1.b, band2.b, high.b]], [
+                band1.b_low,
+                band1.b_high,
+                band2.b_low,
+                band2.b_high,
+                high.b_low,
+                high.b_high
             ]),
-            [([], [
-                calc.a_band1(),
-                calc.a_band2(),
-                calc.a_high()
+            [([[band1.a, band2.a, high.a]], [
+                band1.a_output,
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<LowPass, (BandPass, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_low_band1(),
-                calc.b_low_band2(),
-                calc.b_low_high()
+            ([], [[band1.b, band2.b, high.b]], [
+                band1.b_low,
+                band2.b_low,
+                high.b_low
             ]),
-            [([], [
-                calc.a_band1(),
-                calc.a_band2(),
-                calc.a_high()
+            [([[band1.a, band2.a, high.a]], [
+                band1.a_output,
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
     fn make_coeffs<HighPass, (BandPass, HighPass)>(param, rate) -> _
     {
         let calc = ThirdOrderSallenKeyCalc::new(param.rc3g(), rate);
+        let band1 = calc.sos_band1();
+        let band2 = calc.sos_band2();
+        let high = calc.sos_high();
         (
-            ([], [], [
-                calc.b_high_band1(),
-                calc.b_high_band2(),
-                calc.b_high_high()
+            ([], [[band1.b, band2.b, high.b]], [
+                band1.b_high,
+                band2.b_high,
+                high.b_high
             ]),
-            [([], [
-                calc.a_band1(),
-                calc.a_band2(),
-                calc.a_high()
+            [([[band1.a, band2.a, high.a]], [
+                band1.a_output,
+                band2.a_output,
+                high.a_output
             ])]
         )
     }
@@ -952,14 +1066,57 @@ c!(
 #[cfg(test)]
 mod test
 {
-    use crate::{conf::All, param::RC3GSallenKey};
+    use num::Complex;
+
+    use crate::{calc::iir::third::ThirdOrderSallenKeyCalc, conf::{All, BandPass, HighPass}, param::RC3GSallenKey, rtf::Rtf};
 
     use super::ThirdOrderSallenKeyFilter;
 
+    const RC3G: RC3GSallenKey<f64> = RC3GSallenKey {r1: 470.0, c1: 47.0e-9, r2: 15.0e3, c2: 2.7e-9, r3: 16.0e3, c3: 2.7e-9, g: 1.3846153846153846};
+    const RATE: f64 = 44100.0;
+
+    fn eval(c: [f64; 4], z: Complex<f64>) -> Complex<f64>
+    {
+        c.iter()
+            .rev()
+            .fold(Complex::from(0.0), |y, &c| y*z.inv() + c)
+    }
+
+    #[test]
+    fn sections()
+    {
+        let calc = ThirdOrderSallenKeyCalc::new(RC3G, RATE);
+        let mut all = ThirdOrderSallenKeyFilter::<All>::new(RC3G);
+        let mut high_band1_high = ThirdOrderSallenKeyFilter::<(BandPass<3>, HighPass)>::new(RC3G);
+
+        for omega in (1..32).map(|i| i as f64/32.0*core::f64::consts::PI)
+        {
+            let z = Complex::cis(omega);
+            let h = [
+                (calc.b_low_low(), calc.a_low()),
+                (calc.b_high_low(), calc.a_low()),
+                (calc.b_low_band1(), calc.a_band1()),
+                (calc.b_high_band1(), calc.a_band1()),
+                (calc.b_low_band2(), calc.a_band2()),
+                (calc.b_high_band2(), calc.a_band2()),
+                (calc.b_low_high(), calc.a_high()),
+                (calc.b_high_high(), calc.a_high())
+            ].map(|(b, a)| eval(b, z)/eval(a, z));
+
+            for (h_sos, h) in all.z_response(RATE, z)
+                .into_iter()
+                .chain(high_band1_high.z_response(RATE, z))
+                .zip(h.into_iter().chain([h[3], h[7]]))
+            {
+                assert!((h_sos - h).norm() <= 1e-9*(1.0 + h.norm()), "{h_sos} != {h}")
+            }
+        }
+    }
+
     #[test]
     fn plot()
     {
-        let mut filter = ThirdOrderSallenKeyFilter::<All>::new(RC3GSallenKey {r1: 470.0, c1: 47.0e-9, r2: 15.0e3, c2: 2.7e-9, r3: 16.0e3, c3: 2.7e-9, g: 1.3846153846153846});
+        let mut filter = ThirdOrderSallenKeyFilter::<All>::new(RC3G);
         //let mut filter = ThirdOrderSallenKeyFilter::<All>::new(RC2GSallenKey {r1: 15.0e3, c1: 2.7e-9, r2: 15.0e3, c2: 2.7e-9, g: 2.0});
         //let mut filter = ThirdOrderSallenKeyFilter::<All>::new(RC {r: 470.0, c: 47.0e-9});
 
@@ -2,5 +2,8 @@ moddef::moddef!(
     pub mod {
         fir,
         iir
+    },
+    mod {
+        delay_line for cfg(feature = "comb")
     }
 );
\ No newline at end of file
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{param::FilterFloat, rtf::StaticRtf, serde::{DeserializeOrZeroed, MaybeSerialize}};
+use crate::{param::FilterFloat, rtf::{DenormalProtection, StaticRtf, Structure}, serde::{MaybeDeserialized, MaybeSerialized}};
 
 pub type WInternals<F, const OUTPUT_BUFS: usize, const SOS_BUFS: usize, const SOS_STAGES: usize, const ORDER: usize> = crate::winternals!(F, OUTPUT_BUFS, SOS_BUFS, SOS_STAGES, ORDER);
 pub type BInternals<F, const OUTPUTS: usize, const OUTPUT_BUFS: usize, const SOS_BUFS: usize, const SOS_STAGES: usize, const ORDER: usize> = crate::binternals!(F, OUTPUTS, OUTPUT_BUFS, SOS_BUFS, SOS_STAGES, ORDER);
@@ -31,9 +31,105 @@ where
     pub w: W,
     pub b: B,
     pub a: A,
-    pub(crate) rate: Option<F>
+    pub(crate) rate: Option<F>,
+    pub(crate) structure: StructureInternals<B, A>,
+    pub(crate) denormal_protection: DenormalProtection<F>,
+    pub(crate) seed: u32
 }
 
+/// The realization [`Structure`], with whatever it needs on top of `w`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum StructureInternals<B, A>
+{
+    /// With the output history of each section.
+    DirectFormI(B),
+    DirectFormII,
+    /// With the delay lines of each output.
+    TransposedDirectFormII(B),
+    /// With the cached lattice coefficients. `None` if not yet computed, and `Some(None)` if the filter can't be realized as a lattice.
+    #[allow(clippy::type_complexity)]
+    Lattice(Option<Option<(B, A, A)>>)
+}
+
+impl<B, A> StructureInternals<B, A>
+{
+    pub(crate) const fn new(structure: Structure) -> Self
+    {
+        match structure
+        {
+            Structure::DirectFormI => Self::DirectFormI(unsafe {core::mem::zeroed()}),
+            Structure::DirectFormII => Self::DirectFormII,
+            Structure::TransposedDirectFormII => Self::TransposedDirectFormII(unsafe {core::mem::zeroed()}),
+            Structure::Lattice => Self::Lattice(None)
+        }
+    }
+
+    pub(crate) const fn structure(&self) -> Structure
+    {
+        match self
+        {
+            Self::DirectFormI(_) => Structure::DirectFormI,
+            Self::DirectFormII => Structure::DirectFormII,
+            Self::TransposedDirectFormII(_) => Structure::TransposedDirectFormII,
+            Self::Lattice(_) => Structure::Lattice
+        }
+    }
+
+    /// The additional state, for the structures that need it.
+    pub(crate) fn v(&self) -> Option<&B>
+    {
+        match self
+        {
+            Self::DirectFormI(v) | Self::TransposedDirectFormII(v) => Some(v),
+            Self::DirectFormII | Self::Lattice(_) => None
+        }
+    }
+
+    /// The additional state, for the structures that need it.
+    pub(crate) fn v_mut(&mut self) -> Option<&mut B>
+    {
+        match self
+        {
+            Self::DirectFormI(v) | Self::TransposedDirectFormII(v) => Some(v),
+            Self::DirectFormII | Self::Lattice(_) => None
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "RtfInternals", bound = "")]
+struct FieldsRef<'a, F, W, B>
+{
+    w: MaybeSerialized<'a, W>,
+    structure: Structure,
+    v: MaybeSerialized<'a, Option<&'a B>>,
+    denormal_protection: MaybeSerialized<'a, DenormalProtection<F>>
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "RtfInternals", bound = "", deny_unknown_fields)]
+struct Fields<F, W, B>
+{
+    w: MaybeDeserialized<W>,
+    structure: Structure,
+    v: MaybeDeserialized<Option<B>>,
+    denormal_protection: MaybeDeserialized<DenormalProtection<F>>
+}
+
+/// Either the current format, or the legacy one from before the structure was selectable, which is just `w`.
+#[derive(Deserialize)]
+#[serde(untagged, bound = "")]
+enum AnyFields<F, W, B>
+{
+    Fields(Fields<F, W, B>),
+    Legacy(MaybeDeserialized<W>)
+}
+
+/// The state, structure and denormal protection is serialized, as far as the float type supports it. The coefficients are not, since they are recomputed
+/// from the parameter anyway.
+///
+/// The legacy format, which is just the state, is still accepted from human-readable formats, and gets the default structure and no denormal protection.
+/// Telling the two apart needs a self-describing format, so other formats only accept the current one.
 impl<F, W, B, A> Serialize for RtfInternals<F, W, B, A>
 where
     F: FilterFloat
@@ -42,7 +138,12 @@ where
     where
         S: Serializer
     {
-        self.w.maybe_serialize(serializer)
+        FieldsRef {
+            w: MaybeSerialized(&self.w),
+            structure: self.structure.structure(),
+            v: MaybeSerialized(&self.structure.v()),
+            denormal_protection: MaybeSerialized(&self.denormal_protection)
+        }.serialize(serializer)
     }
 }
 impl<'de, F, W, B, A> Deserialize<'de> for RtfInternals<F, W, B, A>
@@ -53,10 +154,40 @@ where
     where
         D: Deserializer<'de>
     {
-        Ok(Self {
-            w: W::deserialize_or_zeroed(deserializer)?,
-            ..Default::default()
-        })
+        let fields = if deserializer.is_human_readable()
+        {
+            AnyFields::<F, W, B>::deserialize(deserializer)?
+        }
+        else
+        {
+            AnyFields::Fields(Fields::deserialize(deserializer)?)
+        };
+        let Fields {w, structure, v, denormal_protection} = match fields
+        {
+            AnyFields::Fields(fields) => fields,
+            AnyFields::Legacy(w) => Fields {
+                w,
+                structure: Structure::default(),
+                v: MaybeDeserialized(None),
+                denormal_protection: MaybeDeserialized(None)
+            }
+        };
+
+        let mut internals = Self::new();
+        if let Some(w) = w.0
+        {
+            internals.w = w
+        }
+        internals.structure = StructureInternals::new(structure);
+        if let (Some(v_mut), Some(Some(v))) = (internals.structure.v_mut(), v.0)
+        {
+            *v_mut = v
+        }
+        if let Some(denormal_protection) = denormal_protection.0
+        {
+            internals.denormal_protection = denormal_protection
+        }
+        Ok(internals)
     }
 }
 
@@ -70,7 +201,34 @@ where
             w: unsafe {core::mem::zeroed()},
             b: unsafe {core::mem::zeroed()},
             a: unsafe {core::mem::zeroed()},
-            rate: None
+            rate: None,
+            structure: StructureInternals::DirectFormII,
+            denormal_protection: DenormalProtection::None,
+            seed: DenormalProtection::<F>::seed()
+        }
+    }
+
+    pub(crate) const fn structure(&self) -> Structure
+    {
+        self.structure.structure()
+    }
+
+    /// Switches to another structure, resetting the state, since it means different things for different structures.
+    pub(crate) fn set_structure(&mut self, structure: Structure)
+    {
+        if self.structure() != structure
+        {
+            self.structure = StructureInternals::new(structure);
+            self.w = unsafe {core::mem::zeroed()}
+        }
+    }
+
+    pub(crate) fn reset(&mut self)
+    {
+        self.w = unsafe {core::mem::zeroed()};
+        if let Some(v) = self.structure.v_mut()
+        {
+            *v = unsafe {core::mem::zeroed()}
         }
     }
 }
@@ -101,7 +259,73 @@ where
     let (internals, param) = rtf.get_internals_mut();
     if !param.is_unchanged_then_set() || internals.rate != Some(rate)
     {
-        (internals.b, internals.a) = Rtf::make_coeffs(param, rate)
+        (internals.b, internals.a) = Rtf::make_coeffs(param, rate);
+        if let StructureInternals::Lattice(lattice) = &mut internals.structure
+        {
+            *lattice = None
+        }
     }
     internals.rate = Some(rate)
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use crate::{conf::All, filters::iir::second::SecondOrderFilter, param::OmegaZeta, rtf::{DenormalProtection, Rtf, StaticRtf, Structure}};
+
+    #[test]
+    fn serde_round_trip()
+    {
+        const RATE: f64 = 44100.0;
+
+        for structure in [Structure::DirectFormI, Structure::DirectFormII, Structure::TransposedDirectFormII, Structure::Lattice]
+        {
+            let mut filter = SecondOrderFilter::<All>::new(OmegaZeta {omega: 1000.0*TAU, zeta: 0.1});
+            filter.set_structure(structure);
+            filter.set_denormal_protection(DenormalProtection::FlushToZero(1e-30));
+            for n in 0..16
+            {
+                filter.filter(RATE, (0.3*n as f64).sin());
+            }
+
+            let json = serde_json::to_string(&filter).unwrap();
+            let mut copy: SecondOrderFilter<All> = serde_json::from_str(&json).unwrap();
+            assert_eq!(copy.structure(), structure);
+            assert_eq!(copy.denormal_protection(), filter.denormal_protection());
+
+            // It carries on right where it left off
+            for n in 16..64
+            {
+                let x = (0.3*n as f64).sin();
+                assert_eq!(copy.filter(RATE, x), filter.filter(RATE, x), "{structure:?}, n = {n}");
+            }
+        }
+    }
+    #[test]
+    fn serde_legacy()
+    {
+        const RATE: f64 = 44100.0;
+
+        let mut filter = SecondOrderFilter::<All>::new(OmegaZeta {omega: 1000.0*TAU, zeta: 0.1});
+        for n in 0..16
+        {
+            filter.filter(RATE, (0.3*n as f64).sin());
+        }
+
+        // The legacy format only had the state
+        let mut json: serde_json::Value = serde_json::to_value(filter).unwrap();
+        let internals = &mut json["internals"];
+        *internals = internals["w"].take();
+        let mut copy: SecondOrderFilter<All> = serde_json::from_value(json).unwrap();
+        assert_eq!(copy.structure(), Structure::DirectFormII);
+        assert_eq!(copy.denormal_protection(), DenormalProtection::None);
+
+        for n in 16..64
+        {
+            let x = (0.3*n as f64).sin();
+            assert_eq!(copy.filter(RATE, x), filter.filter(RATE, x), "n = {n}");
+        }
+    }
 }
\ No newline at end of file
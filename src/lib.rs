@@ -10,6 +10,7 @@
 #![feature(specialization)]
 #![feature(const_trait_impl)]
 #![feature(const_option_ops)]
+#![feature(array_try_from_fn)]
 #![cfg_attr(test, feature(iter_array_chunks))]
 
 //! Ever needed a low pass filter for your VST? This crate has a wide selection of filters for real-time usage. It's designed to have as little runtime overhead as
@@ -24,6 +25,17 @@
 //! ![Block diagram represnetation of linear constant-coefficient difference equations (Figure 6.5, Alan V. Oppenheimer & Ronald W. Schafer - Discrete-Time Signal Processing)](https://github.com/user-attachments/assets/bd22e03f-b69c-4506-bbbd-baccf7a6c81d)
 //!
 //! (The figure is from: Alan V. Oppenheimer & Ronald W. Schafer - Discrete-Time Signal Processing)
+//!
+//! This is direct form II. For filters that are numerically sensitive, other realization structures (direct form I, transposed direct form II and a normalized
+//! lattice) can be selected per filter with [`StaticRtf::set_structure`](crate::rtf::StaticRtf::set_structure). See [`Structure`](crate::rtf::Structure).
+//!
+//! To keep the state from decaying into slow subnormal numbers when the input goes silent, a [`DenormalProtection`](crate::rtf::DenormalProtection)
+//! (flushing to zero, or injecting a tiny DC offset or noise) can be selected with [`Rtf::set_denormal_protection`](crate::rtf::Rtf::set_denormal_protection).
+//!
+//! Filters with resonances close to the Nyquist frequency can be run at 2, 4 or 8 times the sample rate with [`Oversampled`](crate::rtf::Oversampled), which
+//! up- and downsamples the signal with polyphase half-band filters. These, and general integer-factor [`Decimator`](crate::filters::fir::Decimator)s and
+//! [`Interpolator`](crate::filters::fir::Interpolator)s, are also available on their own in [`filters::fir`](crate::filters::fir).
+//!
 //! # Example
 //!
 //! ```rust
@@ -82,30 +94,74 @@
 //!
 //! # Available filters
 //!
-//! | Order | Filter                                                                                      | Parameterization                                                                                                                                                                                                 | Configuration                                                                                                                                                                                                                                                                                                                                                                     |
-//! |-------|---------------------------------------------------------------------------------------------|------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|-----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|
-//! | 1     | [`FirstOrderAllPassFilter`](crate::filters::iir::first::FirstOrderAllPassFilter)            | [`Tau`](crate::param::Tau)                                                                                                                                                                                       | [`AllPass`](crate::conf::AllPass)                                                                                                                                                                                                                                                                                                                                                 |
-//! | 1     | [`FirstOrderFilter`](crate::filters::iir::first::FirstOrderFilter)                          | [`Omega`](crate::param::Omega) [`RC`](crate::param::RC) [`LR`](crate::param::LR)                                                                                                                                 | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
-//! | 1     | [`FirstOrderLRFilter`](crate::filters::iir::first::FirstOrderLRFilter)                      | [`LR`](crate::param::LR)                                                                                                                                                                                         | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
-//! | 1     | [`FirstOrderRCFilter`](crate::filters::iir::first::FirstOrderRCFilter)                      | [`RC`](crate::param::RC)                                                                                                                                                                                         | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
-//! | 1     | [`PIFilter`](crate::filters::iir::first::PIFilter)                                          | [`PI`](crate::param::PI)                                                                                                                                                                                         | -                                                                                                                                                                                                                                                                                                                                                                                 |
-//! | 2     | [`PIDFilter`](crate::filters::iir::second::PIDFilter)                                       | [`PI`](crate::param::PI) [`PID`](crate::param::PID)                                                                                                                                                              | -                                                                                                                                                                                                                                                                                                                                                                                 |
-//! | 2     | [`SecondOrderButterworthFilter`](crate::filters::iir::second::SecondOrderButterworthFilter) | [`Omega`](crate::param::Omega)                                                                                                                                                                                   | [`LowPass`](crate::conf::LowPass) [`Peak`](crate::conf::Peak) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                 |
-//! | 2     | [`SecondOrderBesselFilter`](crate::filters::iir::second::SecondOrderBesselFilter)           | [`Omega`](crate::param::Omega)                                                                                                                                                                                   | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
-//! | 2     | [`SecondOrderChebyshev1Filter`](crate::filters::iir::second::SecondOrderChebyshev1Filter)   | [`Omega`](crate::param::Omega) [`OmegaEpsilon`](crate::param::OmegaEpsilon)                                                                                                                                      | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
-//! | 2     | [`SecondOrderChebyshev2Filter`](crate::filters::iir::second::SecondOrderChebyshev2Filter)   | [`Omega`](crate::param::Omega) [`OmegaEpsilon`](crate::param::OmegaEpsilon)                                                                                                                                      | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
-//! | 2     | [`SecondOrderEllipticFilter`](crate::filters::iir::second::SecondOrderEllipticFilter)       | [`Omega`](crate::param::Omega) [`OmegaEpsilon`](crate::param::OmegaEpsilon) [`OmegaEpsilonXi`](crate::param::OmegaEpsilonXi)                                                                                     | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
-//! | 2     | [`SecondOrderFilter`](crate::filters::iir::second::SecondOrderFilter)                       | [`Omega`](crate::param::Omega) [`OmegaZeta`](crate::param::OmegaZeta)                                                                                                                                            | [`LowPass`](crate::conf::LowPass) [`Peak`](crate::conf::Peak) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                 |
-//! | 2     | [`SecondOrderRCFilter`](crate::filters::iir::second::SecondOrderRCFilter)                   | [`RC`](crate::param::RC) [`RC2`](crate::param::RC2)                                                                                                                                                              | [`LowPass`](crate::conf::LowPass) <code>[BandPass](crate::conf::BandPass)<1></code> <code>[BandPass](crate::conf::BandPass)<2></code> [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                         |
-//! | 2     | [`SecondOrderRLCFilter`](crate::filters::iir::second::SecondOrderRLCFilter)                 | [`RC`](crate::param::RC) [`LR`](crate::param::LR) [`RLC`](crate::param::RLC)                                                                                                                                     | [`LowPass`](crate::conf::LowPass) [`BandStop`](crate::conf::BandStop) [`BandPass`](crate::conf::BandPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                     |
-//! | 2     | [`SecondOrderSallenKeyFilter`](crate::filters::iir::second::SecondOrderSallenKeyFilter)     | [`RC2SallenKey`](crate::param::RC2SallenKey) [`RC2GSallenKey`](crate::param::RC2GSallenKey)                                                                                                                      | [`LowPass`](crate::conf::LowPass) <code>[BandPass](crate::conf::BandPass)<1></code> <code>[BandPass](crate::conf::BandPass)<2></code> [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                         |
-//! | 3     | [`ThirdOrderButterworthFilter`](crate::filters::iir::third::ThirdOrderButterworthFilter)    | [`Omega`](crate::param::Omega)                                                                                                                                                                                   | [`LowPass`](crate::conf::LowPass) <code>[Peak](crate::conf::Peak)<1></code> <code>[Peak](crate::conf::Peak)<2></code> [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                         |
-//! | 3     | [`ThirdOrderFilter`](crate::filters::iir::third::ThirdOrderFilter)                          | [`Omega`](crate::param::Omega) [`OmegaZeta`](crate::param::OmegaZeta) [`Omega2Zeta`](crate::param::Omega2Zeta)                                                                                                   | [`LowPass`](crate::conf::LowPass) <code>[Peak](crate::conf::Peak)<1></code> <code>[Peak](crate::conf::Peak)<2></code> [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                         |
-//! | 3     | [`ThirdOrderSallenKeyFilter`](crate::filters::iir::third::ThirdOrderSallenKeyFilter)        | [`RC`](crate::param::RC) [`RC2SallenKey`](crate::param::RC2SallenKey) [`RC2GSallenKey`](crate::param::RC2GSallenKey) [`RC3SallenKey`](crate::param::RC3SallenKey) [`RC3GSallenKey`](crate::param::RC3GSallenKey) | [`LowPass`](crate::conf::LowPass) <code>[BandPass](crate::conf::BandPass)<1></code> <code>[BandPass](crate::conf::BandPass)<2></code> <code>[BandPass](crate::conf::BandPass)<3></code> <code>[BandPass](crate::conf::BandPass)<4></code> <code>[BandPass](crate::conf::BandPass)<5></code> <code>[BandPass](crate::conf::BandPass)<6></code> [`HighPass`](crate::conf::HighPass) |
-//! | 4     | [`WahFilter`](crate::filters::iir::fourth::WahFilter)                                       | [`CrybabyGCB95`](crate::param::CrybabyGCB95) [`VoxV847`](crate::param::VoxV847) [`ColorsoundWow`](crate::param::ColorsoundWow)                                                                                   | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | Order | Filter                                                                                      | Parameterization                                                                                                                                                                                                                                                          | Configuration                                                                                                                                                                                                                                                                                                                                                                     |
+//! |-------|---------------------------------------------------------------------------------------------|---------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|-----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|
+//! | 1     | [`FirstOrderAllPassFilter`](crate::filters::iir::first::FirstOrderAllPassFilter)            | [`Tau`](crate::param::Tau)                                                                                                                                                                                                                                                | [`AllPass`](crate::conf::AllPass)                                                                                                                                                                                                                                                                                                                                                 |
+//! | 1     | [`FirstOrderFilter`](crate::filters::iir::first::FirstOrderFilter)                          | [`Omega`](crate::param::Omega) [`RC`](crate::param::RC) [`LR`](crate::param::LR)                                                                                                                                                                                          | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
+//! | 1     | [`FirstOrderLRFilter`](crate::filters::iir::first::FirstOrderLRFilter)                      | [`LR`](crate::param::LR)                                                                                                                                                                                                                                                  | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
+//! | 1     | [`FirstOrderRCFilter`](crate::filters::iir::first::FirstOrderRCFilter)                      | [`RC`](crate::param::RC)                                                                                                                                                                                                                                                  | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
+//! | 1     | [`PIFilter`](crate::filters::iir::first::PIFilter)                                          | [`PI`](crate::param::PI) [`Discretized`](crate::param::Discretized)                                                                                                                                                                                                       | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | 1     | [`FirstOrderLeadLagFilter`](crate::filters::iir::first::FirstOrderLeadLagFilter)            | [`ZeroPole`](crate::param::ZeroPole) [`PhaseLead`](crate::param::PhaseLead)                                                                                                                                                                                               | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | 1     | [`DcBlockerFilter`](crate::filters::iir::first::DcBlockerFilter)                            | [`PoleRadius`](crate::param::PoleRadius) [`Cutoff`](crate::param::Cutoff)                                                                                                                                                                                                 | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | 2     | [`PIDFilter`](crate::filters::iir::second::PIDFilter)                                       | [`PI`](crate::param::PI) [`PID`](crate::param::PID) [`PIDF`](crate::param::PIDF) [`Discretized`](crate::param::Discretized)                                                                                                                                               | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | 2     | [`PIDController`](crate::filters::iir::second::PIDController)                               | [`PI`](crate::param::PI) [`PID`](crate::param::PID) [`PIDF`](crate::param::PIDF) [`Discretized`](crate::param::Discretized)                                                                                                                                               | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | 2     | [`SecondOrderLeadLagFilter`](crate::filters::iir::second::SecondOrderLeadLagFilter)         | [`ZeroPole`](crate::param::ZeroPole) [`PhaseLead`](crate::param::PhaseLead) [`LeadLag`](crate::param::LeadLag)                                                                                                                                                            | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | 2     | [`SecondOrderAllPassFilter`](crate::filters::iir::second::SecondOrderAllPassFilter)         | [`OmegaZeta`](crate::param::OmegaZeta)                                                                                                                                                                                                                                    | [`AllPass`](crate::conf::AllPass)                                                                                                                                                                                                                                                                                                                                                 |
+//! | 2     | [`SecondOrderButterworthFilter`](crate::filters::iir::second::SecondOrderButterworthFilter) | [`Omega`](crate::param::Omega)                                                                                                                                                                                                                                            | [`LowPass`](crate::conf::LowPass) [`Peak`](crate::conf::Peak) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                 |
+//! | 2     | [`SecondOrderBesselFilter`](crate::filters::iir::second::SecondOrderBesselFilter)           | [`Omega`](crate::param::Omega)                                                                                                                                                                                                                                            | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
+//! | 2     | [`SecondOrderChebyshev1Filter`](crate::filters::iir::second::SecondOrderChebyshev1Filter)   | [`Omega`](crate::param::Omega) [`OmegaEpsilon`](crate::param::OmegaEpsilon)                                                                                                                                                                                               | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
+//! | 2     | [`SecondOrderChebyshev2Filter`](crate::filters::iir::second::SecondOrderChebyshev2Filter)   | [`Omega`](crate::param::Omega) [`OmegaEpsilon`](crate::param::OmegaEpsilon)                                                                                                                                                                                               | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
+//! | 2     | [`SecondOrderEllipticFilter`](crate::filters::iir::second::SecondOrderEllipticFilter)       | [`Omega`](crate::param::Omega) [`OmegaEpsilon`](crate::param::OmegaEpsilon) [`OmegaEpsilonXi`](crate::param::OmegaEpsilonXi)                                                                                                                                              | [`LowPass`](crate::conf::LowPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                                             |
+//! | 2     | [`SecondOrderFilter`](crate::filters::iir::second::SecondOrderFilter)                       | [`Omega`](crate::param::Omega) [`OmegaZeta`](crate::param::OmegaZeta)                                                                                                                                                                                                     | [`LowPass`](crate::conf::LowPass) [`Peak`](crate::conf::Peak) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                                                                 |
+//! | 2     | [`SecondOrderRCFilter`](crate::filters::iir::second::SecondOrderRCFilter)                   | [`RC`](crate::param::RC) [`RC2`](crate::param::RC2)                                                                                                                                                                                                                       | [`LowPass`](crate::conf::LowPass) <code>[BandPass](crate::conf::BandPass)<1></code> <code>[BandPass](crate::conf::BandPass)<2></code> [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                         |
+//! | 2     | [`SecondOrderRLCFilter`](crate::filters::iir::second::SecondOrderRLCFilter)                 | [`RC`](crate::param::RC) [`LR`](crate::param::LR) [`RLC`](crate::param::RLC)                                                                                                                                                                                              | [`LowPass`](crate::conf::LowPass) [`BandStop`](crate::conf::BandStop) [`BandPass`](crate::conf::BandPass) [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                                     |
+//! | 2     | [`SecondOrderSallenKeyFilter`](crate::filters::iir::second::SecondOrderSallenKeyFilter)     | [`RC2SallenKey`](crate::param::RC2SallenKey) [`RC2GSallenKey`](crate::param::RC2GSallenKey)                                                                                                                                                                               | [`LowPass`](crate::conf::LowPass) <code>[BandPass](crate::conf::BandPass)<1></code> <code>[BandPass](crate::conf::BandPass)<2></code> [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                         |
+//! | 3     | [`ThirdOrderButterworthFilter`](crate::filters::iir::third::ThirdOrderButterworthFilter)    | [`Omega`](crate::param::Omega)                                                                                                                                                                                                                                            | [`LowPass`](crate::conf::LowPass) <code>[Peak](crate::conf::Peak)<1></code> <code>[Peak](crate::conf::Peak)<2></code> [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                         |
+//! | 3     | [`ThirdOrderFilter`](crate::filters::iir::third::ThirdOrderFilter)                          | [`Omega`](crate::param::Omega) [`OmegaZeta`](crate::param::OmegaZeta) [`Omega2Zeta`](crate::param::Omega2Zeta)                                                                                                                                                            | [`LowPass`](crate::conf::LowPass) <code>[Peak](crate::conf::Peak)<1></code> <code>[Peak](crate::conf::Peak)<2></code> [`HighPass`](crate::conf::HighPass)                                                                                                                                                                                                                         |
+//! | 3     | [`ThirdOrderSallenKeyFilter`](crate::filters::iir::third::ThirdOrderSallenKeyFilter)        | [`RC`](crate::param::RC) [`RC2SallenKey`](crate::param::RC2SallenKey) [`RC2GSallenKey`](crate::param::RC2GSallenKey) [`RC3SallenKey`](crate::param::RC3SallenKey) [`RC3GSallenKey`](crate::param::RC3GSallenKey)                                                          | [`LowPass`](crate::conf::LowPass) <code>[BandPass](crate::conf::BandPass)<1></code> <code>[BandPass](crate::conf::BandPass)<2></code> <code>[BandPass](crate::conf::BandPass)<3></code> <code>[BandPass](crate::conf::BandPass)<4></code> <code>[BandPass](crate::conf::BandPass)<5></code> <code>[BandPass](crate::conf::BandPass)<6></code> [`HighPass`](crate::conf::HighPass) |
+//! | 4     | [`WahFilter`](crate::filters::iir::fourth::WahFilter)                                       | [`CrybabyGCB95`](crate::param::CrybabyGCB95) [`VoxV847`](crate::param::VoxV847) [`ColorsoundWow`](crate::param::ColorsoundWow) [`ThomasOrganCrybaby`](crate::param::ThomasOrganCrybaby) [`Dunlop535Q`](crate::param::Dunlop535Q) [`WahCircuit`](crate::param::WahCircuit) | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | 4     | [`MoogLadderFilter`](crate::filters::iir::fourth::MoogLadderFilter)                         | [`OmegaResonance`](crate::param::OmegaResonance)                                                                                                                                                                                                                          | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | N     | [`ThiranFilter`](crate::filters::iir::nth::ThiranFilter)                                    | [`Delay`](crate::param::Delay)                                                                                                                                                                                                                                            | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | N     | [`StateSpaceFilter`](crate::filters::iir::nth::StateSpaceFilter)                            | [`StateSpace`](crate::param::StateSpace)                                                                                                                                                                                                                                  | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | N     | [`LagrangeFilter`](crate::filters::fir::LagrangeFilter)                                     | [`Delay`](crate::param::Delay)                                                                                                                                                                                                                                            | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | N     | [`PhaserFilter`](crate::filters::iir::nth::PhaserFilter)                                    | [`TauSpreadMix`](crate::param::TauSpreadMix)                                                                                                                                                                                                                              | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | N     | [`HilbertFilter`](crate::filters::iir::nth::HilbertFilter)                                  | [`Transition`](crate::param::Transition)                                                                                                                                                                                                                                  | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | N     | [`FirHilbertFilter`](crate::filters::fir::FirHilbertFilter)                                 | [`Transition`](crate::param::Transition)                                                                                                                                                                                                                                  | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | N     | [`FeedforwardCombFilter`](crate::filters::fir::FeedforwardCombFilter)                       | [`DelayGain`](crate::param::DelayGain)                                                                                                                                                                                                                                    | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | N     | [`FeedbackCombFilter`](crate::filters::iir::nth::FeedbackCombFilter)                        | [`DelayGain`](crate::param::DelayGain)                                                                                                                                                                                                                                    | -                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | N     | [`AllPassCombFilter`](crate::filters::iir::nth::AllPassCombFilter)                          | [`DelayGain`](crate::param::DelayGain)                                                                                                                                                                                                                                    | -                                                                                                                                                                                                                                                                                                                                                                                 |
 //!
 //! ...and more to come!
 //!
+//! # Modulation
+//!
+//! Filter parameters can be swept smoothly in real time with [`Change`](crate::change::Change). The [`modulation`](crate::modulation) module drives them automatically:
+//!
+//! | Module                                                            | Description                                                                                 |
+//! |-------------------------------------------------------------------|---------------------------------------------------------------------------------------------|
+//! | [`EnvelopeFollower`](crate::modulation::EnvelopeFollower)         | Follows the peak or RMS level of a signal, with attack and release times                    |
+//! | [`EnvelopeFilter`](crate::modulation::EnvelopeFilter)             | Sweeps a filter parameter with the envelope of its input (auto-wah, envelope filter)        |
+//!
+//! # Control
+//!
+//! [`PIDController`](crate::filters::iir::second::PIDController) runs a PI- or PID-controller with output limits and anti-windup. The
+//! [`control`](crate::control) module helps tuning and testing it against a plant:
+//!
+//! | Module                                                                                                | Description                                                                            |
+//! |-------------------------------------------------------------------------------------------------------|----------------------------------------------------------------------------------------|
+//! | [`ClosedLoop`](crate::control::ClosedLoop)                                                            | Runs a controller and a plant in a feedback loop, and measures its step response       |
+//! | [`series_margins`](crate::control::series_margins) [`series_nyquist`](crate::control::series_nyquist) | Gain and phase margins, crossover frequencies and Nyquist plot data of a loop          |
+//! | [`RelayAutoTuner`](crate::control::RelayAutoTuner)                                                    | Finds the ultimate gain and period of a loop with a relay, and proposes PID-parameters |
+//!
+//! # Smoothing
+//!
+//! The [`smoothing`](crate::smoothing) module cleans up jittery sensor data, like motion capture or the pots of a pedal:
+//!
+//! | Module                                                 | Description                                                                       |
+//! |--------------------------------------------------------|-----------------------------------------------------------------------------------|
+//! | [`OneEuroFilter`](crate::smoothing::OneEuroFilter)     | A low-pass filter whose cutoff rises with the speed of the signal (the 1€ filter) |
+//! | [`SlewRateLimiter`](crate::smoothing::SlewRateLimiter) | Limits how fast the signal can rise and fall                                      |
+//!
 //! # Adding your own filter
 //!
 //! To make your own filter, you need to derive an expression for the Z-domain filter coefficients.
@@ -115,6 +171,11 @@
 //! Once you have an S-plane representation, you can use the bilinear transform to find the Z-domain transfer function. The numerator and denominator of that expression
 //! are your coefficients, and can be plugged directly into this library.
 //!
+//! High-order transfer functions are sensitive to rounding errors when run directly. They can be split into second-order sections with
+//! [`factor_sos`](crate::rtf::factor_sos), and run as SOS stages instead. Factoring finds the roots numerically, which costs far more than computing the
+//! coefficients. Calling it from `make_coeffs`, like [`WahFilter`](crate::filters::iir::fourth::WahFilter) does, pays for that every time the coefficients
+//! change. Zeros you already know, like the ones at DC and Nyquist from the bilinear transform, are cheaper to place yourself.
+//!
 //! ## Implementation
 //!
 //! Once you have your coefficients, you can easily implement your own filter by using the macro [`def_rtf!`](crate::def_rtf).
@@ -236,9 +297,12 @@
 moddef::moddef!(
     pub mod {
         change,
+        control,
         filters,
         internals,
+        modulation,
         param,
+        smoothing,
         conf,
         rtf
     },
@@ -403,7 +467,7 @@ macro_rules! winternals {
         $crate::winternals!($rtf as StaticRtf)
     };
     ($rtf:ty as $($trait:tt)+) => {
-        $crate::winternals!($rtf where <$rtf as $($trait)+>::F as $($trait)+)
+        $crate::winternals!($rtf where <$rtf as $crate::rtf::RtfBase>::F as $($trait)+)
     };
     ($rtf:ty where $f:ty as $($trait:tt)+) => {
         (
@@ -425,13 +489,16 @@ macro_rules! binternals {
         $crate::binternals!($rtf as StaticRtf)
     };
     ($rtf:ty as $($trait:tt)+) => {
-        $crate::binternals!($rtf where <$rtf as $($trait)+>::F as $($trait)+)
+        $crate::binternals!($rtf where <$rtf as $crate::rtf::RtfBase>::F, $crate::rtf::RtfBase as $($trait)+)
     };
     ($rtf:ty where $f:ty as $($trait:tt)+) => {
+        $crate::binternals!($rtf where $f, $($trait)+ as $($trait)+)
+    };
+    ($rtf:ty where $f:ty, $outputs:path as $($trait:tt)+) => {
         (
             $crate::array_minus1!(<$rtf as $($trait)+>::SosStages<<$rtf as $($trait)+>::SosBufs<[$f; 3]>>),
             $crate::array_min1!(<$rtf as $($trait)+>::SosStages<<$rtf as $($trait)+>::OutputBufs<[$f; 3]>>),
-            <$rtf as $($trait)+>::Outputs<$crate::array_plus1!(<$rtf as $($trait)+>::Order::<$f>)>
+            <$rtf as $outputs>::Outputs<$crate::array_plus1!(<$rtf as $($trait)+>::Order::<$f>)>
         )
     }
 }
@@ -447,7 +514,7 @@ macro_rules! ainternals {
         $crate::ainternals!($rtf as StaticRtf)
     };
     ($rtf:ty as $($trait:tt)+) => {
-        $crate::ainternals!($rtf where <$rtf as $($trait)+>::F as $($trait)+)
+        $crate::ainternals!($rtf where <$rtf as $crate::rtf::RtfBase>::F as $($trait)+)
     };
     ($rtf:ty where $f:ty as $($trait:tt)+) => {
         (
@@ -574,12 +641,15 @@ macro_rules! rtfinternals {
         $crate::rtfinternals!($rtf as StaticRtf)
     };
     ($rtf:ty as $trait:path) => {
-        $crate::rtfinternals!($rtf where <$rtf as $trait>::F as $trait)
+        $crate::rtfinternals!($rtf where <$rtf as $crate::rtf::RtfBase>::F, $crate::rtf::RtfBase as $trait)
     };
     ($rtf:ty where $f:ty as $trait:path) => {
+        $crate::rtfinternals!($rtf where $f, $trait as $trait)
+    };
+    ($rtf:ty where $f:ty, $outputs:path as $trait:path) => {
         $crate::internals::RtfInternals<$f,
             $crate::winternals!($rtf where $f as $trait),
-            $crate::binternals!($rtf where $f as $trait),
+            $crate::binternals!($rtf where $f, $outputs as $trait),
             <$rtf as $trait>::IsIir<$crate::ainternals!($rtf where $f as $trait)>
         >
     };
@@ -800,8 +870,7 @@ macro_rules! def_rtf {
         }
 
         $(
-            #[allow(unused_braces)]
-            impl<P> $crate::rtf::StaticRtf for $name<$conf, <P as $crate::param::FilterParam>::F, P>
+            impl<P> $crate::rtf::RtfBase for $name<$conf, <P as $crate::param::FilterParam>::F, P>
             where
                 $conf: $conf_trait_alias<Conf = $conf>,
                 P: $param_trait_alias<$conf, Conf = $conf> + $crate::param::FilterParam,
@@ -811,13 +880,7 @@ macro_rules! def_rtf {
                 type F = <P as $crate::param::FilterParam>::F;
                 type Param = P;
                 type Conf = $conf;
-
-                type IsIir<U> = <$conf as private::_Helper>::IsIir<U>;
                 type Outputs<U> = <$conf as private::_Helper>::Outputs<U>;
-                type Order<U> = <$conf as private::_Helper>::Order<U>;
-                type OutputBufs<U> = <$conf as private::_Helper>::OutputBufs<U>;
-                type SosBufs<U> = <$conf as private::_Helper>::SosBufs<U>;
-                type SosStages<U> = <$conf as private::_Helper>::SosStages<U>;
 
                 fn from_param(param: Self::Param) -> Self
                 {
@@ -839,6 +902,21 @@ macro_rules! def_rtf {
                 {
                     self.param.into_value()
                 }
+            }
+
+            #[allow(unused_braces)]
+            impl<P> $crate::rtf::StaticRtf for $name<$conf, <P as $crate::param::FilterParam>::F, P>
+            where
+                $conf: $conf_trait_alias<Conf = $conf>,
+                P: $param_trait_alias<$conf, Conf = $conf> + $crate::param::FilterParam,
+                Internals<P::F, $conf>: Copy + core::fmt::Debug + Default + PartialEq,
+                $($($where_c)+)?
+            {
+                type IsIir<U> = <$conf as private::_Helper>::IsIir<U>;
+                type Order<U> = <$conf as private::_Helper>::Order<U>;
+                type OutputBufs<U> = <$conf as private::_Helper>::OutputBufs<U>;
+                type SosBufs<U> = <$conf as private::_Helper>::SosBufs<U>;
+                type SosStages<U> = <$conf as private::_Helper>::SosStages<U>;
 
                 #[allow(clippy::type_complexity)]
                 fn get_internals(&self) -> (&$crate::internals::RtfInternalsFor<Self>, &$crate::param::Param<P>)
@@ -923,8 +1001,7 @@ macro_rules! def_rtf {
             }
         }
 
-        #[allow(unused_braces)]
-        impl<P> $crate::rtf::StaticRtf for $name<<P as $crate::param::FilterParam>::F, P>
+        impl<P> $crate::rtf::RtfBase for $name<<P as $crate::param::FilterParam>::F, P>
         where
             P: $param_trait,
             $($($where)+)?
@@ -932,13 +1009,7 @@ macro_rules! def_rtf {
             type Conf = $crate::conf::All;
             type Param = P;
             type F = <P as $crate::param::FilterParam>::F;
-
-            type IsIir<U> = [U; $is_iir as usize];
             type Outputs<U> = [U; $outputs];
-            type OutputBufs<U> = [U; $output_bufs];
-            type SosBufs<U> = [U; $sos_bufs];
-            type SosStages<U> = [U; $sos_stages];
-            type Order<U> = [U; $order];
 
             fn from_param(param: Self::Param) -> Self
             {
@@ -956,6 +1027,19 @@ macro_rules! def_rtf {
             {
                 self.param.into_value()
             }
+        }
+
+        #[allow(unused_braces)]
+        impl<P> $crate::rtf::StaticRtf for $name<<P as $crate::param::FilterParam>::F, P>
+        where
+            P: $param_trait,
+            $($($where)+)?
+        {
+            type IsIir<U> = [U; $is_iir as usize];
+            type OutputBufs<U> = [U; $output_bufs];
+            type SosBufs<U> = [U; $sos_bufs];
+            type SosStages<U> = [U; $sos_stages];
+            type Order<U> = [U; $order];
 
             #[allow(clippy::type_complexity)]
                 fn get_internals(&self) -> (&$crate::internals::RtfInternalsFor<Self>, &$crate::param::Param<P>)
@@ -1039,8 +1123,7 @@ macro_rules! def_rtf {
             }
         }
 
-        #[allow(unused_braces)]
-        impl<F> $crate::rtf::StaticRtf for $name<F>
+        impl<F> $crate::rtf::RtfBase for $name<F>
         where
             F: $crate::param::FilterFloat,
             $param<F>: $crate::param::FilterParam<F = F>,
@@ -1049,13 +1132,7 @@ macro_rules! def_rtf {
             type Conf = $crate::conf::All;
             type Param = $param<F>;
             type F = F;
-
-            type IsIir<U> = [U; $is_iir as usize];
             type Outputs<U> = [U; $outputs];
-            type OutputBufs<U> = [U; $output_bufs];
-            type SosBufs<U> = [U; $sos_bufs];
-            type SosStages<U> = [U; $sos_stages];
-            type Order<U> = [U; $order];
 
             fn from_param(param: Self::Param) -> Self
             {
@@ -1073,6 +1150,20 @@ macro_rules! def_rtf {
             {
                 self.param.into_value()
             }
+        }
+
+        #[allow(unused_braces)]
+        impl<F> $crate::rtf::StaticRtf for $name<F>
+        where
+            F: $crate::param::FilterFloat,
+            $param<F>: $crate::param::FilterParam<F = F>,
+            $($($where)+)?
+        {
+            type IsIir<U> = [U; $is_iir as usize];
+            type OutputBufs<U> = [U; $output_bufs];
+            type SosBufs<U> = [U; $sos_bufs];
+            type SosStages<U> = [U; $sos_stages];
+            type Order<U> = [U; $order];
 
             #[allow(clippy::type_complexity)]
                 fn get_internals(&self) -> (&$crate::internals::RtfInternalsFor<Self>, &$crate::param::Param<$param<F>>)
@@ -1153,8 +1244,7 @@ macro_rules! def_rtf {
             }
         }
 
-        #[allow(unused_braces)]
-        impl<F> $crate::rtf::StaticRtf for $name<F>
+        impl<F> $crate::rtf::RtfBase for $name<F>
         where
             F: FilterFloat,
             $($($where)+)?
@@ -1162,13 +1252,7 @@ macro_rules! def_rtf {
             type Conf = $crate::conf::All;
             type Param = ();
             type F = F;
-
-            type IsIir<U> = [U; $is_iir as usize];
             type Outputs<U> = [U; $outputs];
-            type OutputBufs<U> = [U; $output_bufs];
-            type SosBufs<U> = [U; $sos_bufs];
-            type SosStages<U> = [U; $sos_stages];
-            type Order<U> = [U; $order];
 
             fn from_param((): Self::Param) -> Self
             {
@@ -1186,6 +1270,19 @@ macro_rules! def_rtf {
             {
                 self.param.into_value()
             }
+        }
+
+        #[allow(unused_braces)]
+        impl<F> $crate::rtf::StaticRtf for $name<F>
+        where
+            F: FilterFloat,
+            $($($where)+)?
+        {
+            type IsIir<U> = [U; $is_iir as usize];
+            type OutputBufs<U> = [U; $output_bufs];
+            type SosBufs<U> = [U; $sos_bufs];
+            type SosStages<U> = [U; $sos_stages];
+            type Order<U> = [U; $order];
 
             #[allow(clippy::type_complexity)]
                 fn get_internals(&self) -> (&$crate::internals::RtfInternalsFor<Self>, &$crate::param::Param<()>)
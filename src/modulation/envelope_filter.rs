@@ -0,0 +1,162 @@
+use num::{Float, One, Zero};
+
+use crate::{change::Change, modulation::EnvelopeFollower, rtf::Rtf};
+
+/// Sweeps the parameter of a filter with the envelope of its input, such as the pedal position of a wah (an auto-wah), or the cutoff of a low-pass filter.
+///
+/// The envelope is scaled by `sensitivity`, clamped to `[0, 1]`, and used to interpolate between the targets `from` (silence) and `to` (full envelope). The
+/// filter parameter is then moved towards that target with [`Change`], by the fraction `change`, so that the sweep stays smooth even if the envelope jumps.
+///
+/// The envelope is followed every sample, but the parameter is only moved once every `interval` samples, as every move makes the filter recalculate its
+/// coefficients.
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     modulation::{Detector, EnvelopeFilter, EnvelopeFollower},
+///     param::{AttackRelease, CrybabyGCB95, X},
+///     filters::iir::fourth::WahFilter
+/// };
+///
+/// // An auto-wah, opening the pedal as you play harder
+/// let mut auto_wah = EnvelopeFilter::new(
+///     WahFilter::new(CrybabyGCB95 {x: 0.0}),
+///     EnvelopeFollower::new(AttackRelease {attack: 0.005, release: 0.1}, Detector::Peak),
+///     X {x: 0.0},
+///     X {x: 1.0}
+/// );
+/// auto_wah.sensitivity = 4.0;
+///
+/// let [y] = auto_wah.filter(44100.0, 0.2);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnvelopeFilter<R, T>
+where
+    R: Rtf
+{
+    pub filter: R,
+    pub envelope: EnvelopeFollower<R::F>,
+    pub from: T,
+    pub to: T,
+    pub sensitivity: R::F,
+    pub change: R::F,
+    pub interval: usize,
+    countdown: usize
+}
+
+impl<R, T> EnvelopeFilter<R, T>
+where
+    R: Rtf,
+    R::Param: Change<T, F = R::F>,
+    T: Change<F = R::F> + Copy
+{
+    /// The number of samples between each update of the filter parameter, unless otherwise specified.
+    pub const DEFAULT_INTERVAL: usize = 16;
+
+    /// Creates a new envelope-driven filter, with a sensitivity of `1`, no smoothing beyond the envelope follower itself, and an interval of
+    /// [`DEFAULT_INTERVAL`](Self::DEFAULT_INTERVAL) samples.
+    pub fn new(filter: R, envelope: EnvelopeFollower<R::F>, from: T, to: T) -> Self
+    {
+        Self {
+            filter,
+            envelope,
+            from,
+            to,
+            sensitivity: R::F::one(),
+            change: R::F::one(),
+            interval: Self::DEFAULT_INTERVAL,
+            countdown: 0
+        }
+    }
+
+    /// Returns the target the filter parameter is currently moving towards.
+    pub fn target(&self) -> T
+    {
+        let amount = (self.envelope.envelope()*self.sensitivity)
+            .max(R::F::zero())
+            .min(R::F::one());
+        let mut target = self.from;
+        target.change(self.to, amount);
+        target
+    }
+
+    /// Follows the envelope of a single sample, updates the filter parameter if the interval has passed, and then feeds the sample through the filter.
+    pub fn filter(&mut self, rate: R::F, x: R::F) -> R::Outputs<R::F>
+    {
+        self.envelope.filter(rate, x);
+        if self.countdown == 0
+        {
+            let target = self.target();
+            self.filter.get_param_mut().change(target, self.change);
+            self.countdown = self.interval;
+        }
+        self.countdown = self.countdown.saturating_sub(1);
+        self.filter.filter(rate, x)
+    }
+
+    pub fn reset(&mut self)
+    {
+        self.envelope.reset();
+        self.filter.reset();
+        self.countdown = 0
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use crate::{conf::LowPass, filters::iir::{fourth::WahFilter, second::SecondOrderFilter}, modulation::{Detector, EnvelopeFollower}, param::{AttackRelease, CrybabyGCB95, Omega, OmegaZeta, X}, rtf::RtfBase};
+
+    use super::EnvelopeFilter;
+
+    #[test]
+    fn sweep()
+    {
+        const RATE: f64 = 44100.0;
+
+        let mut auto_wah = EnvelopeFilter::new(
+            WahFilter::new(CrybabyGCB95 {x: 0.0}),
+            EnvelopeFollower::new(AttackRelease {attack: 0.005, release: 0.05}, Detector::Peak),
+            X {x: 0.1},
+            X {x: 0.9}
+        );
+        auto_wah.sensitivity = 2.0;
+        auto_wah.change = 0.01;
+
+        // Loud notes open the pedal smoothly, and it falls back in silence
+        let mut x_prev = auto_wah.filter.get_param().x;
+        for n in 0..(RATE as usize)/4
+        {
+            auto_wah.filter(RATE, (440.0*TAU*n as f64/RATE).sin());
+            let x = auto_wah.filter.get_param().x;
+            assert!((x - x_prev).abs() <= 0.01);
+            // The pedal only moves once per interval
+            assert!(n % auto_wah.interval == 0 || x == x_prev);
+            x_prev = x;
+        }
+        assert!((x_prev - 0.9).abs() < 0.01);
+        for _ in 0..(RATE as usize)
+        {
+            auto_wah.filter(RATE, 0.0);
+        }
+        assert!((auto_wah.filter.get_param().x - 0.1).abs() < 1e-3);
+
+        // Also works on the cutoff of any filter parameterized by omega
+        let mut auto_filter = EnvelopeFilter::new(
+            SecondOrderFilter::<LowPass>::new(OmegaZeta {omega: 200.0*TAU, zeta: 0.2}),
+            EnvelopeFollower::new(AttackRelease {attack: 0.005, release: 0.05}, Detector::Rms),
+            Omega::<f64> {omega: 200.0*TAU},
+            Omega::<f64> {omega: 5000.0*TAU}
+        );
+        for n in 0..(RATE as usize)/4
+        {
+            auto_filter.filter(RATE, (440.0*TAU*n as f64/RATE).sin());
+        }
+        assert!(auto_filter.filter.get_param().omega > 2000.0*TAU);
+    }
+}
\ No newline at end of file
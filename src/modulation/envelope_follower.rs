@@ -0,0 +1,150 @@
+use crate::param::{AttackRelease, FilterFloat, Param};
+
+/// How an [`EnvelopeFollower`] measures the level of the signal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Detector
+{
+    /// Follows the absolute value of the signal. Reacts quickly to transients.
+    #[default]
+    Peak,
+    /// Follows the mean square of the signal, and returns its root. Closer to the perceived loudness.
+    Rms
+}
+
+/// Follows the level of a signal, with separate time constants for rising (attack) and falling (release) levels.
+///
+/// The level is smoothed by a one-pole low-pass filter, whose time constant switches between the attack and the release time depending on whether the
+/// level is above or below the current envelope. After a step, the envelope reaches `1 - 1/e` (about 63%) of the way in one time constant.
+///
+/// # Example
+///
+/// ```rust
+/// use real_time_fir_iir_filters::{
+///     modulation::{Detector, EnvelopeFollower},
+///     param::AttackRelease
+/// };
+///
+/// // 10 ms attack, 200 ms release
+/// let mut follower = EnvelopeFollower::new(AttackRelease {attack: 0.01, release: 0.2}, Detector::Rms);
+///
+/// let envelope = follower.filter(44100.0, 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnvelopeFollower<F = f64>
+where
+    F: FilterFloat
+{
+    pub param: Param<AttackRelease<F>>,
+    pub detector: Detector,
+    #[serde(skip)]
+    coeffs: Option<(F, [F; 2])>,
+    #[serde(skip)]
+    state: F
+}
+
+impl<F> EnvelopeFollower<F>
+where
+    F: FilterFloat
+{
+    pub fn new(param: AttackRelease<F>, detector: Detector) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            detector,
+            coeffs: None,
+            state: F::zero()
+        }
+    }
+
+    /// Feeds a single sample through the follower, and returns the current envelope.
+    pub fn filter(&mut self, rate: F, x: F) -> F
+    {
+        let [attack, release] = match self.coeffs
+        {
+            Some((r, coeffs)) if r == rate && self.param.is_unchanged() => coeffs,
+            _ => {
+                self.param.set_unchanged();
+                let AttackRelease {attack, release} = *self.param;
+                let pole = |tau: F| if tau > F::zero() {(-(tau*rate).recip()).exp()} else {F::zero()};
+                let coeffs = [pole(attack), pole(release)];
+                self.coeffs = Some((rate, coeffs));
+                coeffs
+            }
+        };
+
+        let level = match self.detector
+        {
+            Detector::Peak => x.abs(),
+            Detector::Rms => x*x
+        };
+        let pole = if level > self.state {attack} else {release};
+        self.state = level + pole*(self.state - level);
+        if self.state.is_subnormal()
+        {
+            self.state = F::zero()
+        }
+
+        self.envelope()
+    }
+
+    /// Returns the current envelope, without feeding it a new sample.
+    pub fn envelope(&self) -> F
+    {
+        match self.detector
+        {
+            Detector::Peak => self.state,
+            Detector::Rms => self.state.sqrt()
+        }
+    }
+
+    pub fn reset(&mut self)
+    {
+        self.state = F::zero()
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::E;
+
+    use crate::param::AttackRelease;
+
+    use super::{Detector, EnvelopeFollower};
+
+    #[test]
+    fn attack_release()
+    {
+        const RATE: f64 = 44100.0;
+        const ATTACK: f64 = 0.01;
+        const RELEASE: f64 = 0.1;
+
+        let mut follower = EnvelopeFollower::new(AttackRelease {attack: ATTACK, release: RELEASE}, Detector::Peak);
+
+        let mut envelope = 0.0;
+        for _ in 0..(ATTACK*RATE) as usize
+        {
+            envelope = follower.filter(RATE, -1.0);
+        }
+        assert!((envelope - (1.0 - 1.0/E)).abs() < 1e-3);
+
+        for _ in 0..(RATE as usize)
+        {
+            follower.filter(RATE, 1.0);
+        }
+        for _ in 0..(RELEASE*RATE) as usize
+        {
+            envelope = follower.filter(RATE, 0.0);
+        }
+        assert!((envelope - 1.0/E).abs() < 1e-3);
+
+        // The RMS of a sine is 1/√2
+        let mut follower = EnvelopeFollower::new(AttackRelease {attack: 0.1, release: 0.1}, Detector::Rms);
+        for n in 0..(RATE as usize)
+        {
+            envelope = follower.filter(RATE, (0.05*n as f64).sin());
+        }
+        assert!((envelope - core::f64::consts::FRAC_1_SQRT_2).abs() < 1e-2);
+    }
+}
\ No newline at end of file
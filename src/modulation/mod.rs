@@ -0,0 +1,6 @@
+moddef::moddef!(
+    flat(pub) mod {
+        envelope_filter for cfg(feature = "envelope_follower"),
+        envelope_follower for cfg(feature = "envelope_follower")
+    }
+);
\ No newline at end of file
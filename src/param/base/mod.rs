@@ -9,6 +9,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -26,6 +27,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -51,6 +53,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -73,6 +76,24 @@ moddef::moddef!(
             feature = "second_order_sallen_key",
             feature = "third_order_sallen_key"
         )),
+        second_order_all_pass for cfg(any(
+            feature = "second_order_elliptic",
+            feature = "second_order_chebyshev1",
+            feature = "second_order_chebyshev2",
+            feature = "second_order_bessel",
+            feature = "second_order_butterworth",
+            feature = "third_order_butterworth",
+            feature = "first_order",
+            feature = "second_order",
+            feature = "second_order_all_pass",
+            feature = "third_order",
+            feature = "first_order_lr",
+            feature = "first_order_rc",
+            feature = "second_order_rc",
+            feature = "second_order_rlc",
+            feature = "second_order_sallen_key",
+            feature = "third_order_sallen_key"
+        )),
         second_order for cfg(any(
             feature = "second_order_elliptic",
             feature = "second_order_chebyshev1",
@@ -82,6 +103,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -106,6 +128,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
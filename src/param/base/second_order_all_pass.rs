@@ -0,0 +1,9 @@
+use crate::{conf::{All, Conf}, param::FilterParam};
+
+pub trait SecondOrderAllPassFilterParamBase<C>: FilterParam
+where
+    C: Conf
+{
+    /// If in doubt, set this to [Self]
+    type ImplBase: SecondOrderAllPassFilterParamBase<All, ImplBase = Self::ImplBase>;
+}
\ No newline at end of file
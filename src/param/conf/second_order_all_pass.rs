@@ -0,0 +1,77 @@
+use crate::{conf::{All, AllPass, Conf}, util::{self, ObviousArray}};
+
+pub trait SecondOrderAllPassFilterConf: Conf
+{
+    type Conf: private::SecondOrderAllPassFilterConfFinal<Self>;
+
+    type Outputs<U>: ObviousArray<Elem = U>;
+}
+
+impl SecondOrderAllPassFilterConf for AllPass
+{
+    type Conf = All;
+
+    type Outputs<U> = [U; 1];
+}
+
+macro impl_composite_conf {
+    ($conf:ty: $($more:ty),+) => {
+        impl SecondOrderAllPassFilterConf for $conf
+        {
+            type Conf = $conf;
+
+            type Outputs<U> = util::array_sum!($(<$more as SecondOrderAllPassFilterConf>::Outputs::<U>),+);
+        }
+    },
+    ($conf:ty: $($more:ty),+ => $($actual:ty),+) => {
+        impl SecondOrderAllPassFilterConf for $conf
+        {
+            type Conf = all!($($actual),+);
+
+            type Outputs<U> = util::array_sum!($(<$more as SecondOrderAllPassFilterConf>::Outputs::<U>),+);
+        }
+    },
+    ($conf0:ty $(,$more:ty)* $(=> $($actual:ty),+)?) => {
+        impl_composite_conf!(
+            all!(
+                $conf0,
+                $($more),*
+            ): $conf0, $($more),* $(=> $($actual),+)?
+        );
+    }
+}
+
+impl_composite_conf!(All: AllPass);
+
+mod private
+{
+    use crate::param::{OmegaZeta, SecondOrderAllPassFilterParam};
+
+    use super::SecondOrderAllPassFilterConf;
+
+    pub trait SecondOrderAllPassFilterConfFinal<C>: SecondOrderAllPassFilterConf<
+        Conf = C::Conf
+    >
+    where
+        C: SecondOrderAllPassFilterConf
+    {
+
+    }
+    impl<
+        CC,
+        C
+    > SecondOrderAllPassFilterConfFinal<C> for CC
+    where
+        CC: SecondOrderAllPassFilterConf<
+            Conf = CC,
+            Outputs<()> = C::Outputs<()>
+        >,
+        C: SecondOrderAllPassFilterConf<
+            Conf = CC
+        >,
+        OmegaZeta<f64>: SecondOrderAllPassFilterParam<CC, Conf = CC>,
+        OmegaZeta<f32>: SecondOrderAllPassFilterParam<CC, Conf = CC>
+    {
+
+    }
+}
\ No newline at end of file
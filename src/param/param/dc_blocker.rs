@@ -0,0 +1,6 @@
+use crate::param::{FilterParam, PoleRadius};
+
+pub trait DcBlockerFilterParam: FilterParam
+{
+    fn pole_radius(&self, rate: Self::F) -> PoleRadius<Self::F>;
+}
\ No newline at end of file
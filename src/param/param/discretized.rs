@@ -0,0 +1,10 @@
+use crate::param::{Discretization, FilterParam};
+
+/// Parameters of a controller that can choose how it's mapped to discrete time.
+pub trait DiscretizedParam: FilterParam
+{
+    fn discretization(&self) -> Discretization
+    {
+        Discretization::Tustin
+    }
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+use crate::param::{FilterParam, ZeroPole};
+
+pub trait FirstOrderLeadLagFilterParam: FilterParam
+{
+    fn zero_pole(&self) -> ZeroPole<Self::F>;
+}
\ No newline at end of file
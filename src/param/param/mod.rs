@@ -10,6 +10,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -27,6 +28,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -44,6 +46,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -52,6 +55,7 @@ moddef::moddef!(
             feature = "second_order_sallen_key",
             feature = "third_order_sallen_key"
         )),
+        dc_blocker for cfg(feature = "dc_blocker"),
         elliptic for cfg(any(
             feature = "second_order_elliptic",
             feature = "second_order_chebyshev1",
@@ -61,6 +65,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -86,6 +91,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -94,6 +100,7 @@ moddef::moddef!(
             feature = "second_order_sallen_key",
             feature = "third_order_sallen_key"
         )),
+        first_order_lead_lag for cfg(feature = "lead_lag"),
         first_order_lr for cfg(feature = "first_order_lr"),
         first_order_rc for cfg(any(
             feature = "first_order_rc",
@@ -102,11 +109,34 @@ moddef::moddef!(
             feature = "second_order_sallen_key",
             feature = "third_order_sallen_key"
         )),
+        discretized for cfg(any(
+            feature = "pi",
+            feature = "pid"
+        )),
         pi for cfg(any(
             feature = "pi",
             feature = "pid"
         )),
         pid for cfg(feature = "pid"),
+        pidf for cfg(feature = "pid"),
+        second_order_all_pass for cfg(any(
+            feature = "second_order_elliptic",
+            feature = "second_order_chebyshev1",
+            feature = "second_order_chebyshev2",
+            feature = "second_order_bessel",
+            feature = "second_order_butterworth",
+            feature = "third_order_butterworth",
+            feature = "first_order",
+            feature = "second_order",
+            feature = "second_order_all_pass",
+            feature = "third_order",
+            feature = "first_order_lr",
+            feature = "first_order_rc",
+            feature = "second_order_rc",
+            feature = "second_order_rlc",
+            feature = "second_order_sallen_key",
+            feature = "third_order_sallen_key"
+        )),
         second_order for cfg(any(
             feature = "second_order_elliptic",
             feature = "second_order_chebyshev1",
@@ -116,6 +146,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -124,6 +155,7 @@ moddef::moddef!(
             feature = "second_order_sallen_key",
             feature = "third_order_sallen_key"
         )),
+        second_order_lead_lag for cfg(feature = "lead_lag"),
         second_order_rc for cfg(any(
             feature = "first_order_rc",
             feature = "second_order_rc",
@@ -154,6 +186,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
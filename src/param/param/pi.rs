@@ -1,6 +1,6 @@
-use crate::param::{FilterParam, PI};
+use crate::param::{DiscretizedParam, PI};
 
-pub trait PIFilterParam: FilterParam
+pub trait PIFilterParam: DiscretizedParam
 {
     fn pi(&self) -> PI<Self::F>;
 }
\ No newline at end of file
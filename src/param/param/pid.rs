@@ -1,10 +1,10 @@
 use num::Zero;
 
-use crate::param::{FilterParam, PID, PI};
+use crate::param::{DiscretizedParam, PID, PI};
 
 use super::PIFilterParam;
 
-pub trait PIDFilterParam: FilterParam
+pub trait PIDFilterParam: DiscretizedParam
 {
     fn pid(&self) -> PID<Self::F>;
 }
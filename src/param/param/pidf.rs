@@ -0,0 +1,25 @@
+use num::Zero;
+
+use crate::param::{DiscretizedParam, PIDF, PID};
+
+use super::PIDFilterParam;
+
+pub trait PIDFFilterParam: DiscretizedParam
+{
+    fn pidf(&self) -> PIDF<Self::F>;
+}
+impl<P> PIDFFilterParam for P
+where
+    P: PIDFilterParam
+{
+    fn pidf(&self) -> PIDF<Self::F>
+    {
+        let PID {p, i, d} = self.pid();
+        PIDF {
+            p,
+            i,
+            d,
+            tf: Zero::zero()
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+use crate::{conf::Conf, param::{OmegaZeta, SecondOrderAllPassFilterConf, SecondOrderAllPassFilterParamBase}, util::same::Same};
+
+pub trait SecondOrderAllPassFilterParam<
+    C,
+    ImplBase = <Self as SecondOrderAllPassFilterParamBase<C>>::ImplBase
+>: SecondOrderAllPassFilterParamBase<C, ImplBase: Same<ImplBase>>
+where
+    C: Conf
+{
+    type Conf: SecondOrderAllPassFilterConf;
+
+    fn omega_zeta(&self) -> OmegaZeta<Self::F>;
+}
\ No newline at end of file
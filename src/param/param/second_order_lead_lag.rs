@@ -0,0 +1,25 @@
+use num::One;
+
+use crate::param::{FilterParam, ZeroPole};
+
+use super::FirstOrderLeadLagFilterParam;
+
+pub trait SecondOrderLeadLagFilterParam: FilterParam
+{
+    fn zero_pole2(&self) -> [ZeroPole<Self::F>; 2];
+}
+impl<P> SecondOrderLeadLagFilterParam for P
+where
+    P: FirstOrderLeadLagFilterParam
+{
+    fn zero_pole2(&self) -> [ZeroPole<Self::F>; 2]
+    {
+        // The second section cancels its own zero and pole
+        let one = ZeroPole {
+            zero: One::one(),
+            pole: One::one(),
+            gain: One::one()
+        };
+        [self.zero_pole(), one]
+    }
+}
\ No newline at end of file
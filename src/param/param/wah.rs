@@ -1,4 +1,6 @@
-use crate::param::{FilterParam, GUITAR_R_I, MPSA18_BETA, MPSA18_V_F, V_T, X};
+use crate::{f, param::{FilterParam, GUITAR_R_I, MPSA18_BETA, MPSA18_V_F, V_T, WahCircuit, X}};
+
+use num::One;
 
 pub trait WahFilterParam: FilterParam
 {
@@ -43,7 +45,50 @@ pub trait WahFilterParam: FilterParam
     /// Inductor
     const L: f64 = 540e-3; // L1
 
+    /// Whether [`circuit`](Self::circuit) can differ from the associated constants by more than the pedal position.
+    /// 
+    /// If not, the parts of the calculation that only depend on the components are done at compile time.
+    const RUNTIME_CIRCUIT: bool = false;
+
     /// Position of wah potentiometer from 0.0 to 1.0
     /// Values out of range may give strange results
     fn x(&self) -> X<Self::F>;
+
+    /// The full component table of the circuit, at the current pedal position.
+    /// 
+    /// By default, this is made from the associated constants. Models with more controls than the pedal override it, and set [`RUNTIME_CIRCUIT`](Self::RUNTIME_CIRCUIT).
+    fn circuit(&self) -> WahCircuit<Self::F>
+    {
+        circuit_from_consts(self)
+    }
+}
+
+/// Builds the component table from the associated constants of the param.
+pub(crate) fn circuit_from_consts<P>(param: &P) -> WahCircuit<P::F>
+where
+    P: WahFilterParam + ?Sized
+{
+    let X {x} = param.x();
+    WahCircuit {
+        x,
+        boost: P::F::one(),
+        r_i: f!(P::R_I; P::F),
+        r_s: f!(P::R_S; P::F),
+        r_p: f!(P::R_P; P::F),
+        r_c1: f!(P::R_C1; P::F),
+        r_e1: f!(P::R_E1; P::F),
+        r_c2: f!(P::R_C2; P::F),
+        r_e2: f!(P::R_E2; P::F),
+        r_j: f!(P::R_J; P::F),
+        r_g: f!(P::R_G; P::F),
+        r_pot: f!(P::R_POT; P::F),
+        v_cc: f!(P::V_CC; P::F),
+        v_f: f!(P::V_F; P::F),
+        v_t: f!(P::V_T; P::F),
+        beta: f!(P::BETA; P::F),
+        c_g: f!(P::C_G; P::F),
+        c_i: f!(P::C_I; P::F),
+        c_f: f!(P::C_F; P::F),
+        l: f!(P::L; P::F)
+    }
 }
\ No newline at end of file
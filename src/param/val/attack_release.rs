@@ -0,0 +1,32 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam}};
+
+/// Attack and release time constants, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct AttackRelease<F>
+where
+    F: FilterFloat
+{
+    pub attack: F,
+    pub release: F
+}
+impl<F> Change for AttackRelease<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.attack.change(to.attack, change);
+        self.release.change(to.release, change);
+    }
+}
+impl<F> FilterParam for AttackRelease<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 1;
+
+    type F = F;
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use crate::{change::Change, param::{DcBlockerFilterParam, FilterFloat, FilterParam, PoleRadius}};
+
+/// A cutoff frequency, in Hz.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct Cutoff<F>
+where
+    F: FilterFloat
+{
+    pub cutoff: F
+}
+impl<F> Change for Cutoff<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.cutoff.change(to.cutoff, change);
+    }
+}
+impl<F> FilterParam for Cutoff<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 1;
+
+    type F = F;
+}
+impl<F> DcBlockerFilterParam for Cutoff<F>
+where
+    F: FilterFloat
+{
+    fn pole_radius(&self, rate: Self::F) -> PoleRadius<Self::F>
+    {
+        PoleRadius {
+            radius: (-F::TAU()*self.cutoff/rate).exp()
+        }
+    }
+}
\ No newline at end of file
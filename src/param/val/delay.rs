@@ -0,0 +1,30 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam}};
+
+/// A delay, in samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct Delay<F>
+where
+    F: FilterFloat
+{
+    pub delay: F
+}
+impl<F> Change for Delay<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.delay.change(to.delay, change);
+    }
+}
+impl<F> FilterParam for Delay<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 0;
+
+    type F = F;
+}
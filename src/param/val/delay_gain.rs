@@ -0,0 +1,32 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam}};
+
+/// A delay in samples, and the gain applied to the delayed signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct DelayGain<F>
+where
+    F: FilterFloat
+{
+    pub delay: F,
+    pub gain: F
+}
+impl<F> Change for DelayGain<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.delay.change(to.delay, change);
+        self.gain.change(to.gain, change);
+    }
+}
+impl<F> FilterParam for DelayGain<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 0;
+
+    type F = F;
+}
\ No newline at end of file
@@ -0,0 +1,147 @@
+use crate::{change::Change, param::{DiscretizedParam, FilterFloat, FilterParam, PIFilterParam, PI}};
+#[cfg(feature = "pid")]
+use crate::param::{PIDFFilterParam, PIDFilterParam, PIDF, PID};
+
+/// The method used to map a continuous-time controller to discrete time.
+/// 
+/// With the sample period `T`, the Laplace variable `s` is replaced by:
+/// 
+/// <pre>
+/// Forward Euler:  s = (z - 1)/T
+/// Backward Euler: s = (z - 1)/(zT)
+/// Tustin:         s = 2(z - 1)/((z + 1)T)
+/// </pre>
+/// 
+/// With [`ForwardEuler`](Discretization::ForwardEuler), the filtered derivative term becomes
+///
+/// <pre>
+/// D(1 - z⁻¹)/(T<sub>f</sub> + (T - T<sub>f</sub>)z⁻¹)
+/// </pre>
+///
+/// which is causal as long as `Tf > 0`, and stable as long as `T < 2Tf`. An unfiltered derivative (`Tf = 0`) has no causal forward difference, so then
+/// the derivative term falls back to backward differences, while the integral term still uses forward differences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Discretization
+{
+    ForwardEuler,
+    BackwardEuler,
+    #[default]
+    Tustin
+}
+impl Discretization
+{
+    /// Returns `(k, α, β)` such that `1/s = (α + βz⁻¹)/(k(1 - z⁻¹))` for the integral term.
+    pub(crate) fn integral<F>(self, rate: F) -> (F, F, F)
+    where
+        F: FilterFloat
+    {
+        match self
+        {
+            Self::ForwardEuler => (rate, F::zero(), F::one()),
+            Self::BackwardEuler => (rate, F::one(), F::zero()),
+            Self::Tustin => (rate + rate, F::one(), F::one())
+        }
+    }
+
+    /// Returns `(k, α, β)` such that `s = k(1 - z⁻¹)/(α + βz⁻¹)` for the derivative term, filtered with the time constant `tf`.
+    #[cfg(feature = "pid")]
+    pub(crate) fn derivative<F>(self, rate: F, tf: F) -> (F, F, F)
+    where
+        F: FilterFloat
+    {
+        match self
+        {
+            Self::ForwardEuler if tf > F::zero() => self.integral(rate),
+            Self::ForwardEuler | Self::BackwardEuler => Self::BackwardEuler.integral(rate),
+            Self::Tustin => self.integral(rate)
+        }
+    }
+}
+
+/// Parameters of a PI- or PID-controller, discretized with a chosen [`Discretization`] instead of the default bilinear transform.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// #![feature(generic_const_exprs)]
+/// 
+/// use real_time_fir_iir_filters::{
+///     param::{Discretization, Discretized, PI},
+///     rtf::Rtf,
+///     filters::iir::first::PIFilter
+/// };
+/// 
+/// let mut filter = PIFilter::new(Discretized {
+///     param: PI::<f64> {p: 1.0, i: 10.0},
+///     discretization: Discretization::BackwardEuler
+/// });
+/// 
+/// // u[n] = u[n - 1] + P(e[n] - e[n - 1]) + IT e[n]
+/// let [u] = filter.filter(100.0, 1.0);
+/// assert!((u - 1.1).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct Discretized<P>
+{
+    pub param: P,
+    pub discretization: Discretization
+}
+impl<P> Change for Discretized<P>
+where
+    P: Change
+{
+    type F = P::F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.param.change(to.param, change);
+        self.discretization = to.discretization;
+    }
+}
+impl<P> FilterParam for Discretized<P>
+where
+    P: FilterParam
+{
+    const ORDER: usize = P::ORDER;
+
+    type F = P::F;
+}
+impl<P> DiscretizedParam for Discretized<P>
+where
+    P: FilterParam
+{
+    fn discretization(&self) -> Discretization
+    {
+        self.discretization
+    }
+}
+impl<P> PIFilterParam for Discretized<P>
+where
+    P: PIFilterParam
+{
+    fn pi(&self) -> PI<Self::F>
+    {
+        self.param.pi()
+    }
+}
+#[cfg(feature = "pid")]
+impl<F> PIDFilterParam for Discretized<PID<F>>
+where
+    F: FilterFloat
+{
+    fn pid(&self) -> PID<Self::F>
+    {
+        self.param.pid()
+    }
+}
+#[cfg(feature = "pid")]
+impl<F> PIDFFilterParam for Discretized<PIDF<F>>
+where
+    F: FilterFloat
+{
+    fn pidf(&self) -> PIDF<Self::F>
+    {
+        self.param.pidf()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam, SecondOrderLeadLagFilterParam, ZeroPole}};
+
+/// A lead-compensator in series with a lag-compensator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct LeadLag<F>
+where
+    F: FilterFloat
+{
+    pub lead: ZeroPole<F>,
+    pub lag: ZeroPole<F>
+}
+impl<F> Change for LeadLag<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.lead.change(to.lead, change);
+        self.lag.change(to.lag, change);
+    }
+}
+impl<F> FilterParam for LeadLag<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 2;
+
+    type F = F;
+}
+impl<F> SecondOrderLeadLagFilterParam for LeadLag<F>
+where
+    F: FilterFloat
+{
+    fn zero_pole2(&self) -> [ZeroPole<Self::F>; 2]
+    {
+        [self.lead, self.lag]
+    }
+}
\ No newline at end of file
@@ -1,6 +1,19 @@
 moddef::moddef!(
     flat(pub) mod {
+        attack_release for cfg(feature = "envelope_follower"),
+        cutoff for cfg(feature = "dc_blocker"),
+        delay_gain for cfg(feature = "comb"),
+        delay for cfg(any(
+            feature = "thiran",
+            feature = "lagrange"
+        )),
+        discretized for cfg(any(
+            feature = "pi",
+            feature = "pid"
+        )),
+        lead_lag for cfg(feature = "lead_lag"),
         lr for cfg(feature = "first_order_lr"),
+        omega_resonance for cfg(feature = "moog_ladder"),
         omega_epsilon_xi for cfg(any(
             feature = "second_order_elliptic",
             feature = "second_order_chebyshev1",
@@ -10,6 +23,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -27,6 +41,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -44,6 +59,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -61,6 +77,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -78,6 +95,7 @@ moddef::moddef!(
             feature = "third_order_butterworth",
             feature = "first_order",
             feature = "second_order",
+            feature = "second_order_all_pass",
             feature = "third_order",
             feature = "first_order_lr",
             feature = "first_order_rc",
@@ -86,11 +104,15 @@ moddef::moddef!(
             feature = "second_order_sallen_key",
             feature = "third_order_sallen_key"
         )),
+        phase_lead for cfg(feature = "lead_lag"),
+        one_euro for cfg(feature = "smoothing"),
         pi for cfg(any(
             feature = "pi",
             feature = "pid"
         )),
         pid for cfg(feature = "pid"),
+        pidf for cfg(feature = "pid"),
+        pole_radius for cfg(feature = "dc_blocker"),
         rc for cfg(any(
             feature = "first_order_rc",
             feature = "second_order_rc",
@@ -140,6 +162,9 @@ moddef::moddef!(
             feature = "second_order_sallen_key",
             feature = "third_order_sallen_key"
         )),
+        slew_rate for cfg(feature = "smoothing"),
+        state_space for cfg(feature = "state_space"),
+        tau_spread_mix for cfg(feature = "phaser"),
         tau for cfg(any(
             feature = "first_order_all_pass",
             feature = "first_order_rc",
@@ -148,7 +173,10 @@ moddef::moddef!(
             feature = "second_order_sallen_key",
             feature = "third_order_sallen_key"
         )),
+        transition for cfg(feature = "hilbert"),
+        wah_circuit for cfg(feature = "wah"),
         wah for cfg(feature = "wah"),
-        x for cfg(feature = "wah")
+        x for cfg(feature = "wah"),
+        zero_pole for cfg(feature = "lead_lag")
     }
 );
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::{change::Change, param::{ChebyshevFilterParam, ChebyshevFilterParamBase, EllipticFilterConf, EllipticFilterParamBase, FilterFloat, FilterParam}};
+use crate::{change::Change, param::{ChebyshevFilterParam, ChebyshevFilterParamBase, EllipticFilterConf, EllipticFilterParamBase, FilterFloat, FilterParam, Omega}};
 
 pub type OmegaEpsilonDyn<F, const TYPE: bool> = OmegaEpsilon<F, TYPE>;
 pub type OmegaEpsilonFirstOrder<F, const TYPE: bool> = OmegaEpsilon<F, TYPE, 1>;
@@ -38,6 +38,17 @@ where
         self.epsilon.change(to.epsilon, change);
     }
 }
+impl<F, const TYPE: bool, const ORDER: usize, const O: usize> Change<Omega<F, O>> for OmegaEpsilon<F, TYPE, ORDER>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Omega<F, O>, change: Self::F)
+    {
+        self.omega.change(to.omega, change);
+    }
+}
 impl<F, const TYPE: bool, const ORDER: usize> FilterParam for OmegaEpsilon<F, TYPE, ORDER>
 where
     F: FilterFloat
@@ -1,4 +1,4 @@
-use crate::{change::Change, param::{EllipticFilterConf, EllipticFilterParam, EllipticFilterParamBase, FilterFloat, FilterParam}};
+use crate::{change::Change, param::{EllipticFilterConf, EllipticFilterParam, EllipticFilterParamBase, FilterFloat, FilterParam, Omega}};
 
 pub type OmegaEpsilonXiDyn<F> = OmegaEpsilonXi<F>;
 pub type OmegaEpsilonXiFirstOrder<F> = OmegaEpsilonXi<F, 1>;
@@ -28,6 +28,17 @@ where
         self.xi.change(to.xi, change);
     }
 }
+impl<F, const ORDER: usize, const O: usize> Change<Omega<F, O>> for OmegaEpsilonXi<F, ORDER>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Omega<F, O>, change: Self::F)
+    {
+        self.omega.change(to.omega, change);
+    }
+}
 impl<F, const ORDER: usize> FilterParam for OmegaEpsilonXi<F, ORDER>
 where
     F: FilterFloat
@@ -0,0 +1,32 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam}};
+
+/// A cutoff frequency in radians per second, and a resonance where `1` is the threshold of self-oscillation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct OmegaResonance<F>
+where
+    F: FilterFloat
+{
+    pub omega: F,
+    pub resonance: F
+}
+impl<F> Change for OmegaResonance<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.omega.change(to.omega, change);
+        self.resonance.change(to.resonance, change);
+    }
+}
+impl<F> FilterParam for OmegaResonance<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 4;
+
+    type F = F;
+}
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::{change::Change, param::{FilterFloat, FilterParam, SecondOrderFilterConf, SecondOrderFilterParam, SecondOrderFilterParamBase}};
+use crate::{change::Change, param::{FilterFloat, FilterParam, Omega, SecondOrderAllPassFilterConf, SecondOrderAllPassFilterParam, SecondOrderAllPassFilterParamBase, SecondOrderFilterConf, SecondOrderFilterParam, SecondOrderFilterParamBase}};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(deny_unknown_fields)] 
@@ -21,6 +21,17 @@ where
         self.zeta.change(to.zeta, change);
     }
 }
+impl<F, const O: usize> Change<Omega<F, O>> for OmegaZeta<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Omega<F, O>, change: Self::F)
+    {
+        self.omega.change(to.omega, change);
+    }
+}
 impl<F> FilterParam for OmegaZeta<F>
 where
     F: FilterFloat
@@ -43,6 +54,25 @@ where
 {
     type Conf = C;
 
+    fn omega_zeta(&self) -> OmegaZeta<Self::F>
+    {
+        *self
+    }
+}
+impl<F, C> SecondOrderAllPassFilterParamBase<C> for OmegaZeta<F>
+where
+    F: FilterFloat,
+    C: SecondOrderAllPassFilterConf
+{
+    type ImplBase = Self;
+}
+impl<F, C> SecondOrderAllPassFilterParam<C> for OmegaZeta<F>
+where
+    F: FilterFloat,
+    C: SecondOrderAllPassFilterConf
+{
+    type Conf = C;
+
     fn omega_zeta(&self) -> OmegaZeta<Self::F>
     {
         *self
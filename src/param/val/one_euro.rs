@@ -0,0 +1,39 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam}};
+
+/// Parameters of a 1€ filter: the lowest cutoff frequency, how fast the cutoff rises with the speed of the signal, and the cutoff frequency of the speed
+/// estimate, all in radians per second.
+///
+/// <pre>
+/// ω = ω<sub>min</sub> + β|dx/dt|
+/// </pre>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct OneEuro<F>
+where
+    F: FilterFloat
+{
+    pub min_omega: F,
+    pub beta: F,
+    pub derivative_omega: F
+}
+impl<F> Change for OneEuro<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.min_omega.change(to.min_omega, change);
+        self.beta.change(to.beta, change);
+        self.derivative_omega.change(to.derivative_omega, change);
+    }
+}
+impl<F> FilterParam for OneEuro<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 1;
+
+    type F = F;
+}
\ No newline at end of file
@@ -0,0 +1,61 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam, FirstOrderLeadLagFilterParam, ZeroPole}};
+
+/// A lead-compensator given by its maximum phase lead (in radians) at a centre frequency (in rad/s), and its gain at that frequency.
+/// 
+/// The zero and the pole are placed symmetrically around the centre frequency:
+/// 
+/// <pre>
+///     1 - sin φ
+/// α = ---------, ω₀ = ω√α, ωₚ = ω/√α
+///     1 + sin φ
+/// </pre>
+/// 
+/// A negative phase gives a lag-compensator instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct PhaseLead<F>
+where
+    F: FilterFloat
+{
+    pub omega: F,
+    pub phase: F,
+    pub gain: F
+}
+impl<F> Change for PhaseLead<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.omega.change(to.omega, change);
+        self.phase.change(to.phase, change);
+        self.gain.change(to.gain, change);
+    }
+}
+impl<F> FilterParam for PhaseLead<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 1;
+
+    type F = F;
+}
+impl<F> FirstOrderLeadLagFilterParam for PhaseLead<F>
+where
+    F: FilterFloat
+{
+    fn zero_pole(&self) -> ZeroPole<Self::F>
+    {
+        let Self {omega, phase, gain} = *self;
+        let sin_phase = phase.sin();
+        let sqrt_alpha = ((F::one() - sin_phase)/(F::one() + sin_phase)).sqrt();
+        ZeroPole {
+            zero: omega*sqrt_alpha,
+            pole: omega/sqrt_alpha,
+            // |H(jω)| = K/√α
+            gain: gain*sqrt_alpha
+        }
+    }
+}
\ No newline at end of file
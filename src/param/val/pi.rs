@@ -1,6 +1,6 @@
 use num::Float;
 
-use crate::{change::Change, param::{FilterFloat, FilterParam, PIFilterParam}};
+use crate::{change::Change, param::{DiscretizedParam, FilterFloat, FilterParam, PIFilterParam}};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(deny_unknown_fields)] 
@@ -30,6 +30,12 @@ where
     const ORDER: usize = 1;
 
     type F = F;
+}
+impl<F> DiscretizedParam for PI<F>
+where
+    F: FilterFloat
+{
+
 }
 impl<F> PIFilterParam for PI<F>
 where
@@ -1,6 +1,6 @@
 use num::Float;
 
-use crate::{change::Change, param::{FilterFloat, FilterParam, PIDFilterParam}};
+use crate::{change::Change, param::{DiscretizedParam, FilterFloat, FilterParam, PIDFilterParam}};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(deny_unknown_fields)] 
@@ -32,6 +32,12 @@ where
     const ORDER: usize = 2;
 
     type F = F;
+}
+impl<F> DiscretizedParam for PID<F>
+where
+    F: FilterFloat
+{
+
 }
 impl<F> PIDFilterParam for PID<F>
 where
@@ -0,0 +1,110 @@
+use num::Float;
+
+use crate::{change::Change, param::{DiscretizedParam, FilterFloat, FilterParam, PIDFFilterParam, PID}};
+
+/// PID-parameters with a first-order low-pass on the derivative term.
+/// 
+/// <pre>
+///            I      Ds
+/// H(s) = P + - + --------
+///            s   1 + sTf
+/// </pre>
+/// 
+/// `tf` is the time constant of the derivative filter. With `tf = 0` this is equivalent to [`PID`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct PIDF<F>
+where
+    F: Float
+{
+    pub p: F,
+    pub i: F,
+    pub d: F,
+    pub tf: F
+}
+impl<F> PIDF<F>
+where
+    F: Float
+{
+    /// Creates PIDF-parameters from a derivative filter coefficient `n`, where `tf = 1/n`.
+    pub fn with_n(pid: PID<F>, n: F) -> Self
+    {
+        let PID {p, i, d} = pid;
+        Self {
+            p,
+            i,
+            d,
+            tf: F::one()/n
+        }
+    }
+
+    /// The derivative filter coefficient `n = 1/tf`.
+    pub fn n(&self) -> F
+    {
+        F::one()/self.tf
+    }
+}
+impl<F> From<PID<F>> for PIDF<F>
+where
+    F: Float
+{
+    fn from(PID {p, i, d}: PID<F>) -> Self
+    {
+        Self {
+            p,
+            i,
+            d,
+            tf: F::zero()
+        }
+    }
+}
+impl<F> Change for PIDF<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.p.change(to.p, change);
+        self.i.change(to.i, change);
+        self.d.change(to.d, change);
+        self.tf.change(to.tf, change);
+    }
+}
+impl<F> Change<PID<F>> for PIDF<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: PID<F>, change: Self::F)
+    {
+        self.p.change(to.p, change);
+        self.i.change(to.i, change);
+        self.d.change(to.d, change);
+    }
+}
+impl<F> FilterParam for PIDF<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 2;
+
+    type F = F;
+}
+impl<F> DiscretizedParam for PIDF<F>
+where
+    F: FilterFloat
+{
+
+}
+impl<F> PIDFFilterParam for PIDF<F>
+where
+    F: FilterFloat
+{
+    fn pidf(&self) -> PIDF<Self::F>
+    {
+        *self
+    }
+}
\ No newline at end of file
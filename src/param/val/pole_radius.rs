@@ -0,0 +1,39 @@
+use crate::{change::Change, param::{DcBlockerFilterParam, FilterFloat, FilterParam}};
+
+/// The radius of a pole in the z-plane, from zero to one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct PoleRadius<F>
+where
+    F: FilterFloat
+{
+    pub radius: F
+}
+impl<F> Change for PoleRadius<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.radius.change(to.radius, change);
+    }
+}
+impl<F> FilterParam for PoleRadius<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 1;
+
+    type F = F;
+}
+impl<F> DcBlockerFilterParam for PoleRadius<F>
+where
+    F: FilterFloat
+{
+    fn pole_radius(&self, _rate: Self::F) -> PoleRadius<Self::F>
+    {
+        *self
+    }
+}
\ No newline at end of file
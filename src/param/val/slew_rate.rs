@@ -0,0 +1,32 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam}};
+
+/// The largest rising and falling rates of change, in units per second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct SlewRate<F>
+where
+    F: FilterFloat
+{
+    pub rise: F,
+    pub fall: F
+}
+impl<F> Change for SlewRate<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.rise.change(to.rise, change);
+        self.fall.change(to.fall, change);
+    }
+}
+impl<F> FilterParam for SlewRate<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 0;
+
+    type F = F;
+}
\ No newline at end of file
@@ -0,0 +1,325 @@
+use array_trait::AsSlice;
+use num::Complex;
+
+use crate::{change::Change, f, param::{FilterFloat, FilterParam}, rtf::StaticRtf, serde::{array, SerdeArray}};
+
+/// Which canonical state-space realization to make from a transfer function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum CanonicalForm
+{
+    /// The input only enters the first state, and the denominator coefficients make up the first row of `A`.
+    ///
+    /// <pre>
+    ///     ⎡-a₁ -a₂ ... -aₙ⎤     ⎡1⎤
+    ///     ⎢ 1   0  ...  0 ⎥     ⎢0⎥
+    /// A = ⎢ 0   1  ...  0 ⎥ B = ⎢⋮⎥
+    ///     ⎣ 0   0  ... 1 0⎦     ⎣0⎦
+    ///
+    /// C = [b₁ - a₁b₀  b₂ - a₂b₀  ...  bₙ - aₙb₀], D = b₀
+    /// </pre>
+    #[default]
+    Controllable,
+    /// The transpose of the controllable canonical form. The output only reads the first state, and the denominator coefficients make up the first
+    /// column of `A`.
+    ///
+    /// <pre>
+    ///     ⎡-a₁ 1 ... 0⎤     ⎡b₁ - a₁b₀⎤
+    ///     ⎢-a₂ 0 ... 0⎥     ⎢b₂ - a₂b₀⎥
+    /// A = ⎢ ⋮  ⋮  ⋱  1⎥ B = ⎢    ⋮    ⎥
+    ///     ⎣-aₙ 0 ... 0⎦     ⎣bₙ - aₙb₀⎦
+    ///
+    /// C = [1 0 ... 0], D = b₀
+    /// </pre>
+    Observable
+}
+
+/// A discrete-time state-space model with `N` states, one input and one output.
+///
+/// <pre>
+/// x[n + 1] = Ax[n] + Bu[n]
+///     y[n] = Cx[n] + Du[n]
+///
+/// H(z) = C(zI - A)⁻¹B + D
+/// </pre>
+///
+/// Any transfer function of order `N` can be realized in one of the [canonical forms](CanonicalForm), and any model can be turned back into a transfer
+/// function with [`transfer_function`](StateSpace::transfer_function).
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(from = "Fields<N, F>", into = "Fields<N, F>", bound(serialize = "F: serde::Serialize", deserialize = "F: serde::Deserialize<'de>"))]
+pub struct StateSpace<const N: usize, F = f64>
+where
+    F: FilterFloat
+{
+    pub a: [[F; N]; N],
+    pub b: [F; N],
+    pub c: [F; N],
+    pub d: F
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "StateSpace", deny_unknown_fields)]
+struct Fields<const N: usize, F>
+{
+    #[serde(with = "array")]
+    a: [SerdeArray<F, N>; N],
+    #[serde(with = "array")]
+    b: [F; N],
+    #[serde(with = "array")]
+    c: [F; N],
+    d: F
+}
+impl<const N: usize, F> From<StateSpace<N, F>> for Fields<N, F>
+where
+    F: FilterFloat
+{
+    fn from(StateSpace {a, b, c, d}: StateSpace<N, F>) -> Self
+    {
+        Self {
+            a: a.map(SerdeArray),
+            b,
+            c,
+            d
+        }
+    }
+}
+impl<const N: usize, F> From<Fields<N, F>> for StateSpace<N, F>
+where
+    F: FilterFloat
+{
+    fn from(Fields {a, b, c, d}: Fields<N, F>) -> Self
+    {
+        Self {
+            a: a.map(|a| a.0),
+            b,
+            c,
+            d
+        }
+    }
+}
+impl<const N: usize, F> Change for StateSpace<N, F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        for (a, to) in self.a.iter_mut()
+            .flatten()
+            .zip(to.a.into_iter().flatten())
+        {
+            a.change(to, change);
+        }
+        for (b, to) in self.b.iter_mut()
+            .zip(to.b)
+        {
+            b.change(to, change);
+        }
+        for (c, to) in self.c.iter_mut()
+            .zip(to.c)
+        {
+            c.change(to, change);
+        }
+        self.d.change(to.d, change);
+    }
+}
+impl<const N: usize, F> FilterParam for StateSpace<N, F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = N;
+
+    type F = F;
+}
+
+impl<const N: usize, F> StateSpace<N, F>
+where
+    F: FilterFloat
+{
+    /// Realizes the transfer function with numerator `b` and denominator `a`, both in ascending powers of `z⁻¹`, in the given canonical form.
+    ///
+    /// The coefficients are normalized by `a₀`.
+    pub fn canonical(form: CanonicalForm, b: [F; N + 1], a: [F; N + 1]) -> Self
+    where
+        [(); N + 1]:
+    {
+        Self::canonical_from_slices(form, &b, &a)
+    }
+
+    /// Realizes the transfer function in [controllable canonical form](CanonicalForm::Controllable).
+    pub fn controllable_canonical(b: [F; N + 1], a: [F; N + 1]) -> Self
+    where
+        [(); N + 1]:
+    {
+        Self::canonical(CanonicalForm::Controllable, b, a)
+    }
+
+    /// Realizes the transfer function in [observable canonical form](CanonicalForm::Observable).
+    pub fn observable_canonical(b: [F; N + 1], a: [F; N + 1]) -> Self
+    where
+        [(); N + 1]:
+    {
+        Self::canonical(CanonicalForm::Observable, b, a)
+    }
+
+    /// Missing coefficients at the end of `a` are taken as zero.
+    fn canonical_from_slices(form: CanonicalForm, b: &[F], a: &[F]) -> Self
+    {
+        let a0 = a[0];
+        let b0 = b[0]/a0;
+        let p: [F; N] = core::array::from_fn(|k| -a.get(k + 1).copied().unwrap_or_else(F::zero)/a0);
+        let q: [F; N] = core::array::from_fn(|k| b[k + 1]/a0 + p[k]*b0);
+
+        let mut e = [F::zero(); N];
+        if let Some(e) = e.first_mut()
+        {
+            *e = F::one()
+        }
+
+        match form
+        {
+            CanonicalForm::Controllable => Self {
+                a: core::array::from_fn(|i| core::array::from_fn(|j| if i == 0 {p[j]} else if i == j + 1 {F::one()} else {F::zero()})),
+                b: e,
+                c: q,
+                d: b0
+            },
+            CanonicalForm::Observable => Self {
+                a: core::array::from_fn(|i| core::array::from_fn(|j| if j == 0 {p[i]} else if j == i + 1 {F::one()} else {F::zero()})),
+                b: q,
+                c: e,
+                d: b0
+            }
+        }
+    }
+
+    /// Realizes the transfer function of a filter, as cached in its internals for the given sample rate, in the given canonical form.
+    ///
+    /// Returns `None` if the filter isn't of order `N`, or if it's split into second-order sections. If the filter has several outputs, `output` picks
+    /// which one to realize.
+    pub fn from_rtf<R>(rtf: &mut R, rate: F, output: usize, form: CanonicalForm) -> Option<Self>
+    where
+        R: StaticRtf<F = F>
+    {
+        rtf.update_internals(rate);
+        let (internals, _) = rtf.get_internals();
+
+        if !internals.b.1.as_slice().is_empty()
+        {
+            return None
+        }
+        let outputs = internals.b.2.as_slice();
+        let b = outputs.get(output)?
+            .as_slice();
+        if b.len() != N + 1
+        {
+            return None
+        }
+        // An FIR filter has no denominator
+        let a = match internals.a.as_slice().first()
+        {
+            Some((_, a)) => {
+                let a = a.as_slice();
+                a[output/(outputs.len()/a.len())].as_slice()
+            },
+            None => &[F::one()]
+        };
+        Some(Self::canonical_from_slices(form, b, a))
+    }
+
+    /// Returns the numerator and denominator of the transfer function, in ascending powers of `z⁻¹`, with `a₀ = 1`.
+    ///
+    /// The denominator is the characteristic polynomial of `A`, found together with the adjugate of `zI - A` by the Faddeev–LeVerrier algorithm.
+    pub fn transfer_function(&self) -> ([F; N + 1], [F; N + 1])
+    where
+        [(); N + 1]:
+    {
+        let mut b = [F::zero(); N + 1];
+        let mut a = [F::zero(); N + 1];
+        b[0] = self.d;
+        a[0] = F::one();
+
+        let mut m = [[F::zero(); N]; N];
+        for k in 1..=N
+        {
+            // Mₖ = AMₖ₋₁ + aₖ₋₁I
+            m = mul(&self.a, &m);
+            for (i, row) in m.iter_mut()
+                .enumerate()
+            {
+                row[i] = row[i] + a[k - 1]
+            }
+            // aₖ = -tr(AMₖ)/k
+            let am = mul(&self.a, &m);
+            let trace = (0..N).map(|i| am[i][i])
+                .fold(F::zero(), |t, x| t + x);
+            a[k] = -trace/f!(k);
+
+            let cmb = self.c.iter()
+                .zip(m.iter())
+                .map(|(&c, row)| c*row.iter()
+                    .zip(self.b.iter())
+                    .fold(F::zero(), |s, (&m, &b)| s + m*b)
+                ).fold(F::zero(), |s, x| s + x);
+            b[k] = cmb + self.d*a[k];
+        }
+
+        (b, a)
+    }
+
+    /// Returns the response of the model for a single z-plane point.
+    pub fn z_response(&self, z: Complex<F>) -> Complex<F>
+    {
+        // Solve (zI - A)v = B by Gaussian elimination with partial pivoting
+        let mut m: [[Complex<F>; N]; N] = core::array::from_fn(|i| core::array::from_fn(|j| {
+            let a = Complex::from(-self.a[i][j]);
+            if i == j {a + z} else {a}
+        }));
+        let mut v = self.b.map(Complex::from);
+
+        for k in 0..N
+        {
+            let pivot = (k..N).max_by(|&i, &j| m[i][k].norm_sqr().partial_cmp(&m[j][k].norm_sqr()).unwrap_or(core::cmp::Ordering::Equal))
+                .unwrap();
+            m.swap(k, pivot);
+            v.swap(k, pivot);
+            let (pivot, below) = m.split_at_mut(k + 1);
+            let pivot = &pivot[k];
+            let (v_pivot, v_below) = v.split_at_mut(k + 1);
+            let v_pivot = v_pivot[k];
+            for (row, v) in below.iter_mut()
+                .zip(v_below.iter_mut())
+            {
+                let f = row[k]/pivot[k];
+                for (m, &p) in row[k..].iter_mut()
+                    .zip(pivot[k..].iter())
+                {
+                    *m = *m - f*p
+                }
+                *v = *v - f*v_pivot
+            }
+        }
+        for k in (0..N).rev()
+        {
+            let s = (k + 1..N).fold(v[k], |s, j| s - m[k][j]*v[j]);
+            v[k] = s/m[k][k]
+        }
+
+        self.c.iter()
+            .zip(v.iter())
+            .fold(Complex::from(self.d), |h, (&c, &v)| h + v*c)
+    }
+
+    /// Returns the response of the model for a single frequency point, in radians per sample.
+    pub fn frequency_response(&self, omega: F) -> Complex<F>
+    {
+        self.z_response(Complex::cis(omega))
+    }
+}
+
+fn mul<F, const N: usize>(x: &[[F; N]; N], y: &[[F; N]; N]) -> [[F; N]; N]
+where
+    F: FilterFloat
+{
+    core::array::from_fn(|i| core::array::from_fn(|j| (0..N).fold(F::zero(), |s, k| s + x[i][k]*y[k][j])))
+}
\ No newline at end of file
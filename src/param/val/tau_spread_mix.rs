@@ -0,0 +1,37 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam}};
+
+/// Parameters of an all-pass cascade, like a phaser.
+///
+/// `tau` is the geometric center of the time constants of the stages, and `spread` is the ratio between the largest and the smallest one. With a `spread` of
+/// one, all stages are equal, like in most analog phasers. `mix` is the amount of all-passed signal mixed with the dry signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct TauSpreadMix<F>
+where
+    F: FilterFloat
+{
+    pub tau: F,
+    pub spread: F,
+    pub mix: F
+}
+impl<F> Change for TauSpreadMix<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.tau.change(to.tau, change);
+        self.spread.change(to.spread, change);
+        self.mix.change(to.mix, change);
+    }
+}
+impl<F> FilterParam for TauSpreadMix<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 0;
+
+    type F = F;
+}
\ No newline at end of file
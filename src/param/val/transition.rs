@@ -0,0 +1,34 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam}};
+
+/// A transition bandwidth, relative to the sample rate.
+///
+/// For filters that are designed to be ideal within a band, this is the width of the bands close to DC and the Nyquist frequency where they're not.
+///
+/// Since those two bands can't overlap, the transition must lie in the open range `0 < transition < 0.25`. Filters clamp it to that range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct Transition<F>
+where
+    F: FilterFloat
+{
+    pub transition: F
+}
+impl<F> Change for Transition<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.transition.change(to.transition, change);
+    }
+}
+impl<F> FilterParam for Transition<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 0;
+
+    type F = F;
+}
\ No newline at end of file
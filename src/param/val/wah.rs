@@ -1,4 +1,4 @@
-use crate::{change::Change, param::{FilterFloat, FilterParam, WahFilterParam}};
+use crate::{change::Change, f, param::{circuit_from_consts, FilterFloat, FilterParam, WahCircuit, WahFilterParam}};
 
 use super::X;
 
@@ -14,6 +14,9 @@ pub(crate) const BC109B_BETA: f64 = 500.0;
 pub(crate) const BC184_V_F: f64 = 0.7;
 pub(crate) const BC184_BETA: f64 = 240.0;
 
+pub(crate) const TWO_N5133_V_F: f64 = 0.65;
+pub(crate) const TWO_N5133_BETA: f64 = 400.0;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(deny_unknown_fields)] 
 pub struct CrybabyGCB95<F>
@@ -201,4 +204,167 @@ where
             x
         }
     }
-}
\ No newline at end of file
+}
+
+/// The original Thomas Organ Cry Baby, as made in the late 1960s.
+/// 
+/// Dunlop took over the Cry Baby from Thomas Organ, and kept its circuit for the [`CrybabyGCB95`], so the component table is the same, part for part.
+/// What changed over the years are the transistors and the inductor: the original used 2N5133 transistors, and a 500 mH inductor instead of the later
+/// 540 mH one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct ThomasOrganCrybaby<F>
+where
+    F: FilterFloat
+{
+    pub x: F
+}
+impl<F> Change<X<F>> for ThomasOrganCrybaby<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: X<F>, change: Self::F)
+    {
+        self.x.change(to.x, change);
+    }
+}
+impl<F> FilterParam for ThomasOrganCrybaby<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 4;
+
+    type F = F;
+}
+impl<F> WahFilterParam for ThomasOrganCrybaby<F>
+where
+    F: FilterFloat
+{
+    const R_I: f64 = 68e3 + GUITAR_R_I; // R1
+    const R_S: f64 = 1.5e3; // R2
+    const R_P: f64 = 33e3; // R7
+    const R_C1: f64 = 22e3; // R3
+    const R_E1: f64 = 470.0; // R4
+    const R_C2: f64 = 1e3; // R9
+    const R_E2: f64 = 10e3; // R10
+    const R_J: f64 = 470e3; // R6 and R5
+    const R_G: f64 = 82e3; // R8
+    const R_POT: f64 = 100e3; // VR1
+    
+    const V_CC: f64 = 9.0; // Supply voltage
+    const V_F: f64 = TWO_N5133_V_F; // BJT forward voltage
+    const V_T: f64 = V_T; // BJT thermal voltage
+    
+    const BETA: f64 = TWO_N5133_BETA; // BJT forward gain / hFE
+    
+    const C_G: f64 = 4.7e-6; // C3
+    const C_I: f64 = 10e-9; // C1
+    const C_F: f64 = 10e-9; // C2
+    
+    const L: f64 = 500e-3; // L1
+
+    fn x(&self) -> X<Self::F>
+    {
+        let ThomasOrganCrybaby {x} = *self;
+        X {
+            x
+        }
+    }
+}
+
+/// The Dunlop 535Q Cry Baby, with its Q and boost controls.
+/// 
+/// `q` sweeps the resistor across the inductor from 10 kΩ (0.0, broad) to 100 kΩ (1.0, sharp), and `boost` sweeps the boost stage from unity gain (0.0)
+/// to +16 dB (1.0). The range selector is left at its stock position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct Dunlop535Q<F>
+where
+    F: FilterFloat
+{
+    pub x: F,
+    pub q: F,
+    pub boost: F
+}
+impl<F> Change for Dunlop535Q<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.x.change(to.x, change);
+        self.q.change(to.q, change);
+        self.boost.change(to.boost, change);
+    }
+}
+impl<F> Change<X<F>> for Dunlop535Q<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: X<F>, change: Self::F)
+    {
+        self.x.change(to.x, change);
+    }
+}
+impl<F> FilterParam for Dunlop535Q<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 4;
+
+    type F = F;
+}
+impl<F> WahFilterParam for Dunlop535Q<F>
+where
+    F: FilterFloat
+{
+    const R_I: f64 = 68e3 + GUITAR_R_I; // R1
+    const R_S: f64 = 1.5e3; // R2
+    const R_P: f64 = 10e3; // Q potentiometer at its minimum, swept up to 100 kΩ by circuit()
+    const R_C1: f64 = 22e3; // R3
+    const R_E1: f64 = 470.0; // R4
+    const R_C2: f64 = 1e3; // R9
+    const R_E2: f64 = 10e3; // R10
+    const R_J: f64 = 470e3; // R6 and R5
+    const R_G: f64 = 82e3; // R8
+    const R_POT: f64 = 100e3; // VR1
+    
+    const V_CC: f64 = 9.0; // Supply voltage
+    const V_F: f64 = MPSA18_V_F; // BJT forward voltage
+    const V_T: f64 = V_T; // BJT thermal voltage
+    
+    const BETA: f64 = MPSA18_BETA; // BJT forward gain / hFE
+    
+    const C_G: f64 = 4.7e-6; // C3
+    const C_I: f64 = 10e-9; // C1
+    const C_F: f64 = 10e-9; // C2
+    
+    const L: f64 = 540e-3; // L1
+
+    const RUNTIME_CIRCUIT: bool = true;
+
+    fn x(&self) -> X<Self::F>
+    {
+        let Dunlop535Q {x, ..} = *self;
+        X {
+            x
+        }
+    }
+
+    fn circuit(&self) -> WahCircuit<Self::F>
+    {
+        let Dunlop535Q {q, boost, ..} = *self;
+        let ten = f!(10.0);
+        WahCircuit {
+            boost: ten.powf(f!(0.8)*boost),
+            r_p: f!(Self::R_P)*ten.powf(q),
+            ..circuit_from_consts(self)
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam, WahFilterParam}};
+
+use super::X;
+
+/// The complete component table of a wah pedal, for circuits not covered by the built-in models.
+///
+/// Every built-in model can be turned into one with [`WahFilterParam::circuit`], as a starting point for tweaking.
+///
+/// There's no built-in model of the Maestro Boomerang. Its filter isn't the two-transistor inductor circuit modelled here, and without a verified
+/// component table for it, a model in this topology would only carry its name. A tweaked component table is the closest this filter can get to it.
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     param::{CrybabyGCB95, WahCircuit, WahFilterParam},
+///     rtf::Rtf,
+///     filters::iir::fourth::WahFilter
+/// };
+///
+/// // A Crybaby with a larger inductor
+/// let circuit = WahCircuit {
+///     l: 660e-3,
+///     ..CrybabyGCB95 {x: 0.3}.circuit()
+/// };
+///
+/// let mut filter = WahFilter::new(circuit);
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct WahCircuit<F>
+where
+    F: FilterFloat
+{
+    /// Position of wah potentiometer from 0.0 to 1.0
+    pub x: F,
+    /// Gain of the boost stage after the wah, if any
+    pub boost: F,
+
+    /// Input resistance to base of Q1
+    pub r_i: F,
+    /// Feedback resistor to base of Q1
+    pub r_s: F,
+    /// Resistor paralell with inductor
+    pub r_p: F,
+    /// Collector resistor of Q1
+    pub r_c1: F,
+    /// Emitter resistor of Q1
+    pub r_e1: F,
+    /// Collector resistor of Q2
+    pub r_c2: F,
+    /// Emitter resistor of Q2
+    pub r_e2: F,
+    /// Junction mixing resistors
+    pub r_j: F,
+    /// Resistor to ground from inductor junction
+    pub r_g: F,
+    /// Potentiometer resistance
+    pub r_pot: F,
+
+    /// Supply voltage
+    pub v_cc: F,
+    /// BJT forward voltage
+    pub v_f: F,
+    /// BJT thermal voltage
+    pub v_t: F,
+
+    /// BJT forward gain / hFE
+    pub beta: F,
+
+    /// Capacitor to ground from inductor junction
+    pub c_g: F,
+    /// Input capacitor to base of Q1
+    pub c_i: F,
+    /// Feeedback capacitor from emitter of Q2
+    pub c_f: F,
+
+    /// Inductor
+    pub l: F
+}
+impl<F> Change for WahCircuit<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        let WahCircuit {x, boost, r_i, r_s, r_p, r_c1, r_e1, r_c2, r_e2, r_j, r_g, r_pot, v_cc, v_f, v_t, beta, c_g, c_i, c_f, l} = to;
+        self.x.change(x, change);
+        self.boost.change(boost, change);
+        self.r_i.change(r_i, change);
+        self.r_s.change(r_s, change);
+        self.r_p.change(r_p, change);
+        self.r_c1.change(r_c1, change);
+        self.r_e1.change(r_e1, change);
+        self.r_c2.change(r_c2, change);
+        self.r_e2.change(r_e2, change);
+        self.r_j.change(r_j, change);
+        self.r_g.change(r_g, change);
+        self.r_pot.change(r_pot, change);
+        self.v_cc.change(v_cc, change);
+        self.v_f.change(v_f, change);
+        self.v_t.change(v_t, change);
+        self.beta.change(beta, change);
+        self.c_g.change(c_g, change);
+        self.c_i.change(c_i, change);
+        self.c_f.change(c_f, change);
+        self.l.change(l, change);
+    }
+}
+impl<F> Change<X<F>> for WahCircuit<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: X<F>, change: Self::F)
+    {
+        self.x.change(to.x, change);
+    }
+}
+impl<F> FilterParam for WahCircuit<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 4;
+
+    type F = F;
+}
+impl<F> WahFilterParam for WahCircuit<F>
+where
+    F: FilterFloat
+{
+    const RUNTIME_CIRCUIT: bool = true;
+
+    fn x(&self) -> X<Self::F>
+    {
+        X {
+            x: self.x
+        }
+    }
+
+    fn circuit(&self) -> WahCircuit<Self::F>
+    {
+        *self
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use crate::{filters::iir::fourth::WahFilter, param::{ColorsoundWow, CrybabyGCB95, Dunlop535Q, ThomasOrganCrybaby, VoxV847, WahFilterParam}, rtf::Rtf};
+
+    #[test]
+    fn circuit()
+    {
+        const RATE: f64 = 44100.0;
+
+        fn test<P>(param: P)
+        where
+            P: WahFilterParam<F = f64> + Copy
+        {
+            let mut model = WahFilter::new(param);
+            let mut circuit = WahFilter::new(param.circuit());
+            for omega in [100.0, 500.0, 1000.0, 2000.0, 5000.0]
+            {
+                let [h_model] = model.frequency_response(RATE, omega*TAU);
+                let [h_circuit] = circuit.frequency_response(RATE, omega*TAU);
+                assert!((h_model - h_circuit).norm() <= 1e-9*h_model.norm());
+            }
+        }
+
+        for x in [0.1, 0.5, 0.9]
+        {
+            test(CrybabyGCB95 {x});
+            test(VoxV847 {x});
+            test(ColorsoundWow {x});
+            test(ThomasOrganCrybaby {x});
+            test(Dunlop535Q {x, q: 0.3, boost: 0.5});
+        }
+
+        // Full boost is +16 dB
+        let mut flat = WahFilter::new(Dunlop535Q {x: 0.5, q: 0.5, boost: 0.0});
+        let mut boosted = WahFilter::new(Dunlop535Q {x: 0.5, q: 0.5, boost: 1.0});
+        let [h_flat] = flat.frequency_response(RATE, 1000.0*TAU);
+        let [h_boosted] = boosted.frequency_response(RATE, 1000.0*TAU);
+        assert!((20.0*(h_boosted.norm()/h_flat.norm()).log10() - 16.0).abs() < 1e-3);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use crate::{change::Change, param::{FilterFloat, FilterParam, FirstOrderLeadLagFilterParam}};
+
+/// A real zero and a real pole, given by their frequencies in rad/s, and the DC gain.
+/// 
+/// <pre>
+///          1 + s/ω₀
+/// H(s) = K --------
+///          1 + s/ωₚ
+/// </pre>
+/// 
+/// With the zero below the pole, the phase leads, and with the pole below the zero, it lags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)] 
+pub struct ZeroPole<F>
+where
+    F: FilterFloat
+{
+    pub zero: F,
+    pub pole: F,
+    pub gain: F
+}
+impl<F> Change for ZeroPole<F>
+where
+    F: FilterFloat
+{
+    type F = F;
+
+    fn change(&mut self, to: Self, change: Self::F)
+    {
+        self.zero.change(to.zero, change);
+        self.pole.change(to.pole, change);
+        self.gain.change(to.gain, change);
+    }
+}
+impl<F> FilterParam for ZeroPole<F>
+where
+    F: FilterFloat
+{
+    const ORDER: usize = 1;
+
+    type F = F;
+}
+impl<F> FirstOrderLeadLagFilterParam for ZeroPole<F>
+where
+    F: FilterFloat
+{
+    fn zero_pole(&self) -> ZeroPole<Self::F>
+    {
+        *self
+    }
+}
\ No newline at end of file
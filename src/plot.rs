@@ -274,7 +274,7 @@ where
                 .map(|(x, (y_norm, y_arg))| db(y_norm).map(|y_norm| (x, (y_norm, y_arg))))
                 .collect::<Vec<_>>();
             for xy in xy.split(|xy| xy.is_none())
-                .map(|xy| xy.into_iter()
+                .map(|xy| xy.iter()
                     .filter_map(|&xy| xy)
                     .collect::<Vec<_>>()
                 )
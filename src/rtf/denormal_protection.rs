@@ -0,0 +1,151 @@
+use num::Float;
+
+/// Keeps the state of a filter from decaying into subnormal numbers when the input goes silent.
+///
+/// Arithmetic on subnormal numbers is very slow on many CPUs (x86 in particular), so a filter left ringing out into silence can suddenly cost many times more
+/// than usual. All of these options leave signals at normal levels practically unchanged, as long as the threshold or level is chosen far below the signal.
+///
+/// The protection can be selected per filter with [`Rtf::set_denormal_protection`](crate::rtf::Rtf::set_denormal_protection).
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use core::f32::consts::TAU;
+///
+/// use real_time_fir_iir_filters::{
+///     conf::LowPass,
+///     param::Omega,
+///     rtf::{DenormalProtection, Rtf},
+///     filters::iir::first::FirstOrderFilter
+/// };
+///
+/// let mut filter = FirstOrderFilter::<LowPass, f32>::new(
+///     Omega {
+///         omega: 440.0*TAU
+///     }
+/// );
+///
+/// filter.set_denormal_protection(DenormalProtection::FlushToZero(1e-30));
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum DenormalProtection<F>
+{
+    /// No protection. This is the default.
+    #[default]
+    None,
+    /// Sets every state variable smaller in magnitude than the given threshold to zero, after each sample.
+    FlushToZero(F),
+    /// Adds a tiny constant offset of the given level to the input of each section.
+    /// 
+    /// This is the cheapest option, but the offset is passed on to the output, scaled by the DC gain of the filter.
+    Dc(F),
+    /// Adds tiny white noise of the given peak level to the input of each section.
+    /// 
+    /// Unlike a DC offset, this also keeps sections behind a high-pass section from decaying.
+    Noise(F)
+}
+
+impl<F> DenormalProtection<F>
+{
+    /// The initial state of the noise generator.
+    pub(crate) const fn seed() -> u32
+    {
+        0x9e3779b9
+    }
+}
+
+impl<F> DenormalProtection<F>
+where
+    F: Float
+{
+    /// Returns the offset to add to the input of each section for the next sample.
+    pub(crate) fn offset(&self, seed: &mut u32) -> F
+    {
+        match *self
+        {
+            Self::None | Self::FlushToZero(_) => F::zero(),
+            Self::Dc(level) => level,
+            Self::Noise(level) => {
+                // xorshift32
+                *seed ^= *seed << 13;
+                *seed ^= *seed >> 17;
+                *seed ^= *seed << 5;
+                let uniform = F::from(*seed).unwrap()/F::from(u32::MAX).unwrap();
+                level*(uniform + uniform - F::one())
+            }
+        }
+    }
+
+    /// Flushes the values of a state to zero, if they are below the threshold. Nested arrays can be flattened with
+    /// [`as_flattened_mut`](slice::as_flattened_mut).
+    pub(crate) fn flush(&self, state: &mut [F])
+    {
+        if let Self::FlushToZero(threshold) = *self
+        {
+            for x in state.iter_mut()
+            {
+                if x.abs() < threshold
+                {
+                    *x = F::zero()
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f32::consts::TAU;
+
+    use crate::{conf::HighPass, filters::iir::second::SecondOrderFilter, param::OmegaZeta, rtf::Rtf};
+
+    use super::DenormalProtection;
+
+    #[test]
+    fn silence()
+    {
+        const RATE: f32 = 44100.0;
+        const N: usize = 1 << 16;
+
+        let mut filter = SecondOrderFilter::<HighPass, f32>::new(OmegaZeta {omega: 1000.0*TAU, zeta: 0.5});
+
+        let mut reference = [0.0; N];
+        for protection in [
+            DenormalProtection::None,
+            DenormalProtection::FlushToZero(1e-30),
+            DenormalProtection::Dc(1e-20),
+            DenormalProtection::Noise(1e-20)
+        ]
+        {
+            filter.set_denormal_protection(protection);
+            filter.reset();
+
+            let mut any_subnormal = false;
+            for (n, y0) in reference.iter_mut()
+                .enumerate()
+            {
+                let [y] = filter.filter(RATE, if n == 0 {1.0} else {0.0});
+                any_subnormal |= filter.internals.w.1.iter()
+                    .flatten()
+                    .any(|w| w.is_subnormal());
+
+                // The impulse response is unchanged, until it decays to the level of the protection itself
+                if protection == DenormalProtection::None
+                {
+                    *y0 = y
+                }
+                assert!((y - *y0).abs() < 1e-6, "{protection:?}, n = {n}: {y} != {y0}");
+                if y0.abs() > 1e-15
+                {
+                    assert!((y - *y0).abs() <= 1e-3*y0.abs(), "{protection:?}, n = {n}: {y} != {y0}");
+                }
+            }
+            assert_eq!(any_subnormal, protection == DenormalProtection::None, "{protection:?}");
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use core::{fmt::Debug, marker::PhantomData, mem::MaybeUninit};
+
+use array_trait::AsSlice;
+
+use crate::{ainternals, f, internals::{AInternalsFor, BInternalsFor, RtfInternals, RtfInternalsFor}, param::{FilterFloat, Param}, rtf::{RtfBase, StaticRtf}, util};
+
+#[allow(type_alias_bounds)]
+type Internals<T: StaticRtf, F> = RtfInternals<F,
+    crate::winternals!(T where F as StaticRtf),
+    crate::binternals!(T where F, RtfBase as StaticRtf),
+    T::IsIir<crate::ainternals!(T where F as StaticRtf)>
+>;
+
+/// Runs the signal path of a filter `T` in the float type `F`, while computing its coefficients in `T::F`.
+///
+/// The coefficients are calculated by `T`'s own [`make_coeffs`](StaticRtf::make_coeffs) in `T::F` (typically [`f64`]), and then converted once to `F` (typically
+/// [`f32`]) every time the parameter or the sample rate changes. This keeps the coefficients accurate for filters that are sensitive to rounding errors during their
+/// calculation (like the [`ThirdOrderSallenKeyFilter`](crate::filters::iir::third::ThirdOrderSallenKeyFilter) at low frequencies), while keeping the cost of the
+/// signal path the same as for a filter in `F`.
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     conf::LowPass,
+///     param::RC3GSallenKey,
+///     rtf::{MixedPrecision, Rtf},
+///     filters::iir::third::ThirdOrderSallenKeyFilter
+/// };
+///
+/// // Coefficients are computed in f64, the signal is processed in f32
+/// let mut filter = MixedPrecision::<ThirdOrderSallenKeyFilter<LowPass>, f32>::new(
+///     RC3GSallenKey {r1: 47e3, c1: 470e-9, r2: 150e3, c2: 27e-9, r3: 160e3, c3: 27e-9, g: 1.38}
+/// );
+///
+/// let [y]: [f32; 1] = filter.filter(44100.0, 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields, bound(serialize = "T::Param: serde::Serialize", deserialize = "T::Param: serde::Deserialize<'de>"))]
+pub struct MixedPrecision<T, F = f32>
+where
+    T: StaticRtf,
+    F: FilterFloat,
+    Internals<T, F>: Copy + Debug + Default + PartialEq
+{
+    pub param: Param<T::Param>,
+    pub internals: Internals<T, F>,
+    #[serde(skip)]
+    phantom: PhantomData<T>
+}
+
+impl<T, F> MixedPrecision<T, F>
+where
+    T: StaticRtf,
+    F: FilterFloat,
+    Internals<T, F>: Copy + Debug + Default + PartialEq
+{
+    pub const fn new(param: T::Param) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            internals: RtfInternals::new(),
+            phantom: PhantomData
+        }
+    }
+}
+
+impl<T, F> RtfBase for MixedPrecision<T, F>
+where
+    T: StaticRtf,
+    F: FilterFloat,
+    Internals<T, F>: Copy + Debug + Default + PartialEq
+{
+    type Param = T::Param;
+    type Conf = T::Conf;
+    type F = F;
+    type Outputs<U> = T::Outputs<U>;
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(param)
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        &self.param
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        &mut self.param
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.param.into_value()
+    }
+}
+
+impl<T, F> StaticRtf for MixedPrecision<T, F>
+where
+    T: StaticRtf,
+    F: FilterFloat,
+    Internals<T, F>: Copy + Debug + Default + PartialEq
+{
+    type IsIir<U> = T::IsIir<U>;
+    type Order<U> = T::Order<U>;
+    type OutputBufs<U> = T::OutputBufs<U>;
+    type SosBufs<U> = T::SosBufs<U>;
+    type SosStages<U> = T::SosStages<U>;
+
+    #[allow(clippy::type_complexity)]
+    fn get_internals(&self) -> (&RtfInternalsFor<Self>, &Param<Self::Param>)
+    {
+        (&self.internals, &self.param)
+    }
+    #[allow(clippy::type_complexity)]
+    fn get_internals_mut(&mut self) -> (&mut RtfInternalsFor<Self>, &mut Param<Self::Param>)
+    {
+        (&mut self.internals, &mut self.param)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn make_coeffs(param: &Self::Param, rate: Self::F) -> (
+        BInternalsFor<Self>,
+        Self::IsIir<AInternalsFor<Self>>
+    )
+    {
+        let ((b_stages, b_last_stage, b_output), a) = T::make_coeffs(param, f!(rate; T::F));
+
+        // SAFETY: The buffer types of `StaticRtf` are all arrays, so the coefficients of both filters are nested arrays of the same shape, only of different floats
+        let mut a_cast = MaybeUninit::<Self::IsIir<AInternalsFor<Self>>>::uninit();
+        for (i, (a_stages, a_output)) in a.as_slice()
+            .iter()
+            .enumerate()
+        {
+            unsafe {
+                a_cast.as_mut_ptr()
+                    .cast::<ainternals!(Self)>()
+                    .add(i)
+                    .write((
+                        util::cast_floats::<T::F, F, _, _>(a_stages),
+                        util::cast_floats::<T::F, F, _, _>(a_output)
+                    ))
+            }
+        }
+
+        unsafe {
+            (
+                (
+                    util::cast_floats::<T::F, F, _, _>(&b_stages),
+                    util::cast_floats::<T::F, F, _, _>(&b_last_stage),
+                    util::cast_floats::<T::F, F, _, _>(&b_output)
+                ),
+                a_cast.assume_init()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    #[cfg(feature = "third_order_sallen_key")]
+    #[test]
+    fn low_frequency_accuracy()
+    {
+        use crate::{conf::LowPass, filters::iir::third::ThirdOrderSallenKeyFilter, param::RC3GSallenKey, rtf::Rtf};
+
+        use super::MixedPrecision;
+
+        const RATE: f64 = 44100.0;
+        const OMEGA: f64 = 0.001;
+
+        let param = RC3GSallenKey {r1: 47e3, c1: 470e-9, r2: 150e3, c2: 27e-9, r3: 160e3, c3: 27e-9, g: 1.38};
+        let param32 = RC3GSallenKey {
+            r1: param.r1 as f32,
+            c1: param.c1 as f32,
+            r2: param.r2 as f32,
+            c2: param.c2 as f32,
+            r3: param.r3 as f32,
+            c3: param.c3 as f32,
+            g: param.g as f32
+        };
+
+        let mut reference = ThirdOrderSallenKeyFilter::<LowPass>::new(param);
+        let mut single = ThirdOrderSallenKeyFilter::<LowPass, f32>::new(param32);
+        let mut mixed = MixedPrecision::<ThirdOrderSallenKeyFilter<LowPass>, f32>::new(param);
+
+        let [h] = reference.frequency_response(RATE, OMEGA);
+        let [h_single] = single.frequency_response(RATE as f32, OMEGA as f32);
+        let [h_mixed] = mixed.frequency_response(RATE as f32, OMEGA as f32);
+
+        let e_single = (h_single.norm() as f64 - h.norm()).abs();
+        let e_mixed = (h_mixed.norm() as f64 - h.norm()).abs();
+
+        assert!(e_mixed < e_single, "|H| = {}, e_single = {e_single}, e_mixed = {e_mixed}", h.norm());
+
+        let y: [f32; 1] = mixed.filter(RATE as f32, 1.0);
+        assert!(y[0].is_finite());
+    }
+}
@@ -1,6 +1,12 @@
 moddef::moddef!(
     flat(pub) mod {
+        denormal_protection,
+        mixed_precision,
+        oversampled,
         rtf,
-        static_rtf
+        rtf_base,
+        sos,
+        static_rtf,
+        structure
     }
 );
\ No newline at end of file
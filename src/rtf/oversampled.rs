@@ -0,0 +1,313 @@
+use core::mem::MaybeUninit;
+
+use array_trait::{AsArray, AsSlice};
+use num::{Complex, Float, One, Zero};
+
+use crate::{f, filters::fir::{half_band_z_response, HalfBandDecimator, HalfBandInterpolator}, rtf::{DenormalProtection, Rtf, RtfBase}};
+
+const MAX_STAGES: usize = 3;
+const MAX_FACTOR: usize = 1 << MAX_STAGES;
+
+/// Runs a filter `T` at `FACTOR` times the sample rate, where `FACTOR` is 2, 4 or 8.
+///
+/// The signal is upsampled by a cascade of 2x [`HalfBandInterpolator`]s, filtered by `T` at the higher sample rate, and then downsampled again by a cascade of 2x
+/// [`HalfBandDecimator`]s (one cascade for each output). This pushes the warping of the bilinear transform, and any aliasing, far above the audible range, which
+/// matters for filters with resonances close to the Nyquist frequency.
+///
+/// The half-band filters pass everything up to 0.4 times the sample rate (17.6kHz at 44.1kHz) unchanged, except for adding a constant delay of
+/// [`latency`](Oversampled::latency) samples.
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use real_time_fir_iir_filters::{
+///     param::CrybabyGCB95,
+///     rtf::{Oversampled, Rtf},
+///     filters::iir::fourth::WahFilter
+/// };
+///
+/// // Run the wah at 4 times the host rate
+/// let mut filter = Oversampled::<_, 4>::new(WahFilter::new(CrybabyGCB95 {x: 0.9}));
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+///
+/// // Latency in samples at the host rate
+/// let latency = filter.latency();
+/// ```
+pub struct Oversampled<T, const FACTOR: usize = 2>
+where
+    T: Rtf
+{
+    /// The inner filter, running at `FACTOR` times the sample rate.
+    pub filter: T,
+    up: [HalfBandInterpolator<T::F>; MAX_STAGES],
+    down: T::Outputs<[HalfBandDecimator<T::F>; MAX_STAGES]>
+}
+
+impl<T, const FACTOR: usize> Oversampled<T, FACTOR>
+where
+    T: Rtf
+{
+    const STAGES: usize = FACTOR.ilog2() as usize;
+    const VALID_FACTOR: () = assert!(FACTOR.is_power_of_two() && FACTOR > 1 && FACTOR <= MAX_FACTOR, "The oversampling factor must be 2, 4 or 8");
+
+    pub fn new(filter: T) -> Self
+    {
+        let () = Self::VALID_FACTOR;
+
+        let mut down = MaybeUninit::<T::Outputs<[HalfBandDecimator<T::F>; MAX_STAGES]>>::uninit();
+        for i in 0..T::Outputs::<()>::LENGTH
+        {
+            unsafe {
+                down.as_mut_ptr()
+                    .cast::<[HalfBandDecimator<T::F>; MAX_STAGES]>()
+                    .add(i)
+                    .write(Default::default())
+            }
+        }
+
+        Self {
+            filter,
+            up: Default::default(),
+            down: unsafe {
+                down.assume_init()
+            }
+        }
+    }
+
+    /// The delay added by the up- and downsampling, in samples at the host sample rate.
+    ///
+    /// Each 2x stage delays the signal by [`HalfBandDecimator::DELAY`] samples at its lower sample rate. This does not include any delay caused by the inner filter itself.
+    pub fn latency(&self) -> T::F
+    {
+        (0..Self::STAGES).map(|i| f!(HalfBandDecimator::<T::F>::DELAY; T::F)/f!(1usize << i; T::F))
+            .fold(T::F::zero(), |l, d| l + d)
+    }
+
+    /// Returns the response of the up- and downsampling, where `z(i)` is the z-plane point at the sample rate of stage `i`, from 1 to the number of stages.
+    fn resampling_response(&self, z: impl Fn(usize) -> Complex<T::F>) -> Complex<T::F>
+    {
+        (1..=Self::STAGES).map(|i| {
+                let h = half_band_z_response(self.up[0].get_param(), z(i));
+                h*h
+            }).fold(Complex::from(T::F::one()), |h, t| h*t)
+    }
+
+    fn scale(mut h: T::Outputs<Complex<T::F>>, h_resampling: Complex<T::F>) -> T::Outputs<Complex<T::F>>
+    {
+        for h in h.as_mut_slice()
+        {
+            *h = *h*h_resampling
+        }
+        h
+    }
+}
+
+impl<T, const FACTOR: usize> RtfBase for Oversampled<T, FACTOR>
+where
+    T: Rtf
+{
+    type Param = T::Param;
+    type Conf = T::Conf;
+    type F = T::F;
+
+    type Outputs<U> = T::Outputs<U>;
+
+    fn from_param(param: Self::Param) -> Self
+    {
+        Self::new(T::from_param(param))
+    }
+    fn get_param(&self) -> &Self::Param
+    {
+        self.filter.get_param()
+    }
+    fn get_param_mut(&mut self) -> &mut Self::Param
+    {
+        self.filter.get_param_mut()
+    }
+    fn into_param(self) -> Self::Param
+    {
+        self.filter.into_param()
+    }
+}
+
+impl<T, const FACTOR: usize> Rtf for Oversampled<T, FACTOR>
+where
+    T: Rtf
+{
+    /// Feeds a single sample through the filter at `FACTOR` times `rate`, and returns the results from each output in an array.
+    fn filter(&mut self, rate: T::F, x: T::F) -> T::Outputs<T::F>
+    {
+        let mut x_up = [T::F::zero(); MAX_FACTOR];
+        x_up[0] = x;
+        let mut n = 1;
+        for up in self.up[..Self::STAGES].iter_mut()
+        {
+            let x = x_up;
+            let rate = rate*f!(n; T::F);
+            for j in 0..n
+            {
+                [x_up[2*j], x_up[2*j + 1]] = up.filter(rate, x[j])
+            }
+            n *= 2
+        }
+
+        let rate = rate*f!(FACTOR; T::F);
+        let mut y_up = MaybeUninit::<T::Outputs<[T::F; MAX_FACTOR]>>::uninit();
+        let y_up = unsafe {
+            y_up.as_mut_ptr().write_bytes(0u8, 1);
+            y_up.assume_init_mut()
+        };
+        for (j, &x) in x_up[..FACTOR].iter()
+            .enumerate()
+        {
+            for (y_up, y) in y_up.as_mut_slice()
+                .iter_mut()
+                .zip(self.filter.filter(rate, x).as_slice())
+            {
+                y_up[j] = *y
+            }
+        }
+
+        let mut y = MaybeUninit::<T::Outputs<T::F>>::uninit();
+        for (i, (y_up, down)) in y_up.as_mut_slice()
+            .iter_mut()
+            .zip(self.down.as_mut_slice())
+            .enumerate()
+        {
+            let mut n = FACTOR;
+            for down in down[..Self::STAGES].iter_mut()
+                .rev()
+            {
+                let rate = rate*f!(n; T::F)/f!(FACTOR; T::F);
+                n /= 2;
+                for j in 0..n
+                {
+                    down.filter(rate, y_up[2*j]);
+                    [y_up[j]] = down.filter(rate, y_up[2*j + 1])
+                }
+            }
+            unsafe {
+                y.as_mut_ptr()
+                    .cast::<T::F>()
+                    .add(i)
+                    .write(y_up[0])
+            }
+        }
+        unsafe {
+            y.assume_init()
+        }
+    }
+
+    /// Returns the response of the whole signal path for a single frequency point, in radians at the host sample rate.
+    ///
+    /// This is the response of the inner filter at the higher sample rate, times the responses of the half-band filters.
+    fn frequency_response(&mut self, rate: T::F, omega: T::F) -> T::Outputs<Complex<T::F>>
+    {
+        let factor = f!(FACTOR; T::F);
+        let h_resampling = self.resampling_response(|i| Complex::cis(omega/f!(1usize << i; T::F)));
+        Self::scale(self.filter.frequency_response(rate*factor, omega/factor), h_resampling)
+    }
+
+    /// Returns the response of the whole signal path for a single s-plane point.
+    fn s_response(&mut self, rate: T::F, s: Complex<T::F>) -> T::Outputs<Complex<T::F>>
+    {
+        let h_resampling = self.resampling_response(|i| (s/(rate*f!(1usize << i; T::F))).exp());
+        Self::scale(self.filter.s_response(rate*f!(FACTOR; T::F), s), h_resampling)
+    }
+
+    /// Returns the response of the whole signal path for a single z-plane point.
+    ///
+    /// The z-plane point is mapped to the higher sample rate through the principal root, `z^(1/FACTOR)`, so for points on the unit circle this agrees with
+    /// [`frequency_response`](Rtf::frequency_response) for ω ∈ (-π, π].
+    fn z_response(&mut self, rate: T::F, z: Complex<T::F>) -> T::Outputs<Complex<T::F>>
+    {
+        let h_resampling = self.resampling_response(|i| z.powf(f!(1usize << i; T::F).recip()));
+        Self::scale(self.filter.z_response(rate*f!(FACTOR; T::F), z.powf(f!(FACTOR; T::F).recip())), h_resampling)
+    }
+
+    /// Resets the internal state of the inner filter, and of the up- and downsampling filters.
+    fn reset(&mut self)
+    {
+        self.filter.reset();
+        for up in self.up.iter_mut()
+        {
+            up.reset()
+        }
+        for down in self.down.as_mut_slice()
+            .iter_mut()
+            .flatten()
+        {
+            down.reset()
+        }
+    }
+
+    /// Returns the denormal protection of the inner filter. The half-band filters have no feedback, and don't need any.
+    fn denormal_protection(&self) -> DenormalProtection<T::F>
+    {
+        self.filter.denormal_protection()
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<T::F>)
+    {
+        self.filter.set_denormal_protection(denormal_protection)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::{PI, TAU};
+
+    use crate::{conf::LowPass, filters::iir::second::SecondOrderFilter, param::OmegaZeta, rtf::Rtf};
+
+    use super::Oversampled;
+
+    #[test]
+    fn sine_response()
+    {
+        const RATE: f64 = 44100.0;
+        const N: usize = 4096;
+
+        fn test<const FACTOR: usize>()
+        {
+            // Resonant peak at 15kHz, where the bilinear transform warps a lot at the host rate
+            let param = OmegaZeta {omega: 15000.0*TAU, zeta: 0.1};
+            let mut filter = Oversampled::<_, FACTOR>::new(SecondOrderFilter::<LowPass>::new(param));
+            let mut reference = SecondOrderFilter::<LowPass>::new(param);
+
+            for f in [100.0, 1000.0, 10000.0, 15000.0]
+            {
+                let omega = f*TAU/RATE;
+
+                // Frequency response matches the measured response to a sine
+                let [h] = filter.frequency_response(RATE, omega);
+                filter.reset();
+                let mut y_last = 0.0;
+                for n in 0..N
+                {
+                    [y_last] = filter.filter(RATE, (omega*n as f64).sin());
+                }
+                let y_expected = h.norm()*(omega*(N - 1) as f64 + h.arg()).sin();
+                assert!((y_last - y_expected).abs() < 1e-3*h.norm().max(1.0), "{FACTOR}x, {f}Hz: {y_last} != {y_expected}");
+
+                // The linear phase of the resampling is the reported latency
+                let [h_inner] = filter.filter.frequency_response(RATE*FACTOR as f64, omega/FACTOR as f64);
+                let phase_error = (h_inner.arg() - h.arg() - omega*filter.latency() + PI).rem_euclid(TAU) - PI;
+                assert!(phase_error.abs() < 1e-3, "{FACTOR}x, {f}Hz");
+            }
+
+            // The resonance ends up much closer to the analog 1/(2ζ) = 5
+            let omega = 15000.0*TAU/RATE;
+            let [h] = filter.frequency_response(RATE, omega);
+            let [h_reference] = reference.frequency_response(RATE, omega);
+            assert!((h.norm() - 5.0).abs() < (h_reference.norm() - 5.0).abs());
+        }
+
+        test::<2>();
+        test::<4>();
+        test::<8>();
+    }
+}
@@ -2,9 +2,13 @@ use core::{iter::Sum, mem::MaybeUninit, ops::{Add, MulAssign}};
 
 use array_trait::AsArray;
 use num::{Complex, Float};
-use crate::{ainternals, binternals, rtfinternals, winternals, param::{FilterFloat, Param}, rtf::StaticRtf, util::{ArrayChunks, ArrayMax, ArrayMin1, ArrayMinus1, ArrayPlus1}};
+use crate::{ainternals, binternals, rtfinternals, winternals, internals::StructureInternals, param::{FilterFloat, Param}, rtf::{DenormalProtection, RtfBase, StaticRtf, structure}, util::{ArrayChunks, ArrayMax, ArrayMin1, ArrayMinus1, ArrayPlus1}};
 
-pub trait Rtf: StaticRtf
+/// A real-time filter, that can be fed one sample at a time.
+///
+/// This is implemented for all filters that cache their coefficients (see [`StaticRtf`]). Filters that run in a topology of their own, like the
+/// [`MoogLadderFilter`](crate::filters::iir::fourth::MoogLadderFilter), implement it directly.
+pub trait Rtf: RtfBase
 {
     /// Feeds a single sample through the filter, and returns the results from each output in an array.
     /// 
@@ -143,6 +147,12 @@ pub trait Rtf: StaticRtf
     /// assert_eq!(imp1, imp2);
     /// ```
     fn reset(&mut self);
+
+    /// Returns the denormal protection currently used by the filter.
+    fn denormal_protection(&self) -> DenormalProtection<Self::F>;
+
+    /// Selects how the filter's state is kept from decaying into subnormal numbers.
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<Self::F>);
 }
 
 impl<
@@ -212,7 +222,7 @@ where
         Rem = [[F; ORDER_PLUS_1]; 0],
         Chunks = [[[F; ORDER_PLUS_1]; OUTPUT_BUFS]; OUTPUT_CHUNKS]
     >,
-    Self::SosStages<Self::OutputBufs<[F; 3]>>: ArrayMin1<
+    T::SosStages<T::OutputBufs<[F; 3]>>: ArrayMin1<
         Elem = [[F; 3]; OUTPUT_BUFS],
         Min1 = [[[F; 3]; OUTPUT_BUFS]; SOS_STAGES_MAX_1]
     >,
@@ -230,306 +240,189 @@ where
             return unsafe {MaybeUninit::uninit().assume_init()}
         }
 
-        fn filter_once_iir<F, const ORDER: usize, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
-            y: &mut [F],
-            w: &mut [[F; ORDER]; A],
-            b: &[[F; ORDER_PLUS_1]; B], // B = A*CHUNKS
-            a: &[[F; ORDER_PLUS_1]; A]
-        )
-        where
-            F: Float + Sum,
-            [[F; ORDER_PLUS_1]; B]: ArrayChunks<[[F; ORDER_PLUS_1]; A], Elem = [F; ORDER_PLUS_1], Rem = [[F; ORDER_PLUS_1]; 0]>,
-            [F; ORDER]: ArrayPlus1<Elem = F, Plus1 = [F; ORDER_PLUS_1]>
-        {
-            assert!(y.len() >= B);
+        self.update_internals(rate);
 
-            if A <= 1
+        #[allow(clippy::type_complexity)]
+        let (internals, _): (&mut rtfinternals!(T), &mut Param<T::Param>) = self.get_internals_mut();
+        let structure = internals.structure();
+        if let StructureInternals::Lattice(cache @ None) = &mut internals.structure
+        {
+            let (b_stages, b_last_stage, b_output) = &internals.b;
+            let mut lattice = (internals.b, internals.a, internals.a);
+            let mut realizable = true;
             {
-                if let Some(((w, a), x)) = w.first_mut()
-                    .zip(a.first())
-                    .zip(y.first()
-                        .copied()
-                    )
+                let ((l_stages, l_last_stage, l_output), k, c) = &mut lattice;
+                for (((a_stages, a_output), (k_stages, k_output)), (c_stages, c_output)) in internals.a.iter()
+                    .zip(k.iter_mut())
+                    .zip(c.iter_mut())
                 {
-                    let mut a = a.iter()
-                        .copied();
-                    let a0 = a.next().unwrap();
-
-                    let w0 = x - w.iter()
-                        .copied()
-                        .zip(a)
-                        .map(|(w, a)| w*a)
-                        .sum::<F>()/a0;
-
-                    for (b, y) in b.iter()
-                        .zip(y.iter_mut())
+                    for (s, (b_stage, l_stage)) in b_stages.iter()
+                        .zip(l_stages.iter_mut())
+                        .enumerate()
                     {
-                        *y = core::iter::once(w0)
-                            .chain(w.iter()
-                                .copied()
-                            ).zip(b.iter()
-                                .copied()
-                            ).map(|(w, b)| w*b)
-                            .reduce(Add::add)
-                            .unwrap()/a0;
+                        realizable &= structure::lattice_coeffs::<F, 3, {SOS_BUFS}, {SOS_BUFS}>(
+                            b_stage,
+                            &a_stages[s],
+                            l_stage,
+                            &mut k_stages[s],
+                            &mut c_stages[s]
+                        )
                     }
-                    
-                    w.rotate_right(1);
-                    if let Some(w_first) = w.first_mut()
+                    if let Some((b_stage, l_stage)) = b_last_stage.first()
+                        .zip(l_last_stage.first_mut())
                     {
-                        *w_first = w0;
+                        realizable &= structure::lattice_coeffs::<F, 3, {OUTPUT_BUFS}, {SOS_BUFS}>(
+                            b_stage,
+                            &a_stages[SOS_STAGES - 1],
+                            l_stage,
+                            &mut k_stages[SOS_STAGES - 1],
+                            &mut c_stages[SOS_STAGES - 1]
+                        )
                     }
-                }
-            }
-            else if B/A <= 1
-            {
-                for (((b, w), a), y) in b.iter()
-                    .zip(w.iter_mut())
-                    .zip(a.iter())
-                    .zip(y.iter_mut())
-                {
-                    let mut a = a.iter()
-                        .copied();
-                    let a0 = a.next().unwrap();
-
-                    let w0 = *y - w.iter()
-                        .copied()
-                        .zip(a)
-                        .map(|(w, a)| w*a)
-                        .sum::<F>()/a0;
-
-                    *y = core::iter::once(w0)
-                        .chain(w.iter()
-                            .copied()
-                        ).zip(b.iter()
-                            .copied()
-                        ).map(|(w, b)| w*b)
-                        .reduce(Add::add)
-                        .unwrap()/a0;
-                    
-                    w.rotate_right(1);
-                    if let Some(w_first) = w.first_mut()
-                    {
-                        *w_first = w0;
-                    }
-                }
-            }
-            else
-            {
-                let mut j = A;
-                let mut i = B;
-                for ((b, w), a) in b.chunks_exact(B/A)
-                    .rev()
-                    .zip(w.iter_mut()
-                        .rev()
-                    ).zip(a.iter()
-                        .rev()
+                    realizable &= structure::lattice_coeffs::<F, {ORDER_PLUS_1}, {OUTPUTS}, {OUTPUT_BUFS}>(
+                        b_output,
+                        a_output,
+                        l_output,
+                        k_output,
+                        c_output
                     )
-                {
-                    let mut a = a.iter()
-                        .copied();
-                    let a0 = a.next().unwrap();
-
-                    j -= 1;
-                    let w0 = y[j] - w.iter()
-                        .copied()
-                        .zip(a)
-                        .map(|(w, a)| w*a)
-                        .sum::<F>()/a0;
-
-                    for b in b.iter()
-                        .rev()
-                    {
-                        i -= 1;
-                        y[i] = core::iter::once(w0)
-                            .chain(w.iter()
-                                .copied()
-                            ).zip(b.iter()
-                                .copied()
-                            ).map(|(w, b)| w*b)
-                            .reduce(Add::add)
-                            .unwrap()/a0;
-                    }
-                    
-                    w.rotate_right(1);
-                    if let Some(w_first) = w.first_mut()
-                    {
-                        *w_first = w0;
-                    }
                 }
             }
+            *cache = Some(realizable.then_some(lattice))
         }
-        
-        fn filter_once_fir<F, const ORDER: usize, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
-            y: &mut [F],
-            w: &mut [[F; ORDER]; A],
-            b: &[[F; ORDER_PLUS_1]; B]
-        )
-        where
-            F: Float + Sum,
-            [[F; ORDER_PLUS_1]; B]: ArrayChunks<[[F; ORDER_PLUS_1]; A], Elem = [F; ORDER_PLUS_1], Rem = [[F; ORDER_PLUS_1]; 0]>,
-            [F; ORDER]: ArrayPlus1<Elem = F, Plus1 = [F; ORDER_PLUS_1]>
-        {
-            assert!(y.len() >= B);
 
-            if A <= 1
-            {
-                if let Some((w, w0)) = w.first_mut()
-                    .zip(y.first()
-                        .copied()
-                    )
-                {
-                    for (b, y) in b.iter()
-                        .zip(y.iter_mut())
-                    {
-                        *y = core::iter::once(w0)
-                            .chain(w.iter()
-                                .copied()
-                            ).zip(b.iter()
-                                .copied()
-                            ).map(|(w, b)| w*b)
-                            .reduce(Add::add)
-                            .unwrap();
-                    }
-                    
-                    w.rotate_right(1);
-                    if let Some(w_first) = w.first_mut()
-                    {
-                        *w_first = w0;
-                    }
-                }
-            }
-            else if B/A <= 1
-            {
-                for ((b, w), y) in b.iter()
-                    .zip(w.iter_mut())
-                    .zip(y.iter_mut())
-                {
-                    let w0 = *y;
-
-                    *y = core::iter::once(w0)
-                        .chain(w.iter()
-                            .copied()
-                        ).zip(b.iter()
-                            .copied()
-                        ).map(|(w, b)| w*b)
-                        .reduce(Add::add)
-                        .unwrap();
-                    
-                    w.rotate_right(1);
-                    if let Some(w_first) = w.first_mut()
-                    {
-                        *w_first = w0;
-                    }
-                }
-            }
-            else
+        let denormal_protection = internals.denormal_protection;
+        let offset = denormal_protection.offset(&mut internals.seed);
+        let inject = |y: &mut [F; OUTPUTS]| if !offset.is_zero()
+        {
+            for y in y.iter_mut()
             {
-                let mut j = A;
-                let mut i = B;
-                for (b, w) in b.chunks(B/A)
-                    .rev()
-                    .zip(w.iter_mut()
-                        .rev()
-                    )
-                {
-                    j -= 1;
-                    let w0 = y[j];
-
-                    for b in b.iter()
-                        .rev()
-                    {
-                        i -= 1;
-                        y[i] = core::iter::once(w0)
-                            .chain(w.iter()
-                                .copied()
-                            ).zip(b.iter()
-                                .copied()
-                            ).map(|(w, b)| w*b)
-                            .reduce(Add::add)
-                            .unwrap();
-                    }
-                    
-                    w.rotate_right(1);
-                    if let Some(w_first) = w.first_mut()
-                    {
-                        *w_first = w0;
-                    }
-                }
+                *y = *y + offset
             }
-        }
-        
-        self.update_internals(rate);
+        };
 
         #[allow(clippy::type_complexity)]
-        let (internals, _): (&mut rtfinternals!(Self), &mut Param<T::Param>) = self.get_internals_mut();
-        #[allow(clippy::type_complexity)]
-        let (w, b, a): (&mut winternals!(Self), &binternals!(Self), &Self::IsIir<ainternals!(Self)>) = (&mut internals.w, &internals.b, &internals.a);
+        let (w, b, a): (&mut winternals!(T), &binternals!(T), &T::IsIir<ainternals!(T)>) = (
+            &mut internals.w,
+            &internals.b,
+            &internals.a
+        );
         let (w_stages, w_output) = w;
-        let (w_stages, w_last_stage) = w_stages.split_at_mut(<Self as StaticRtf>::SosStages::<()>::LENGTH.saturating_sub(1));
+        let (w_stages, w_last_stage) = w_stages.split_at_mut(<T as StaticRtf>::SosStages::<()>::LENGTH.saturating_sub(1));
         let (b_stages, b_last_stage, b_output) = b;
+        #[allow(clippy::type_complexity)]
+        let (v, lattice): (Option<&mut binternals!(T)>, _) = match &mut internals.structure
+        {
+            StructureInternals::DirectFormI(v) | StructureInternals::TransposedDirectFormII(v) => (Some(v), None),
+            StructureInternals::Lattice(lattice) => (None, lattice.as_ref()
+                .and_then(Option::as_ref)
+                .and_then(|((l_stages, l_last_stage, l_output), k, c)| Some(((l_stages, l_last_stage, l_output), k.first()?, c.first()?)))),
+            StructureInternals::DirectFormII => (None, None)
+        };
+        let (mut v_stages, v_last_stage, v_output) = match v
+        {
+            Some((v_stages, v_last_stage, v_output)) => (Some(v_stages), Some(v_last_stage), Some(v_output)),
+            None => (None, None, None)
+        };
         
         let mut y = [x; OUTPUTS];
 
         if let Some((a_stages, a_output)) = a.first()
         {
-            let (a_stages, a_last_stage) = a_stages.split_at(<Self as StaticRtf>::SosStages::<()>::LENGTH.saturating_sub(1));
-            for ((w_stage, b_stage), a_stage) in w_stages.iter_mut()
+            let (a_stages, a_last_stage) = a_stages.split_at(<T as StaticRtf>::SosStages::<()>::LENGTH.saturating_sub(1));
+            for (s, ((w_stage, b_stage), a_stage)) in w_stages.iter_mut()
                 .zip(b_stages.iter())
                 .zip(a_stages.iter())
+                .enumerate()
             {
-                filter_once_iir::<F, 2, 3, {SOS_BUFS}, {SOS_BUFS}>(
+                inject(&mut y);
+                structure::filter_once_iir::<F, 2, 3, {SOS_BUFS}, {SOS_BUFS}>(
+                    structure,
                     &mut y,
                     w_stage,
+                    v_stages.as_deref_mut().map(|v_stages| &mut v_stages[s]),
                     b_stage,
-                    a_stage
+                    a_stage,
+                    lattice.map(|((l_stages, _, _), (k_stages, _), (c_stages, _))| (&l_stages[s], &k_stages[s], &c_stages[s]))
                 )
             }
             if let Some(((w_stage, b_stage), a_stage)) = w_last_stage.first_mut()
                 .zip(b_last_stage.first())
                 .zip(a_last_stage.first())
             {
-                filter_once_iir::<F, 2, 3, {OUTPUT_BUFS}, {SOS_BUFS}>(
+                inject(&mut y);
+                structure::filter_once_iir::<F, 2, 3, {OUTPUT_BUFS}, {SOS_BUFS}>(
+                    structure,
                     &mut y,
                     w_stage,
+                    v_last_stage.and_then(|v_last_stage| v_last_stage.first_mut()),
                     b_stage,
-                    a_stage
+                    a_stage,
+                    lattice.map(|((_, l_last_stage, _), (k_stages, _), (c_stages, _))| (
+                        &l_last_stage[0],
+                        &k_stages[SOS_STAGES - 1],
+                        &c_stages[SOS_STAGES - 1]
+                    ))
                 )
             }
-            filter_once_iir::<F, {ORDER}, {ORDER_PLUS_1}, {OUTPUTS}, {OUTPUT_BUFS}>(
+            inject(&mut y);
+            structure::filter_once_iir::<F, {ORDER}, {ORDER_PLUS_1}, {OUTPUTS}, {OUTPUT_BUFS}>(
+                structure,
                 &mut y,
                 w_output,
+                v_output,
                 b_output,
-                a_output
+                a_output,
+                lattice.map(|((_, _, l_output), (_, k_output), (_, c_output))| (l_output, k_output, c_output))
             )
         }
         else
         {
-            for (w_stage, b_stage) in w_stages.iter_mut()
+            for (s, (w_stage, b_stage)) in w_stages.iter_mut()
                 .zip(b_stages.iter())
+                .enumerate()
             {
-                filter_once_fir::<F, 2, 3, {SOS_BUFS}, {SOS_BUFS}>(
+                inject(&mut y);
+                structure::filter_once_fir::<F, 2, 3, {SOS_BUFS}, {SOS_BUFS}>(
+                    structure,
                     &mut y,
                     w_stage,
+                    v_stages.as_deref_mut().map(|v_stages| &mut v_stages[s]),
                     b_stage
                 )
             }
             if let Some((w_stage, b_stage)) = w_last_stage.first_mut()
                 .zip(b_last_stage.first())
             {
-                filter_once_fir::<F, 2, 3, {OUTPUT_BUFS}, {SOS_BUFS}>(
+                inject(&mut y);
+                structure::filter_once_fir::<F, 2, 3, {OUTPUT_BUFS}, {SOS_BUFS}>(
+                    structure,
                     &mut y,
                     w_stage,
+                    v_last_stage.and_then(|v_last_stage| v_last_stage.first_mut()),
                     b_stage
                 )
             }
-            filter_once_fir::<F, {ORDER}, {ORDER_PLUS_1}, {OUTPUTS}, {OUTPUT_BUFS}>(
+            inject(&mut y);
+            structure::filter_once_fir::<F, {ORDER}, {ORDER_PLUS_1}, {OUTPUTS}, {OUTPUT_BUFS}>(
+                structure,
                 &mut y,
                 w_output,
+                v_output,
                 b_output
             )
         }
 
+        let (w_stages, w_output) = &mut internals.w;
+        denormal_protection.flush(w_stages.as_flattened_mut().as_flattened_mut());
+        denormal_protection.flush(w_output.as_flattened_mut());
+        if let Some((v_stages, v_last_stage, v_output)) = internals.structure.v_mut()
+        {
+            denormal_protection.flush(v_stages.as_flattened_mut().as_flattened_mut());
+            denormal_protection.flush(v_last_stage.as_flattened_mut().as_flattened_mut());
+            denormal_protection.flush(v_output.as_flattened_mut());
+        }
+
         y
     }
     
@@ -779,11 +672,17 @@ where
     
     fn reset(&mut self)
     {
-        let w = &mut self.get_internals_mut().0.w;
+        self.get_internals_mut().0.reset()
+    }
 
-        unsafe {
-            core::ptr::write_bytes(w as *mut winternals!(Self), 0u8, 1)
-        }
+    fn denormal_protection(&self) -> DenormalProtection<Self::F>
+    {
+        self.get_internals().0.denormal_protection
+    }
+
+    fn set_denormal_protection(&mut self, denormal_protection: DenormalProtection<Self::F>)
+    {
+        self.get_internals_mut().0.denormal_protection = denormal_protection
     }
 }
 
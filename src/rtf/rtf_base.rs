@@ -0,0 +1,22 @@
+use array_trait::Array;
+
+use crate::{conf, param::FilterFloat};
+
+/// The parameter and the outputs of a filter.
+///
+/// This is shared by all filters, both those that run on cached coefficients (see [`StaticRtf`](crate::rtf::StaticRtf)), and those that run in a topology of
+/// their own, like the [`MoogLadderFilter`](crate::filters::iir::fourth::MoogLadderFilter), which implement [`Rtf`](crate::rtf::Rtf) directly.
+pub trait RtfBase: Sized
+{
+    type Param;
+
+    type Conf: conf::Conf;
+    type F: FilterFloat;
+
+    type Outputs<U>: Array<Elem = U>;
+
+    fn from_param(param: Self::Param) -> Self;
+    fn get_param(&self) -> &Self::Param;
+    fn get_param_mut(&mut self) -> &mut Self::Param;
+    fn into_param(self) -> Self::Param;
+}
\ No newline at end of file
@@ -0,0 +1,467 @@
+use core::f64::consts::TAU;
+
+use num::{Complex, Float};
+
+use crate::f;
+
+const MAX_ITERATIONS: usize = 100;
+
+/// Factors a transfer function `b(z⁻¹)/a(z⁻¹)` into `S` second-order sections, followed by a remainder section of order `R - 1` (a first-order section for
+/// odd orders, or just a gain for even orders).
+///
+/// Returns `((b_sections, b_remainder), (a_sections, a_remainder))`, in the order they should be applied, which is the same layout as the SOS stages and the
+/// output stage of [`make_coeffs`](crate::rtf::StaticRtf::make_coeffs) for a filter with `SOS_STAGES = S` and `ORDER = R - 1`.
+///
+/// Returns `None` if the coefficients aren't finite, or if the sections aren't, which happens when the roots or the gain overflow [`f64`], like for
+/// coefficients that span more than its range.
+///
+/// Poles and zeros are found numerically in [`f64`], and then paired up like this:
+///
+/// - For odd orders, the real pole furthest from the unit circle is put in the remainder section, together with the real zero closest to it.
+/// - The remaining poles are grouped into sections, starting with the ones closest to the unit circle, which are placed last. Each section gets the zeros
+///   closest to its poles.
+/// - The gain of the whole filter is put in the first section.
+///
+/// Finding the roots costs far more than computing the coefficients of a filter. When it's done in
+/// [`make_coeffs`](crate::rtf::StaticRtf::make_coeffs), it's paid every time the coefficients change, which is every sample for a filter that's swept.
+///
+/// # Example
+///
+/// ```rust
+/// use real_time_fir_iir_filters::rtf::factor_sos;
+///
+/// // (1 + z⁻¹)³/((1 - 0.5z⁻¹)(1 - 0.9z⁻¹ + 0.81z⁻²))
+/// let b = [1.0, 3.0, 3.0, 1.0];
+/// let a = [1.0, -1.4, 1.26, -0.405];
+///
+/// let (([b_sos], b_first), ([a_sos], a_first)) = factor_sos::<f64, 4, 1, 2>(&b, &a).unwrap();
+///
+/// // The complex poles in the section, and the real pole in the first order remainder
+/// assert!((a_sos[1] + 0.9).abs() < 1e-9 && (a_sos[2] - 0.81).abs() < 1e-9);
+/// assert!((a_first[1] + 0.5).abs() < 1e-9);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn factor_sos<F, const N: usize, const S: usize, const R: usize>(b: &[F; N], a: &[F; N]) -> Option<(([[F; 3]; S], [F; R]), ([[F; 3]; S], [F; R]))>
+where
+    F: Float
+{
+    let () = Shape::<N, S, R>::VALID;
+
+    if b.iter()
+        .chain(a.iter())
+        .any(|c| !c.is_finite())
+    {
+        return None
+    }
+
+    let b = b.map(|b| f!(b; f64));
+    let a = a.map(|a| f!(a; f64));
+
+    let (mut zeros, b_lead) = roots(&b);
+    let (poles, a_lead) = roots(&a);
+    // A causal filter has no poles at infinity
+    let mut poles = poles.map(|p| p.unwrap_or_default());
+    let gain = match (b_lead, a_lead)
+    {
+        (Some(b_lead), Some(a_lead)) => b_lead/a_lead,
+        _ => 0.0
+    };
+    let mut zeros = &mut zeros[..N - 1];
+    let mut poles = &mut poles[..N - 1];
+
+    let mut b_sos = [[0.0; 3]; S];
+    let mut a_sos = [[0.0; 3]; S];
+    let mut b_rem = [0.0; R];
+    let mut a_rem = [0.0; R];
+    b_rem[0] = 1.0;
+    a_rem[0] = 1.0;
+
+    if R == 2
+    {
+        let p = take_where(&mut poles, |p| p.im == 0.0, |p| -(p.norm() - 1.0).abs())?;
+        let z = take_where(&mut zeros, |z| z.is_none_or(|z| z.im == 0.0), |z| distance(z, p))?;
+        b_rem[..2].copy_from_slice(&first_order(z));
+        a_rem[..2].copy_from_slice(&first_order(Some(p)));
+    }
+
+    for (b_sos, a_sos) in b_sos.iter_mut()
+        .zip(a_sos.iter_mut())
+        .rev()
+    {
+        let p1 = take_where(&mut poles, |_| true, |p| (p.norm() - 1.0).abs())?;
+        let p2 = if p1.im != 0.0
+        {
+            take_where(&mut poles, |p| *p == p1.conj(), |_| 0.0)?
+        }
+        else
+        {
+            take_where(&mut poles, |p| p.im == 0.0, |p| (p - p1).norm())?
+        };
+        let z1 = take_where(&mut zeros, |_| true, |z| distance(z, p1))?;
+        let z2 = match z1
+        {
+            Some(z1) if z1.im != 0.0 => take_where(&mut zeros, |z| *z == Some(z1.conj()), |_| 0.0)?,
+            _ => take_where(&mut zeros, |z| z.is_none_or(|z| z.im == 0.0), |z| distance(z, p1))?
+        };
+        *b_sos = second_order(z1, z2);
+        *a_sos = second_order(Some(p1), Some(p2));
+    }
+
+    match b_sos.first_mut()
+    {
+        Some(b_first) => *b_first = b_first.map(|b| b*gain),
+        None => b_rem = b_rem.map(|b| b*gain)
+    }
+
+    if b_sos.iter()
+        .chain(a_sos.iter())
+        .flatten()
+        .chain(b_rem.iter())
+        .chain(a_rem.iter())
+        .any(|c| !c.is_finite())
+    {
+        return None
+    }
+
+    Some((
+        (b_sos.map(|b| b.map(|b| f!(b; F))), b_rem.map(|b| f!(b; F))),
+        (a_sos.map(|a| a.map(|a| f!(a; F))), a_rem.map(|a| f!(a; F)))
+    ))
+}
+
+struct Shape<const N: usize, const S: usize, const R: usize>;
+
+impl<const N: usize, const S: usize, const R: usize> Shape<N, S, R>
+{
+    /// Fails to compile if the sections don't fit the coefficients.
+    const VALID: () = {
+        assert!(R == 1 || R == 2, "The remainder section must be of order zero or one");
+        assert!(N == 2*S + R, "The number of coefficients must match the sections");
+    };
+}
+
+/// Distance from a zero to a pole, where zeros at infinity are the furthest away.
+fn distance(z: &Option<Complex<f64>>, p: Complex<f64>) -> f64
+{
+    match z
+    {
+        Some(z) => (z - p).norm(),
+        None => f64::INFINITY
+    }
+}
+
+/// Removes the root that minimizes `cost` among the ones matching `filter`, if there is one.
+fn take_where<T, C>(roots: &mut &mut [T], filter: impl Fn(&T) -> bool, cost: C) -> Option<T>
+where
+    T: Copy,
+    C: Fn(&T) -> f64
+{
+    let i = roots.iter()
+        .enumerate()
+        .filter(|(_, r)| filter(r))
+        .min_by(|(_, r1), (_, r2)| cost(r1).total_cmp(&cost(r2)))
+        .map(|(i, _)| i)?;
+    let last = roots.len() - 1;
+    roots.swap(i, last);
+    let (rest, taken) = core::mem::take(roots).split_at_mut(last);
+    *roots = rest;
+    Some(taken[0])
+}
+
+fn first_order(r: Option<Complex<f64>>) -> [f64; 2]
+{
+    match r
+    {
+        Some(r) => [1.0, -r.re],
+        None => [0.0, 1.0]
+    }
+}
+
+fn second_order(r1: Option<Complex<f64>>, r2: Option<Complex<f64>>) -> [f64; 3]
+{
+    match (r1, r2)
+    {
+        (Some(r1), Some(r2)) => [1.0, -(r1 + r2).re, (r1*r2).re],
+        (Some(r), None) | (None, Some(r)) => [0.0, 1.0, -r.re],
+        (None, None) => [0.0, 0.0, 1.0]
+    }
+}
+
+/// Finds the roots of `c[0] zᴺ⁻¹ + c[1] zᴺ⁻² + ... + c[N - 1]`, which are the roots of the polynomial `c[0] + c[1] z⁻¹ + ... + c[N - 1] z¹⁻ᴺ` as well.
+///
+/// Missing leading coefficients give roots at infinity (`None`), and the last element is always left as `None`. Complex roots are made exact conjugate pairs, and
+/// the rest are made real. Also returns the first non-zero coefficient, if any.
+fn roots<const N: usize>(c: &[f64; N]) -> ([Option<Complex<f64>>; N], Option<f64>)
+{
+    let mut roots = [None; N];
+
+    let lead = c.iter()
+        .position(|&c| c != 0.0);
+    let Some(lead) = lead
+    else
+    {
+        return (roots, None)
+    };
+    let trail = c.iter()
+        .rev()
+        .position(|&c| c != 0.0)
+        .unwrap();
+    let p = &c[lead..N - trail];
+    let degree = p.len() - 1;
+
+    let mut z = [Complex::from(0.0); N];
+    aberth(p, &mut z[..degree]);
+    merge_clusters::<N>(p, &mut z[..degree]);
+    make_conjugate(&mut z[..degree]);
+
+    for (root, z) in roots[lead..N - 1].iter_mut()
+        .zip(z[..degree].iter()
+            .copied()
+            .chain(core::iter::repeat(Complex::from(0.0)))
+        )
+    {
+        *root = Some(z)
+    }
+
+    (roots, Some(c[lead]))
+}
+
+/// Aberth-Ehrlich iteration for all roots of a polynomial at once.
+fn aberth(p: &[f64], z: &mut [Complex<f64>])
+{
+    let degree = z.len();
+    if degree == 0
+    {
+        return
+    }
+
+    let radius = Float::powf((p[degree]/p[0]).abs(), 1.0/degree as f64);
+    for (k, z) in z.iter_mut()
+        .enumerate()
+    {
+        *z = Complex::from_polar(radius, TAU*k as f64/degree as f64 + 0.4)
+    }
+
+    for _ in 0..MAX_ITERATIONS
+    {
+        let mut converged = true;
+        for k in 0..degree
+        {
+            let (y, dy) = p.iter()
+                .fold((Complex::from(0.0), Complex::from(0.0)), |(y, dy), &p| (y*z[k] + p, dy*z[k] + y));
+            if y == Complex::from(0.0)
+            {
+                continue
+            }
+            let ratio = y/dy;
+            let repulsion = (0..degree).filter(|&j| j != k)
+                .map(|j| (z[k] - z[j]).inv())
+                .fold(Complex::from(0.0), |s, r| s + r);
+            let w = ratio/(Complex::from(1.0) - ratio*repulsion);
+            if !w.is_finite()
+            {
+                continue
+            }
+            z[k] -= w;
+            if w.norm() > 4.0*f64::EPSILON*z[k].norm()
+            {
+                converged = false
+            }
+        }
+        if converged
+        {
+            break
+        }
+    }
+}
+
+/// Replaces clusters of roots with one multiple root.
+///
+/// Multiple roots (like the zeros at `z = -1` of a bilinear-transformed low-pass filter) can only be found to about `ε^(1/m)` directly. A root of multiplicity
+/// `m` is a simple root of the `(m - 1)`-th derivative though, so it's polished there with Newton's method, starting from the mean of the cluster.
+fn merge_clusters<const N: usize>(p: &[f64], z: &mut [Complex<f64>])
+{
+    const TOLERANCE: f64 = 1e-4;
+
+    let near = |zi: Complex<f64>, zj: Complex<f64>| (zj - zi).norm() <= TOLERANCE*(1.0 + zi.norm());
+
+    for i in 0..z.len()
+    {
+        let zi = z[i];
+        let (sum, m) = z.iter()
+            .filter(|&&zj| near(zi, zj))
+            .fold((Complex::from(0.0), 0), |(sum, m), &zj| (sum + zj, m + 1));
+        if m <= 1
+        {
+            continue
+        }
+
+        // Coefficients of the (m - 1)-th derivative, in descending powers
+        let degree = p.len() - 1;
+        let mut dp = [0.0; N];
+        for (k, dp) in dp[..degree + 2 - m].iter_mut()
+            .enumerate()
+        {
+            *dp = p[k]*((degree + 2 - m - k)..=(degree - k)).map(|n| n as f64).product::<f64>()
+        }
+        let dp = &dp[..degree + 2 - m];
+
+        let mut root = sum/m as f64;
+        for _ in 0..MAX_ITERATIONS
+        {
+            let (y, dy) = dp.iter()
+                .fold((Complex::from(0.0), Complex::from(0.0)), |(y, dy), &p| (y*root + p, dy*root + y));
+            let step = y/dy;
+            if !step.is_finite()
+            {
+                break
+            }
+            root -= step;
+            if step.norm() <= 4.0*f64::EPSILON*root.norm()
+            {
+                break
+            }
+        }
+
+        for zj in z.iter_mut()
+        {
+            if near(zi, *zj)
+            {
+                *zj = root
+            }
+        }
+    }
+}
+
+/// Pairs up the most complex roots with the roots closest to their conjugates, and makes the odd ones out real.
+fn make_conjugate(z: &mut [Complex<f64>])
+{
+    const TOLERANCE: f64 = 1e-9;
+
+    let mut rest = z;
+    while let Some(i) = rest.iter()
+        .enumerate()
+        .max_by(|(_, z1), (_, z2)| z1.im.abs().total_cmp(&z2.im.abs()))
+        .map(|(i, _)| i)
+    {
+        rest.swap(0, i);
+        let (z1, tail) = rest.split_first_mut().unwrap();
+        if z1.im.abs() <= TOLERANCE*(1.0 + z1.norm())
+        {
+            for z in core::iter::once(z1).chain(tail.iter_mut())
+            {
+                z.im = 0.0
+            }
+            return
+        }
+        let partner = tail.iter()
+            .enumerate()
+            .min_by(|(_, z2), (_, z3)| (**z2 - z1.conj()).norm().total_cmp(&(**z3 - z1.conj()).norm()))
+            .map(|(j, _)| j);
+        match partner
+        {
+            Some(j) => {
+                tail.swap(0, j);
+                let z2 = &mut tail[0];
+                let z = Complex::new((z1.re + z2.re)/2.0, (z1.im - z2.im).abs()/2.0);
+                *z1 = z;
+                *z2 = z.conj();
+                rest = &mut rest[2..];
+            },
+            None => {
+                z1.im = 0.0;
+                rest = &mut rest[1..];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use num::Complex;
+
+    use super::factor_sos;
+
+    fn eval(c: &[f64], z: Complex<f64>) -> Complex<f64>
+    {
+        c.iter()
+            .rev()
+            .fold(Complex::from(0.0), |y, &c| y*z.inv() + c)
+    }
+
+    fn assert_factors<const N: usize, const S: usize, const R: usize>(b: [f64; N], a: [f64; N]) -> [[f64; 3]; S]
+    {
+        let ((b_sos, b_rem), (a_sos, a_rem)) = factor_sos::<f64, N, S, R>(&b, &a).unwrap();
+
+        for omega in (0..32).map(|i| i as f64/32.0*TAU/2.0)
+        {
+            let z = Complex::cis(omega);
+            let h = eval(&b, z)/eval(&a, z);
+            let h_sos = b_sos.iter()
+                .zip(a_sos.iter())
+                .map(|(b, a)| eval(b, z)/eval(a, z))
+                .fold(eval(&b_rem, z)/eval(&a_rem, z), |h, h_sos| h*h_sos);
+            assert!((h - h_sos).norm() <= 1e-9*(1.0 + h.norm()), "{h} != {h_sos}");
+        }
+
+        a_sos
+    }
+
+    #[test]
+    fn factors()
+    {
+        // (1 + z⁻¹)³/((1 - 0.5z⁻¹)(1 - 0.9z⁻¹ + 0.81z⁻²))
+        assert_factors::<4, 1, 2>([1.0, 3.0, 3.0, 1.0], [1.0, -1.4, 1.26, -0.405]);
+
+        // Pure delay in the numerator, and a pole at the origin
+        assert_factors::<4, 1, 2>([0.0, 0.5, 0.0, 0.0], [1.0, -0.5, 0.0, 0.0]);
+
+        // Two resonances, and zeros at DC and nyquist
+        let p1 = Complex::from_polar(0.99, 0.01);
+        let p2 = Complex::from_polar(0.7, 1.2);
+        let a1 = [1.0, -2.0*p1.re, p1.norm_sqr()];
+        let a2 = [1.0, -2.0*p2.re, p2.norm_sqr()];
+        let a = [
+            a1[0]*a2[0],
+            a1[0]*a2[1] + a1[1]*a2[0],
+            a1[0]*a2[2] + a1[1]*a2[1] + a1[2]*a2[0],
+            a1[1]*a2[2] + a1[2]*a2[1],
+            a1[2]*a2[2]
+        ];
+        let a_sos = assert_factors::<5, 2, 1>([0.1, 0.0, -0.2, 0.0, 0.1], a);
+
+        // Sections are ordered with the poles closest to the unit circle last
+        let radii = a_sos.map(|a| a[2].abs().sqrt());
+        assert!(radii.is_sorted());
+    }
+
+    #[cfg(feature = "wah")]
+    #[test]
+    fn wah()
+    {
+        use crate::{calc::iir::fourth::WahCalc, param::CrybabyGCB95};
+
+        for x in [0.0, 0.3, 0.9]
+        {
+            let calc = WahCalc::new(&CrybabyGCB95 {x}, 44100.0);
+            assert_factors::<5, 2, 1>(calc.b(), calc.a());
+        }
+    }
+
+    #[test]
+    fn unfactorable()
+    {
+        assert!(factor_sos::<f64, 3, 1, 1>(&[1.0, f64::NAN, 0.0], &[1.0, 0.0, 0.0]).is_none());
+
+        // Poles and zeros beyond the range of f64
+        assert!(factor_sos::<f64, 3, 1, 1>(&[1.0, 0.0, 0.0], &[1e-300, 0.0, 1e300]).is_none());
+        assert!(factor_sos::<f64, 3, 1, 1>(&[1e-300, 0.0, 1e300], &[1.0, 0.0, 0.0]).is_none());
+        assert!(factor_sos::<f64, 4, 1, 2>(&[1.0, 0.0, 0.0, 0.0], &[1e-200, 1.0, 0.0, 1e200]).is_none());
+
+        // A gain beyond the range of f64
+        assert!(factor_sos::<f64, 3, 1, 1>(&[1e300, 0.0, 0.0], &[1e-300, 0.0, 0.0]).is_none());
+    }
+}
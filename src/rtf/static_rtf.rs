@@ -1,24 +1,17 @@
-use crate::{conf, param::{FilterFloat, Param}, util::{ArrayChunks, ArrayMin1, ArrayMinus1, ArrayPlus1, BoolArray}};
+use crate::{param::Param, rtf::{RtfBase, Structure}, util::{ArrayChunks, ArrayMin1, ArrayMinus1, ArrayPlus1, BoolArray}};
 
-pub trait StaticRtf: Sized
+/// A filter that runs on coefficients, which are cached in its [internals](crate::internals::RtfInternals), and only recalculated when the parameter or the
+/// sample rate changes.
+///
+/// [`Rtf`](crate::rtf::Rtf) is implemented for all of these, in any of the realization [structures](Structure).
+pub trait StaticRtf: RtfBase
 {
-    type Param;
-
-    type Conf: conf::Conf;
-    type F: FilterFloat;
-    
-    type Outputs<U>: ArrayChunks<Self::OutputBufs<U>, Elem = U, Rem = [U; 0]>;
     type IsIir<U>: BoolArray<Elem = U>;
     type Order<U>: ArrayPlus1<Elem = U>;
     type OutputBufs<U>: ArrayChunks<Self::SosBufs<U>, Elem = U, Rem = [U; 0]>;
     type SosBufs<U>: ArrayChunks<Self::SosBufs<U>, Elem = U, Rem = [U; 0], Chunks = [Self::SosBufs<U>; 1]>;
     type SosStages<U>: ArrayMin1<Elem = U> + ArrayMinus1<Elem = U>;
     
-    fn from_param(param: Self::Param) -> Self;
-    fn get_param(&self) -> &Self::Param;
-    fn get_param_mut(&mut self) -> &mut Self::Param;
-    fn into_param(self) -> Self::Param;
-    
     #[allow(clippy::type_complexity)]
     fn get_internals(&self) -> (&crate::rtfinternals!(Self), &Param<Self::Param>);
     #[allow(clippy::type_complexity)]
@@ -34,4 +27,18 @@ pub trait StaticRtf: Sized
     {
         crate::internals::update(self, rate)
     }
+
+    /// Returns the realization structure the filter is currently running in.
+    fn structure(&self) -> Structure
+    {
+        self.get_internals().0.structure()
+    }
+
+    /// Selects the realization structure to run the filter in.
+    /// 
+    /// The internal state of the filter is reset if the structure changes, since it means different things for different structures.
+    fn set_structure(&mut self, structure: Structure)
+    {
+        self.get_internals_mut().0.set_structure(structure)
+    }
 }
\ No newline at end of file
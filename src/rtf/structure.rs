@@ -0,0 +1,691 @@
+use core::{iter::Sum, ops::Add};
+
+use num::Float;
+
+use crate::{f, util::{ArrayChunks, ArrayPlus1}};
+
+/// The realization structure used when running a filter's difference equations.
+///
+/// All structures realize the same transfer function, so [`z_response`](crate::rtf::Rtf::z_response) is the same for all of them, but they differ in how
+/// rounding errors build up in the filter state. This matters mostly for filters with poles close to the unit circle, like low-frequency or high-Q filters in
+/// [`f32`].
+///
+/// The structure can be selected per filter with [`StaticRtf::set_structure`](crate::rtf::StaticRtf::set_structure).
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use core::f32::consts::TAU;
+///
+/// use real_time_fir_iir_filters::{
+///     conf::LowPass,
+///     param::OmegaZeta,
+///     rtf::{Rtf, StaticRtf, Structure},
+///     filters::iir::second::SecondOrderFilter
+/// };
+///
+/// // Initialize a resonant 2. order low-pass filter at 20Hz
+/// let mut filter = SecondOrderFilter::<LowPass, f32>::new(
+///     OmegaZeta {
+///         omega: 20.0*TAU,
+///         zeta: 0.05
+///     }
+/// );
+///
+/// filter.set_structure(Structure::TransposedDirectFormII);
+///
+/// let [y] = filter.filter(44100.0, 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Structure
+{
+    /// Direct form I.
+    ///
+    /// Keeps separate delay lines for the input and the output of each section, so there is no internal gain before the zeros are applied.
+    DirectFormI,
+    /// Direct form II.
+    ///
+    /// Shares one delay line between the poles and the zeros of each section (Figure 6.5, Alan V. Oppenheimer & Ronald W. Schafer - Discrete-Time Signal
+    /// Processing). This needs the least state, and is the default.
+    #[default]
+    DirectFormII,
+    /// Transposed direct form II.
+    ///
+    /// Applies the zeros before the poles, with one delay line per output.
+    TransposedDirectFormII,
+    /// Normalized lattice-ladder form (Gray & Markel).
+    ///
+    /// Realizes the poles as a cascade of energy-preserving rotations, derived from the reflection coefficients of the denominator, and the zeros as a weighted
+    /// sum of the lattice states. The coefficients are derived from the cached direct form coefficients whenever they change.
+    ///
+    /// Deriving them takes a step-down recursion per section, which costs about as much as running the filter for `ORDER` samples. It's done on the first
+    /// sample after the coefficients change, so with parameters that change every sample, like a swept filter, every sample pays for it. Prefer one of the
+    /// direct forms for those.
+    ///
+    /// Only stable denominators can be realized this way. Filters with poles on or outside the unit circle (like the integrator of a
+    /// [`PIFilter`](crate::filters::iir::first::PIFilter)) fall back to [`DirectFormII`](Structure::DirectFormII).
+    Lattice
+}
+
+/// Coefficients of the normalized lattice form for one section: ladder weights for each numerator, and reflection coefficients with their complements for each
+/// denominator.
+pub(crate) type LatticeCoeffs<'a, F, const ORDER_PLUS_1: usize, const B: usize, const A: usize> = (
+    &'a [[F; ORDER_PLUS_1]; B],
+    &'a [[F; ORDER_PLUS_1]; A],
+    &'a [[F; ORDER_PLUS_1]; A]
+);
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn filter_once_iir<F, const ORDER: usize, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
+    structure: Structure,
+    y: &mut [F],
+    w: &mut [[F; ORDER]; A],
+    v: Option<&mut [[F; ORDER_PLUS_1]; B]>,
+    b: &[[F; ORDER_PLUS_1]; B], // B = A*CHUNKS
+    a: &[[F; ORDER_PLUS_1]; A],
+    lattice: Option<LatticeCoeffs<F, ORDER_PLUS_1, B, A>>
+)
+where
+    F: Float + Sum,
+    [[F; ORDER_PLUS_1]; B]: ArrayChunks<[[F; ORDER_PLUS_1]; A], Elem = [F; ORDER_PLUS_1], Rem = [[F; ORDER_PLUS_1]; 0]>,
+    [F; ORDER]: ArrayPlus1<Elem = F, Plus1 = [F; ORDER_PLUS_1]>
+{
+    match (structure, v, lattice)
+    {
+        (Structure::DirectFormI, Some(v), _) => direct_form_i_iir(y, w, v, b, a),
+        (Structure::TransposedDirectFormII, Some(v), _) => transposed_direct_form_ii_iir(y, v, b, a),
+        (Structure::Lattice, _, Some((l, k, c))) => lattice_iir(y, w, l, k, c),
+        _ => direct_form_ii_iir(y, w, b, a)
+    }
+}
+
+pub(crate) fn filter_once_fir<F, const ORDER: usize, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
+    structure: Structure,
+    y: &mut [F],
+    w: &mut [[F; ORDER]; A],
+    v: Option<&mut [[F; ORDER_PLUS_1]; B]>,
+    b: &[[F; ORDER_PLUS_1]; B] // B = A*CHUNKS
+)
+where
+    F: Float + Sum,
+    [[F; ORDER_PLUS_1]; B]: ArrayChunks<[[F; ORDER_PLUS_1]; A], Elem = [F; ORDER_PLUS_1], Rem = [[F; ORDER_PLUS_1]; 0]>,
+    [F; ORDER]: ArrayPlus1<Elem = F, Plus1 = [F; ORDER_PLUS_1]>
+{
+    // Without feedback, direct form I and II are the same, and there is no lattice to realize.
+    match (structure, v)
+    {
+        (Structure::TransposedDirectFormII, Some(v)) => transposed_direct_form_ii_fir::<F, ORDER_PLUS_1, B, A>(y, v, b),
+        _ => direct_form_ii_fir(y, w, b)
+    }
+}
+
+/// Computes the normalized lattice coefficients of a section, returning `false` if any of its denominators is not strictly stable.
+/// 
+/// This is done in [`f64`] regardless of `F`, since the reflection coefficients of poles close to `z = 1` are very sensitive to rounding.
+pub(crate) fn lattice_coeffs<F, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
+    b: &[[F; ORDER_PLUS_1]; B],
+    a: &[[F; ORDER_PLUS_1]; A],
+    l: &mut [[F; ORDER_PLUS_1]; B],
+    k: &mut [[F; ORDER_PLUS_1]; A],
+    c: &mut [[F; ORDER_PLUS_1]; A]
+) -> bool
+where
+    F: Float
+{
+    if A == 0 || ORDER_PLUS_1 == 0
+    {
+        return true
+    }
+    let order = ORDER_PLUS_1 - 1;
+    let chunk = B/A;
+
+    for (j, ((a, k), c)) in a.iter()
+        .zip(k.iter_mut())
+        .zip(c.iter_mut())
+        .enumerate()
+    {
+        let a0 = f!(a[0]; f64);
+
+        // Step-down recursion, keeping every lower order denominator for the ladder
+        let mut a_m = [[0.0; ORDER_PLUS_1]; ORDER_PLUS_1];
+        let mut c_m = [1.0; ORDER_PLUS_1];
+        a_m[order] = a.map(|a| f!(a; f64)/a0);
+        for m in (1..=order).rev()
+        {
+            let k_m = a_m[m][m];
+            let d = (1.0 - k_m)*(1.0 + k_m);
+            if d.is_nan() || d <= 0.0
+            {
+                return false
+            }
+            c_m[m] = Float::sqrt(d);
+            k[m] = f!(k_m; F);
+            c[m] = f!(c_m[m]; F);
+            for i in 0..m
+            {
+                a_m[m - 1][i] = (a_m[m][i] - k_m*a_m[m][m - i])/d;
+            }
+        }
+        k[0] = F::zero();
+        c[0] = F::one();
+
+        for (b, l) in b[j*chunk..(j + 1)*chunk].iter()
+            .zip(l[j*chunk..(j + 1)*chunk].iter_mut())
+        {
+            // Peel off one ladder weight per order, scaled by the gain of the normalized rotations below it
+            let mut r = b.map(|b| f!(b; f64)/a0);
+            let mut scale = 1.0;
+            for m in (0..=order).rev()
+            {
+                let l_m = r[m];
+                for i in 0..=m
+                {
+                    r[i] -= l_m*a_m[m][m - i];
+                }
+                l[m] = f!(l_m/scale; F);
+                scale *= c_m[m];
+            }
+        }
+    }
+
+    true
+}
+
+/// Shifts a new value into the start of a delay line.
+fn shift_in<F>(w: &mut [F], x: F)
+{
+    if let Some(w_last) = w.last_mut()
+    {
+        *w_last = x;
+        w.rotate_right(1)
+    }
+}
+
+fn direct_form_i_iir<F, const ORDER: usize, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
+    y: &mut [F],
+    w: &mut [[F; ORDER]; A],
+    v: &mut [[F; ORDER_PLUS_1]; B],
+    b: &[[F; ORDER_PLUS_1]; B],
+    a: &[[F; ORDER_PLUS_1]; A]
+)
+where
+    F: Float + Sum
+{
+    assert!(y.len() >= B);
+    if A == 0
+    {
+        return
+    }
+    let chunk = B/A;
+
+    for (j, (w, a)) in w.iter_mut()
+        .zip(a.iter())
+        .enumerate()
+        .rev()
+    {
+        let x = y[j];
+        let mut a = a.iter()
+            .copied();
+        let a0 = a.next().unwrap();
+
+        for i in (j*chunk..(j + 1)*chunk).rev()
+        {
+            let v = &mut v[i][..ORDER];
+            let yi = (core::iter::once(x)
+                .chain(w.iter()
+                    .copied()
+                ).zip(b[i].iter()
+                    .copied()
+                ).map(|(x, b)| x*b)
+                .reduce(Add::add)
+                .unwrap() - v.iter()
+                .copied()
+                .zip(a.clone())
+                .map(|(y, a)| y*a)
+                .sum::<F>())/a0;
+
+            shift_in(v, yi);
+            y[i] = yi;
+        }
+
+        shift_in(w, x);
+    }
+}
+
+fn transposed_direct_form_ii_iir<F, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
+    y: &mut [F],
+    v: &mut [[F; ORDER_PLUS_1]; B],
+    b: &[[F; ORDER_PLUS_1]; B],
+    a: &[[F; ORDER_PLUS_1]; A]
+)
+where
+    F: Float
+{
+    assert!(y.len() >= B);
+    if A == 0
+    {
+        return
+    }
+    let chunk = B/A;
+
+    for (j, a) in a.iter()
+        .enumerate()
+        .rev()
+    {
+        let x = y[j];
+        let a0 = a[0];
+
+        for i in (j*chunk..(j + 1)*chunk).rev()
+        {
+            // The last element of the state is always zero
+            let (b, s) = (&b[i], &mut v[i]);
+            let yi = (b[0]*x + s[0])/a0;
+            for k in 1..ORDER_PLUS_1
+            {
+                s[k - 1] = b[k]*x - a[k]*yi + s[k];
+            }
+            y[i] = yi;
+        }
+    }
+}
+
+fn lattice_iir<F, const ORDER: usize, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
+    y: &mut [F],
+    w: &mut [[F; ORDER]; A],
+    l: &[[F; ORDER_PLUS_1]; B],
+    k: &[[F; ORDER_PLUS_1]; A],
+    c: &[[F; ORDER_PLUS_1]; A]
+)
+where
+    F: Float
+{
+    assert!(y.len() >= B);
+    if A == 0
+    {
+        return
+    }
+    let chunk = B/A;
+
+    for (j, ((w, k), c)) in w.iter_mut()
+        .zip(k.iter())
+        .zip(c.iter())
+        .enumerate()
+        .rev()
+    {
+        // Forward and backward waves through each rotation
+        let mut f = y[j];
+        let mut g = [F::zero(); ORDER_PLUS_1];
+        for m in (1..ORDER_PLUS_1).rev()
+        {
+            let s = w[m - 1];
+            g[m] = k[m]*f + c[m]*s;
+            f = c[m]*f - k[m]*s;
+        }
+        g[0] = f;
+
+        for i in (j*chunk..(j + 1)*chunk).rev()
+        {
+            y[i] = g.iter()
+                .zip(l[i].iter())
+                .map(|(&g, &l)| g*l)
+                .fold(F::zero(), Add::add);
+        }
+
+        for (w, g) in w.iter_mut()
+            .zip(g)
+        {
+            *w = g;
+        }
+    }
+}
+
+fn transposed_direct_form_ii_fir<F, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
+    y: &mut [F],
+    v: &mut [[F; ORDER_PLUS_1]; B],
+    b: &[[F; ORDER_PLUS_1]; B]
+)
+where
+    F: Float
+{
+    assert!(y.len() >= B);
+    if A == 0
+    {
+        return
+    }
+    let chunk = B/A;
+
+    for j in (0..A).rev()
+    {
+        let x = y[j];
+
+        for i in (j*chunk..(j + 1)*chunk).rev()
+        {
+            // The last element of the state is always zero
+            let (b, s) = (&b[i], &mut v[i]);
+            let yi = b[0]*x + s[0];
+            for k in 1..ORDER_PLUS_1
+            {
+                s[k - 1] = b[k]*x + s[k];
+            }
+            y[i] = yi;
+        }
+    }
+}
+
+fn direct_form_ii_iir<F, const ORDER: usize, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
+    y: &mut [F],
+    w: &mut [[F; ORDER]; A],
+    b: &[[F; ORDER_PLUS_1]; B], // B = A*CHUNKS
+    a: &[[F; ORDER_PLUS_1]; A]
+)
+where
+    F: Float + Sum,
+    [[F; ORDER_PLUS_1]; B]: ArrayChunks<[[F; ORDER_PLUS_1]; A], Elem = [F; ORDER_PLUS_1], Rem = [[F; ORDER_PLUS_1]; 0]>,
+    [F; ORDER]: ArrayPlus1<Elem = F, Plus1 = [F; ORDER_PLUS_1]>
+{
+    assert!(y.len() >= B);
+
+    if A <= 1
+    {
+        if let Some(((w, a), x)) = w.first_mut()
+            .zip(a.first())
+            .zip(y.first()
+                .copied()
+            )
+        {
+            let mut a = a.iter()
+                .copied();
+            let a0 = a.next().unwrap();
+
+            let w0 = x - w.iter()
+                .copied()
+                .zip(a)
+                .map(|(w, a)| w*a)
+                .sum::<F>()/a0;
+
+            for (b, y) in b.iter()
+                .zip(y.iter_mut())
+            {
+                *y = core::iter::once(w0)
+                    .chain(w.iter()
+                        .copied()
+                    ).zip(b.iter()
+                        .copied()
+                    ).map(|(w, b)| w*b)
+                    .reduce(Add::add)
+                    .unwrap()/a0;
+            }
+            
+            shift_in(w, w0);
+        }
+    }
+    else if B/A <= 1
+    {
+        for (((b, w), a), y) in b.iter()
+            .zip(w.iter_mut())
+            .zip(a.iter())
+            .zip(y.iter_mut())
+        {
+            let mut a = a.iter()
+                .copied();
+            let a0 = a.next().unwrap();
+
+            let w0 = *y - w.iter()
+                .copied()
+                .zip(a)
+                .map(|(w, a)| w*a)
+                .sum::<F>()/a0;
+
+            *y = core::iter::once(w0)
+                .chain(w.iter()
+                    .copied()
+                ).zip(b.iter()
+                    .copied()
+                ).map(|(w, b)| w*b)
+                .reduce(Add::add)
+                .unwrap()/a0;
+            
+            shift_in(w, w0);
+        }
+    }
+    else
+    {
+        let mut j = A;
+        let mut i = B;
+        for ((b, w), a) in b.chunks_exact(B/A)
+            .rev()
+            .zip(w.iter_mut()
+                .rev()
+            ).zip(a.iter()
+                .rev()
+            )
+        {
+            let mut a = a.iter()
+                .copied();
+            let a0 = a.next().unwrap();
+
+            j -= 1;
+            let w0 = y[j] - w.iter()
+                .copied()
+                .zip(a)
+                .map(|(w, a)| w*a)
+                .sum::<F>()/a0;
+
+            for b in b.iter()
+                .rev()
+            {
+                i -= 1;
+                y[i] = core::iter::once(w0)
+                    .chain(w.iter()
+                        .copied()
+                    ).zip(b.iter()
+                        .copied()
+                    ).map(|(w, b)| w*b)
+                    .reduce(Add::add)
+                    .unwrap()/a0;
+            }
+            
+            shift_in(w, w0);
+        }
+    }
+}
+
+fn direct_form_ii_fir<F, const ORDER: usize, const ORDER_PLUS_1: usize, const B: usize, const A: usize>(
+    y: &mut [F],
+    w: &mut [[F; ORDER]; A],
+    b: &[[F; ORDER_PLUS_1]; B]
+)
+where
+    F: Float + Sum,
+    [[F; ORDER_PLUS_1]; B]: ArrayChunks<[[F; ORDER_PLUS_1]; A], Elem = [F; ORDER_PLUS_1], Rem = [[F; ORDER_PLUS_1]; 0]>,
+    [F; ORDER]: ArrayPlus1<Elem = F, Plus1 = [F; ORDER_PLUS_1]>
+{
+    assert!(y.len() >= B);
+
+    if A <= 1
+    {
+        if let Some((w, w0)) = w.first_mut()
+            .zip(y.first()
+                .copied()
+            )
+        {
+            for (b, y) in b.iter()
+                .zip(y.iter_mut())
+            {
+                *y = core::iter::once(w0)
+                    .chain(w.iter()
+                        .copied()
+                    ).zip(b.iter()
+                        .copied()
+                    ).map(|(w, b)| w*b)
+                    .reduce(Add::add)
+                    .unwrap();
+            }
+            
+            shift_in(w, w0);
+        }
+    }
+    else if B/A <= 1
+    {
+        for ((b, w), y) in b.iter()
+            .zip(w.iter_mut())
+            .zip(y.iter_mut())
+        {
+            let w0 = *y;
+
+            *y = core::iter::once(w0)
+                .chain(w.iter()
+                    .copied()
+                ).zip(b.iter()
+                    .copied()
+                ).map(|(w, b)| w*b)
+                .reduce(Add::add)
+                .unwrap();
+            
+            shift_in(w, w0);
+        }
+    }
+    else
+    {
+        let mut j = A;
+        let mut i = B;
+        for (b, w) in b.chunks(B/A)
+            .rev()
+            .zip(w.iter_mut()
+                .rev()
+            )
+        {
+            j -= 1;
+            let w0 = y[j];
+
+            for b in b.iter()
+                .rev()
+            {
+                i -= 1;
+                y[i] = core::iter::once(w0)
+                    .chain(w.iter()
+                        .copied()
+                    ).zip(b.iter()
+                        .copied()
+                    ).map(|(w, b)| w*b)
+                    .reduce(Add::add)
+                    .unwrap();
+            }
+            
+            shift_in(w, w0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use crate::{conf::{All, LowPass}, filters::iir::{first::PIFilter, second::SecondOrderFilter, third::ThirdOrderSallenKeyFilter}, internals::StructureInternals, param::{OmegaZeta, RC3GSallenKey, PI}, rtf::{Rtf, StaticRtf}};
+
+    use super::Structure;
+
+    const STRUCTURES: [Structure; 4] = [
+        Structure::DirectFormI,
+        Structure::DirectFormII,
+        Structure::TransposedDirectFormII,
+        Structure::Lattice
+    ];
+    const RATE: f64 = 44100.0;
+    const N: usize = 256;
+
+    fn assert_impulse_responses_match<T, const OUTPUTS: usize>(mut filter: T)
+    where
+        T: Rtf + StaticRtf<F = f64, Outputs<f64> = [f64; OUTPUTS]>
+    {
+        let responses = STRUCTURES.map(|structure| {
+            filter.set_structure(structure);
+            core::array::from_fn::<_, N, _>(|n| filter.filter(RATE, if n == 0 {1.0} else {0.0}))
+        });
+        let [_, reference, ..] = &responses;
+        let peak = reference.iter()
+            .flatten()
+            .fold(0.0f64, |peak, y| peak.max(y.abs()));
+
+        for (structure, response) in STRUCTURES.into_iter()
+            .zip(responses.iter())
+        {
+            for (y, y_ref) in response.iter()
+                .flatten()
+                .zip(reference.iter()
+                    .flatten()
+                )
+            {
+                assert!((y - y_ref).abs() <= peak*1e-9, "{structure:?}: {y} != {y_ref}")
+            }
+        }
+    }
+
+    #[test]
+    fn impulse_responses()
+    {
+        assert_impulse_responses_match(SecondOrderFilter::<All>::new(OmegaZeta {omega: 1000.0*TAU, zeta: 0.1}));
+        assert_impulse_responses_match(ThirdOrderSallenKeyFilter::<All>::new(
+            RC3GSallenKey {r1: 47e3, c1: 470e-9, r2: 150e3, c2: 27e-9, r3: 160e3, c3: 27e-9, g: 1.38}
+        ));
+
+        // Can't be realized as a lattice, so it falls back to direct form II
+        let mut filter = PIFilter::new(PI {p: 1.0, i: 100.0});
+        assert_impulse_responses_match(filter);
+        filter.set_structure(Structure::Lattice);
+        filter.filter(RATE, 1.0);
+        assert_eq!(filter.internals.structure, StructureInternals::Lattice(Some(None)));
+    }
+
+    #[test]
+    fn noise_floors()
+    {
+        const OMEGA: f64 = 20.0*TAU;
+        const ZETA: f64 = 0.05;
+        const M: usize = 1 << 16;
+
+        let mut reference = SecondOrderFilter::<LowPass>::new(OmegaZeta {omega: OMEGA, zeta: ZETA});
+        let mut filter = SecondOrderFilter::<LowPass, f32>::new(OmegaZeta {omega: OMEGA as f32, zeta: ZETA as f32});
+
+        // The single precision filter is compared to a double precision filter with the same (rounded) coefficients, so only rounding in the signal path counts
+        reference.filter(RATE, 0.0);
+        filter.filter(RATE as f32, 0.0);
+        reference.internals.b.2 = filter.internals.b.2.map(|b| b.map(|b| b as f64));
+        reference.internals.a[0].1 = filter.internals.a[0].1.map(|a| a.map(|a| a as f64));
+        reference.param.set_unchanged();
+        filter.param.set_unchanged();
+
+        let mut seed = 1u32;
+        let x: Vec<f64> = (0..M).map(|_| {
+                seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+                (seed >> 8) as f64/(1u32 << 24) as f64 - 0.5
+            }).collect();
+
+        reference.reset();
+        let y_ref: Vec<f64> = x.iter()
+            .map(|&x| reference.filter(RATE, x)[0])
+            .collect();
+        let power = y_ref.iter().map(|y| y*y).sum::<f64>()/M as f64;
+
+        let noise_floors = STRUCTURES.map(|structure| {
+            filter.set_structure(structure);
+            filter.reset();
+            let noise = x.iter()
+                .zip(y_ref.iter())
+                .map(|(&x, y_ref)| (filter.filter(RATE as f32, x as f32)[0] as f64 - y_ref).powi(2))
+                .sum::<f64>()/M as f64;
+            10.0*(noise/power).log10()
+        });
+
+        for (structure, noise_floor) in STRUCTURES.into_iter()
+            .zip(noise_floors)
+        {
+            assert!(noise_floor < -45.0, "{structure:?}: {noise_floor:.1} dB")
+        }
+        let [_, direct_form_ii, _, lattice] = noise_floors;
+        assert!(lattice < direct_form_ii - 10.0, "lattice: {lattice:.1} dB, direct form II: {direct_form_ii:.1} dB")
+    }
+}
@@ -1,57 +1,145 @@
-use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::{de::IgnoredAny, Serialize, Deserialize, Serializer, Deserializer};
 
-pub(crate) trait DeserializeOrZeroed<'de>: Sized
+pub(crate) trait MaybeSerialize
+{
+    fn maybe_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+impl<T> MaybeSerialize for T
+where
+    T: ?Sized
+{
+    default fn maybe_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        serializer.serialize_none()
+    }
+}
+impl<T> MaybeSerialize for T
+where
+    T: Serialize + ?Sized
+{
+    fn maybe_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        self.serialize(serializer)
+    }
+}
+
+/// Deserializes a `T`, if `T` is deserializable. Otherwise, the value is skipped, and `None` is returned.
+pub(crate) trait MaybeDeserialize<'de>: Sized
 {
-    fn deserialize_or_zeroed<D>(deserializer: D) -> Result<Self, D::Error>
+    fn maybe_deserialize<D>(deserializer: D) -> Result<Option<Self>, D::Error>
     where
         D: Deserializer<'de>;
 }
-impl<'de, T> DeserializeOrZeroed<'de> for T
+impl<'de, T> MaybeDeserialize<'de> for T
 {
-    default fn deserialize_or_zeroed<D>(_: D) -> Result<Self, D::Error>
+    default fn maybe_deserialize<D>(deserializer: D) -> Result<Option<Self>, D::Error>
     where
         D: Deserializer<'de>
     {
-        Ok(unsafe {core::mem::zeroed()})
+        IgnoredAny::deserialize(deserializer)?;
+        Ok(None)
     }
 }
-impl<'de, T> DeserializeOrZeroed<'de> for T
+impl<'de, T> MaybeDeserialize<'de> for T
 where
     T: Deserialize<'de>
 {
-    fn deserialize_or_zeroed<D>(deserializer: D) -> Result<Self, D::Error>
+    fn maybe_deserialize<D>(deserializer: D) -> Result<Option<Self>, D::Error>
     where
         D: Deserializer<'de>
     {
-        Ok(Self::deserialize(deserializer).unwrap_or_else(|_| unsafe {core::mem::zeroed()}))
+        Self::deserialize(deserializer).map(Some)
     }
 }
 
-pub(crate) trait MaybeSerialize
-{
-    fn maybe_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer;
-}
-impl<T> MaybeSerialize for T
+/// Serializes a field with [`MaybeSerialize`], for deriving serde on structs of types that might not support it.
+pub(crate) struct MaybeSerialized<'a, T>(pub &'a T)
+where
+    T: ?Sized;
+impl<T> Serialize for MaybeSerialized<'_, T>
 where
     T: ?Sized
 {
-    default fn maybe_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer
     {
-        serializer.serialize_none()
+        self.0.maybe_serialize(serializer)
     }
 }
-impl<T> MaybeSerialize for T
-where
-    T: Serialize + ?Sized
+
+/// Deserializes a field with [`MaybeDeserialize`], for deriving serde on structs of types that might not support it.
+pub(crate) struct MaybeDeserialized<T>(pub Option<T>);
+impl<'de, T> Deserialize<'de> for MaybeDeserialized<T>
 {
-    fn maybe_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        T::maybe_deserialize(deserializer).map(Self)
+    }
+}
+
+/// Serializes arrays of any length as tuples, since serde only implements it for arrays of up to 32 elements. For use with `#[serde(with = "...")]`.
+#[cfg(any(feature = "comb", feature = "state_space"))]
+pub(crate) mod array
+{
+    use core::{fmt, marker::PhantomData};
+
+    use serde::{de::{Error, SeqAccess, Visitor}, ser::SerializeTuple, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(crate) fn serialize<T, S, const N: usize>(array: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
     where
+        T: Serialize,
         S: Serializer
     {
-        self.serialize(serializer)
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for x in array.iter()
+        {
+            tuple.serialize_element(x)?;
+        }
+        tuple.end()
+    }
+
+    pub(crate) fn deserialize<'de, T, D, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>
+    {
+        struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+        impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+        where
+            T: Deserialize<'de>
+        {
+            type Value = [T; N];
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result
+            {
+                write!(formatter, "an array of length {N}")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>
+            {
+                core::array::try_from_fn(|i| seq.next_element()?
+                    .ok_or_else(|| Error::invalid_length(i, &self))
+                )
+            }
+        }
+
+        deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
     }
-}
\ No newline at end of file
+}
+
+/// An array of any length, serialized with [`array`], for arrays nested in arrays.
+#[cfg(any(feature = "comb", feature = "state_space"))]
+#[derive(Serialize, Deserialize)]
+#[serde(transparent, bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))]
+pub(crate) struct SerdeArray<T, const N: usize>(#[serde(with = "array")] pub [T; N]);
\ No newline at end of file
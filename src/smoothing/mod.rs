@@ -0,0 +1,6 @@
+moddef::moddef!(
+    flat(pub) mod {
+        one_euro_filter for cfg(feature = "smoothing"),
+        slew_rate_limiter for cfg(feature = "smoothing")
+    }
+);
\ No newline at end of file
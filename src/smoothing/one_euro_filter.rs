@@ -0,0 +1,172 @@
+use array_trait::AsSlice;
+use num::{Float, One, Zero};
+
+use crate::{change::Change, conf::LowPass, filters::iir::first::FirstOrderFilter, param::{Omega, OmegaFirstOrder, OneEuro, Param}, rtf::Rtf};
+
+/// The 1€ filter by Casiez, Roussel and Vogel, for smoothing jittery sensor data, like motion capture or the pots of a pedal.
+///
+/// A low-pass filter, whose cutoff frequency rises with the speed of the signal. When the signal is still, the cutoff stays at `min_omega`,
+/// which removes the jitter. When it moves, the cutoff rises by `beta` times the speed, which keeps the lag low. The speed is estimated by differentiating
+/// the signal, and smoothing the result with a second low-pass filter at `derivative_omega`.
+///
+/// <pre>
+/// ω = ω<sub>min</sub> + β|dx/dt|
+/// </pre>
+///
+/// To tune it, first set `beta` to zero and lower `min_omega` until the jitter is gone while holding still, then raise `beta` until the lag is acceptable
+/// while moving.
+///
+/// The cutoff is driven through the `Omega` of a [`FirstOrderFilter<LowPass>`](FirstOrderFilter) by default, but any low-pass filter whose parameter can be
+/// [changed](Change) to an `Omega` will do, like a [`SecondOrderFilter<LowPass>`](crate::filters::iir::second::SecondOrderFilter) for a steeper roll-off.
+/// Only the first output of the filter is used. The filter starts settled on its first sample, so there's no initial sweep up from zero.
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(generic_const_exprs)]
+///
+/// use core::f64::consts::TAU;
+///
+/// use real_time_fir_iir_filters::{
+///     conf::LowPass,
+///     param::{Omega, OneEuro},
+///     filters::iir::first::FirstOrderFilter,
+///     smoothing::OneEuroFilter
+/// };
+///
+/// // Sensor data at 120 Hz
+/// let mut filter = OneEuroFilter::new(
+///     FirstOrderFilter::<LowPass>::new(Omega {omega: 1.0*TAU}),
+///     OneEuro {min_omega: 1.0*TAU, beta: 0.01, derivative_omega: 1.0*TAU}
+/// );
+///
+/// let position = filter.filter(120.0, 0.5);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OneEuroFilter<R = FirstOrderFilter<LowPass>>
+where
+    R: Rtf
+{
+    pub param: Param<OneEuro<R::F>>,
+    /// Smooths the signal, with its cutoff driven by the speed.
+    pub filter: R,
+    /// Smooths the speed estimate.
+    pub derivative: R,
+    omega: R::F,
+    origin: Option<R::F>,
+    x_prev: R::F
+}
+
+impl<R> OneEuroFilter<R>
+where
+    R: Rtf + Clone,
+    R::Param: Change<OmegaFirstOrder<R::F>, F = R::F>
+{
+    /// Creates a new 1€ filter, using copies of the given low-pass filter to smooth both the signal and its speed.
+    pub fn new(filter: R, param: OneEuro<R::F>) -> Self
+    {
+        let mut filter = filter;
+        filter.reset();
+        let mut one_euro = Self {
+            param: Param::new(param),
+            derivative: filter.clone(),
+            filter,
+            omega: param.min_omega,
+            origin: None,
+            x_prev: Zero::zero()
+        };
+        one_euro.set_omega(param.min_omega);
+        one_euro
+    }
+
+    fn set_omega(&mut self, omega: R::F)
+    {
+        self.filter.get_param_mut().change(Omega {omega}, One::one())
+    }
+
+    /// Feeds a single sample through the filter, and returns the smoothed value.
+    pub fn filter(&mut self, rate: R::F, x: R::F) -> R::F
+    {
+        let OneEuro {min_omega, beta, derivative_omega} = *self.param;
+
+        // Run both filters relative to the first sample, so that they start out settled on it
+        let origin = *self.origin.get_or_insert(x);
+        let x = x - origin;
+
+        let dx = (x - self.x_prev)*rate;
+        self.x_prev = x;
+        if !self.param.is_unchanged_then_set()
+        {
+            self.derivative.get_param_mut().change(Omega {omega: derivative_omega}, One::one())
+        }
+        let dx = self.derivative.filter(rate, dx).as_slice()[0];
+
+        self.omega = min_omega + beta*dx.abs();
+        self.set_omega(self.omega);
+        let y = self.filter.filter(rate, x).as_slice()[0];
+
+        y + origin
+    }
+
+    /// Returns the cutoff frequency, in radians per second, that the last sample was filtered with.
+    pub fn omega(&self) -> R::F
+    {
+        self.omega
+    }
+
+    pub fn reset(&mut self)
+    {
+        self.filter.reset();
+        self.derivative.reset();
+        self.omega = self.param.min_omega;
+        self.set_omega(self.omega);
+        self.origin = None;
+        self.x_prev = Zero::zero()
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use core::f64::consts::TAU;
+
+    use crate::{conf::LowPass, filters::iir::first::FirstOrderFilter, param::{Omega, OneEuro}};
+
+    use super::OneEuroFilter;
+
+    #[test]
+    fn jitter_and_lag()
+    {
+        const RATE: f64 = 120.0;
+
+        let param = OneEuro {min_omega: 1.0*TAU, beta: 5.0, derivative_omega: 1.0*TAU};
+        let jitter = |n: usize| 0.01*(-1f64).powi(n as i32);
+
+        // Holding still, the jitter is mostly removed, and it starts settled
+        let lowpass = FirstOrderFilter::<LowPass>::new(Omega {omega: 1.0});
+        let mut filter = OneEuroFilter::new(lowpass, param);
+        let mut max_error: f64 = 0.0;
+        for n in 0..(RATE as usize)
+        {
+            let y = filter.filter(RATE, 3.0 + jitter(n));
+            max_error = max_error.max((y - 3.0).abs());
+        }
+        assert!(max_error < 0.011, "{max_error}");
+        assert!((filter.filter(RATE, 3.0) - 3.0).abs() < 1e-3);
+        assert!((filter.omega() - param.min_omega).abs() < 1.0);
+
+        // Moving fast, the cutoff rises, and it lags much less than a fixed cutoff would
+        let mut fixed = OneEuroFilter::new(lowpass, OneEuro {beta: 0.0, ..param});
+        filter.reset();
+        let ramp = |n: usize| 10.0*n as f64/RATE;
+        let (mut y, mut y_fixed) = (0.0, 0.0);
+        for n in 0..(RATE as usize)
+        {
+            y = filter.filter(RATE, ramp(n) + jitter(n));
+            y_fixed = fixed.filter(RATE, ramp(n) + jitter(n));
+        }
+        let target = ramp(RATE as usize - 1);
+        assert!(filter.omega() > 5.0*param.min_omega);
+        assert!((target - y).abs() < 0.2*(target - y_fixed).abs(), "{y} {y_fixed} {target}");
+    }
+}
\ No newline at end of file
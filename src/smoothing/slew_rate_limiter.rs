@@ -0,0 +1,102 @@
+use crate::param::{FilterFloat, Param, SlewRate};
+
+/// Limits how fast a signal can rise and fall, like the slew rate of an op-amp, or the lag of a portamento.
+///
+/// Steps in the input turn into ramps, while signals that are slow enough pass through untouched. This tames sudden jumps in sensor data, such as a pot
+/// with a dirty track, or zipper noise when a parameter is moved in coarse steps.
+///
+/// The limiter starts out on its first sample.
+///
+/// # Example
+///
+/// ```rust
+/// use real_time_fir_iir_filters::{
+///     param::SlewRate,
+///     smoothing::SlewRateLimiter
+/// };
+///
+/// // Rise by at most 2 per second, and fall by at most 10 per second
+/// let mut limiter = SlewRateLimiter::new(SlewRate {rise: 2.0, fall: 10.0});
+///
+/// let y = limiter.filter(1000.0, 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SlewRateLimiter<F = f64>
+where
+    F: FilterFloat
+{
+    pub param: Param<SlewRate<F>>,
+    #[serde(skip)]
+    state: Option<F>
+}
+
+impl<F> SlewRateLimiter<F>
+where
+    F: FilterFloat
+{
+    pub fn new(param: SlewRate<F>) -> Self
+    {
+        Self {
+            param: Param::new(param),
+            state: None
+        }
+    }
+
+    /// Feeds a single sample through the limiter, and returns the limited value.
+    pub fn filter(&mut self, rate: F, x: F) -> F
+    {
+        let SlewRate {rise, fall} = *self.param;
+        let y = match self.state
+        {
+            Some(y) => y + (x - y).min(rise/rate).max(-fall/rate),
+            None => x
+        };
+        self.state = Some(y);
+        y
+    }
+
+    pub fn reset(&mut self)
+    {
+        self.state = None
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::param::SlewRate;
+
+    use super::SlewRateLimiter;
+
+    #[test]
+    fn step()
+    {
+        const RATE: f64 = 1000.0;
+
+        let mut limiter = SlewRateLimiter::new(SlewRate {rise: 2.0, fall: 10.0});
+        assert_eq!(limiter.filter(RATE, 1.0), 1.0);
+
+        // A step up takes half a second
+        for n in 1..=500
+        {
+            let y = limiter.filter(RATE, 2.0);
+            assert!((y - (1.0 + n as f64*2.0/RATE)).abs() < 1e-12);
+        }
+        assert!((limiter.filter(RATE, 2.0) - 2.0).abs() < 1e-12);
+
+        // A step down takes a tenth
+        for _ in 0..100
+        {
+            limiter.filter(RATE, 1.0);
+        }
+        assert!((limiter.filter(RATE, 1.0) - 1.0).abs() < 1e-12);
+
+        // Slow signals pass through
+        for n in 0..1000
+        {
+            let x = 1.0 + (n as f64/RATE).sin();
+            assert!((limiter.filter(RATE, x) - x).abs() < 1e-12);
+        }
+    }
+}
\ No newline at end of file
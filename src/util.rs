@@ -1,5 +1,7 @@
 #![allow(unused)]
 
+use core::mem::MaybeUninit;
+
 use array_trait::{Array, AsArray, AsSlice};
 use num::Float;
 
@@ -117,6 +119,17 @@ impl<T> ArrayMin1 for [T; 0]
 {
     type Min1 = [T; 0];
 }
+macro_rules! impl_array_min1 {
+    ($($n:literal),*) => {
+        $(
+            impl<T> ArrayMin1 for [T; $n]
+            {
+                type Min1 = [T; 1];
+            }
+        )*
+    };
+}
+impl_array_min1!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
 
 pub trait ArrayMax<Rhs>: Array
 where
@@ -208,6 +221,41 @@ where
 
 pub trait ZeroSized = SizedAt<0>;
 
+/// Converts an array (possibly nested) of `F1` into an array of the same shape of `F2`.
+///
+/// # Safety
+///
+/// `A` must be an array of `F1`, or an array of such arrays, nested to any depth, and `B` must be the same shape of array of `F2`. Both are then laid out as a
+/// contiguous run of floats, without padding, which is reinterpreted here. Any other type, like a tuple, a struct or a reference, is undefined behaviour,
+/// even if the sizes add up.
+pub(crate) unsafe fn cast_floats<F1, F2, A, B>(from: &A) -> B
+where
+    F1: Float,
+    F2: Float
+{
+    let n = core::mem::size_of::<A>()/core::mem::size_of::<F1>();
+    assert_eq!(n*core::mem::size_of::<F1>(), core::mem::size_of::<A>());
+    assert_eq!(n*core::mem::size_of::<F2>(), core::mem::size_of::<B>());
+
+    let from = unsafe {
+        core::slice::from_raw_parts((from as *const A).cast::<F1>(), n)
+    };
+    let mut to = MaybeUninit::<B>::uninit();
+    for (i, &x) in from.iter()
+        .enumerate()
+    {
+        unsafe {
+            to.as_mut_ptr()
+                .cast::<F2>()
+                .add(i)
+                .write(f!(x; F2))
+        }
+    }
+    unsafe {
+        to.assume_init()
+    }
+}
+
 pub mod same
 {
     mod private